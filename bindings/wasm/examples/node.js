@@ -118,7 +118,7 @@ async function run() {
   })
 
   // The "authentication" key was not compromised so it's safe to publish an update
-  user2.doc.sign(user2.key)
+  user2.doc.sign(user2.key, { resign: true })
 
   user2.message = await Identity.publish(user2.doc.toJSON(), CLIENT_CONFIG)
 
@@ -13,6 +13,7 @@ use identity::credential::Subject;
 use identity::credential::VerifiableCredential as VerifiableCredential_;
 use wasm_bindgen::prelude::*;
 
+use crate::crypto::KeyPair;
 use crate::document::Document;
 use crate::utils::err;
 
@@ -88,6 +89,93 @@ impl VerifiableCredential {
       .map_err(err)
   }
 
+  /// Returns the `id` of the credential, or `null` if not set.
+  #[wasm_bindgen(getter)]
+  pub fn id(&self) -> JsValue {
+    match self.0.id.as_ref() {
+      Some(id) => id.to_string().into(),
+      None => JsValue::NULL,
+    }
+  }
+
+  /// Returns the URL of the credential issuer.
+  #[wasm_bindgen(getter)]
+  pub fn issuer(&self) -> String {
+    self.0.issuer.url().to_string()
+  }
+
+  /// Returns the `id` of every credential subject; subjects without an `id`
+  /// are represented as `null`.
+  #[wasm_bindgen(getter)]
+  pub fn subjects(&self) -> Result<JsValue, JsValue> {
+    let subjects: Vec<Option<String>> = self
+      .0
+      .credential_subject
+      .iter()
+      .map(|subject| subject.id.as_ref().map(ToString::to_string))
+      .collect();
+
+    JsValue::from_serde(&subjects).map_err(err)
+  }
+
+  /// Returns the types of the credential.
+  #[wasm_bindgen(getter)]
+  pub fn types(&self) -> Result<JsValue, JsValue> {
+    let types: Vec<&String> = self.0.types.iter().collect();
+
+    JsValue::from_serde(&types).map_err(err)
+  }
+
+  /// Returns the issuance date of the credential as an RFC 3339 string.
+  #[wasm_bindgen(getter, js_name = issuanceDate)]
+  pub fn issuance_date(&self) -> String {
+    self.0.issuance_date.to_string()
+  }
+
+  /// Returns the expiration date of the credential as an RFC 3339 string, or
+  /// `null` if not set.
+  #[wasm_bindgen(getter, js_name = expirationDate)]
+  pub fn expiration_date(&self) -> JsValue {
+    match self.0.expiration_date {
+      Some(timestamp) => timestamp.to_string().into(),
+      None => JsValue::NULL,
+    }
+  }
+
+  /// Returns `true` if the credential is revoked in the revocation `list`
+  /// referenced by its `RevocationList2020Status`.
+  ///
+  /// The signature of `list` is not verified.
+  #[wasm_bindgen(js_name = isRevoked)]
+  pub fn is_revoked(&self, list: &VerifiableCredential) -> Result<bool, JsValue> {
+    self.0.is_revoked(&*list.0).map_err(err)
+  }
+
+  /// Revokes the credential in the revocation `list`; returns the updated
+  /// list signed by `issuer` with `key`, ready to be published.
+  ///
+  /// The list is signed with the default authentication method of `issuer`
+  /// unless a `method` query is given.
+  #[wasm_bindgen]
+  pub fn revoke(
+    &self,
+    list: &VerifiableCredential,
+    issuer: &Document,
+    key: &KeyPair,
+    method: Option<String>,
+  ) -> Result<VerifiableCredential, JsValue> {
+    let method: String = match method {
+      Some(method) => method,
+      None => issuer.0.authentication().id().to_string(),
+    };
+
+    self
+      .0
+      .revoke(&*list.0, issuer.0.as_document(), method.as_str().into(), key.0.secret())
+      .map_err(err)
+      .map(Self)
+  }
+
   /// Serializes a `VerifiableCredential` object as a JSON object.
   #[wasm_bindgen(js_name = toJSON)]
   pub fn to_json(&self) -> Result<JsValue, JsValue> {
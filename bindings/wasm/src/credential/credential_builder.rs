@@ -0,0 +1,103 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity::core::Timestamp;
+use identity::core::Url;
+use identity::credential::CredentialBuilder as CredentialBuilder_;
+use identity::credential::Status;
+use identity::credential::Subject;
+use identity::credential::VerifiableCredential as VerifiableCredential_;
+use wasm_bindgen::prelude::*;
+
+use crate::credential::VerifiableCredential;
+use crate::utils::err;
+
+/// Assembles an unsigned `VerifiableCredential` from chainable setters.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct CredentialBuilder(CredentialBuilder_);
+
+#[wasm_bindgen]
+impl CredentialBuilder {
+  #[wasm_bindgen(constructor)]
+  pub fn new() -> CredentialBuilder {
+    Self(CredentialBuilder_::default())
+  }
+
+  /// Sets the `id` of the credential.
+  #[wasm_bindgen]
+  pub fn id(self, id: &str) -> Result<CredentialBuilder, JsValue> {
+    let id: Url = Url::parse(id).map_err(err)?;
+
+    Ok(Self(self.0.id(id)))
+  }
+
+  /// Sets the URL of the credential issuer, e.g. the DID of the issuer.
+  #[wasm_bindgen]
+  pub fn issuer(self, issuer: &str) -> Result<CredentialBuilder, JsValue> {
+    let issuer: Url = Url::parse(issuer).map_err(err)?;
+
+    Ok(Self(self.0.issuer(issuer)))
+  }
+
+  /// Adds a credential subject from a JSON object.
+  #[wasm_bindgen]
+  pub fn subject(self, subject: &JsValue) -> Result<CredentialBuilder, JsValue> {
+    let subject: Subject = subject.into_serde().map_err(err)?;
+
+    Ok(Self(self.0.subject(subject)))
+  }
+
+  /// Adds a type to the credential; the base `VerifiableCredential` type is
+  /// always included.
+  #[wasm_bindgen(js_name = type)]
+  pub fn type_(self, value: &str) -> CredentialBuilder {
+    Self(self.0.type_(value))
+  }
+
+  /// Sets the issuance date of the credential from an RFC 3339 timestamp.
+  ///
+  /// Defaults to the current time.
+  #[wasm_bindgen(js_name = issuanceDate)]
+  pub fn issuance_date(self, value: &str) -> Result<CredentialBuilder, JsValue> {
+    let value: Timestamp = Timestamp::parse(value).map_err(err)?;
+
+    Ok(Self(self.0.issuance_date(value)))
+  }
+
+  /// Sets the expiration date of the credential from an RFC 3339 timestamp.
+  #[wasm_bindgen(js_name = expirationDate)]
+  pub fn expiration_date(self, value: &str) -> Result<CredentialBuilder, JsValue> {
+    let value: Timestamp = Timestamp::parse(value).map_err(err)?;
+
+    Ok(Self(self.0.expiration_date(value)))
+  }
+
+  /// Adds a credential status from a JSON object with `id` and `type`.
+  #[wasm_bindgen(js_name = credentialStatus)]
+  pub fn credential_status(self, status: &JsValue) -> Result<CredentialBuilder, JsValue> {
+    let status: Status = status.into_serde().map_err(err)?;
+
+    Ok(Self(self.0.status(status)))
+  }
+
+  /// Returns a new unsigned `VerifiableCredential`.
+  ///
+  /// Fails if the `issuer` or a credential subject is missing.
+  #[wasm_bindgen]
+  pub fn build(&self) -> Result<VerifiableCredential, JsValue> {
+    self
+      .0
+      .clone()
+      .build()
+      .map(|credential| VerifiableCredential_::new(credential, Vec::new()))
+      .map(VerifiableCredential)
+      .map_err(err)
+  }
+}
+
+impl Default for CredentialBuilder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -4,7 +4,11 @@
 #![allow(clippy::module_inception)]
 
 mod credential;
+mod credential_builder;
 mod presentation;
+mod presentation_builder;
 
 pub use self::credential::VerifiableCredential;
+pub use self::credential_builder::CredentialBuilder;
 pub use self::presentation::VerifiablePresentation;
+pub use self::presentation_builder::PresentationBuilder;
@@ -0,0 +1,89 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity::core::Url;
+use identity::credential::PresentationBuilder as PresentationBuilder_;
+use identity::credential::VerifiableCredential;
+use identity::credential::VerifiablePresentation as VerifiablePresentation_;
+use wasm_bindgen::prelude::*;
+
+use crate::credential::VerifiablePresentation;
+use crate::utils::err;
+
+/// Assembles an unsigned `VerifiablePresentation` from chainable setters.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct PresentationBuilder {
+  builder: PresentationBuilder_,
+  holder: bool,
+}
+
+#[wasm_bindgen]
+impl PresentationBuilder {
+  #[wasm_bindgen(constructor)]
+  pub fn new() -> PresentationBuilder {
+    Self {
+      builder: PresentationBuilder_::default(),
+      holder: false,
+    }
+  }
+
+  /// Sets the `id` of the presentation.
+  #[wasm_bindgen]
+  pub fn id(mut self, id: &str) -> Result<PresentationBuilder, JsValue> {
+    self.builder = self.builder.id(Url::parse(id).map_err(err)?);
+
+    Ok(self)
+  }
+
+  /// Sets the URL of the presentation holder, e.g. the DID of the holder.
+  #[wasm_bindgen]
+  pub fn holder(mut self, holder: &str) -> Result<PresentationBuilder, JsValue> {
+    self.builder = self.builder.holder(Url::parse(holder).map_err(err)?);
+    self.holder = true;
+
+    Ok(self)
+  }
+
+  /// Adds a verifiable credential from a JSON object.
+  #[wasm_bindgen]
+  pub fn credential(mut self, credential: &JsValue) -> Result<PresentationBuilder, JsValue> {
+    let credential: VerifiableCredential = credential.into_serde().map_err(err)?;
+
+    self.builder = self.builder.credential(credential);
+
+    Ok(self)
+  }
+
+  /// Adds a type to the presentation; the base `VerifiablePresentation` type
+  /// is always included.
+  #[wasm_bindgen(js_name = type)]
+  pub fn type_(mut self, value: &str) -> PresentationBuilder {
+    self.builder = self.builder.type_(value);
+    self
+  }
+
+  /// Returns a new unsigned `VerifiablePresentation`.
+  ///
+  /// Fails if the `holder` is missing or a credential is invalid.
+  #[wasm_bindgen]
+  pub fn build(&self) -> Result<VerifiablePresentation, JsValue> {
+    if !self.holder {
+      return Err("Missing property: `holder`".into());
+    }
+
+    self
+      .builder
+      .clone()
+      .build()
+      .map(|presentation| VerifiablePresentation_::new(presentation, Vec::new()))
+      .map(VerifiablePresentation)
+      .map_err(err)
+  }
+}
+
+impl Default for PresentationBuilder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -4,9 +4,12 @@
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum Digest {
+  #[serde(rename = "sha256")]
   Sha256 = 1,
+  #[serde(rename = "blake2b-256")]
+  Blake2b256 = 2,
 }
 
 impl Default for Digest {
@@ -3,7 +3,9 @@
 
 use identity::core::decode_b58;
 use identity::core::encode_b58;
+use identity::crypto::merkle_key::Blake2b256;
 use identity::crypto::merkle_key::Sha256;
+use identity::crypto::merkle_tree::Hash;
 use identity::crypto::merkle_tree::Proof;
 use identity::crypto::KeyCollection as KeyCollection_;
 use identity::crypto::PublicKey;
@@ -19,6 +21,8 @@ use crate::utils::err;
 struct JsonData {
   #[serde(rename = "type")]
   type_: KeyType,
+  #[serde(default)]
+  digest: Digest,
   keys: Vec<KeyData>,
 }
 
@@ -33,14 +37,24 @@ struct KeyData {
 
 #[wasm_bindgen(inspectable)]
 #[derive(Clone, Debug)]
-pub struct KeyCollection(pub(crate) KeyCollection_);
+pub struct KeyCollection(pub(crate) KeyCollection_, pub(crate) Digest);
 
 #[wasm_bindgen]
 impl KeyCollection {
   /// Creates a new `KeyCollection` with the specified key type.
+  ///
+  /// The `digest` used to build the Merkle tree defaults to `Digest.Sha256`.
   #[wasm_bindgen(constructor)]
-  pub fn new(type_: KeyType, count: usize) -> Result<KeyCollection, JsValue> {
-    KeyCollection_::new(type_.into(), count).map_err(err).map(Self)
+  pub fn new(type_: KeyType, count: usize, digest: Option<Digest>) -> Result<KeyCollection, JsValue> {
+    KeyCollection_::new(type_.into(), count)
+      .map_err(err)
+      .map(|keys| Self(keys, digest.unwrap_or_default()))
+  }
+
+  /// Returns the digest used to build the Merkle tree.
+  #[wasm_bindgen(getter)]
+  pub fn digest(&self) -> Digest {
+    self.1
   }
 
   /// Returns the number of keys in the collection.
@@ -73,24 +87,30 @@ impl KeyCollection {
     self.0.secret(index).map(encode_b58)
   }
 
+  /// Returns the base58-encoded Merkle root of the collection, built with
+  /// `digest` or the digest of the collection if omitted.
   #[wasm_bindgen(js_name = merkleRoot)]
-  pub fn merkle_root(&self, digest: Digest) -> String {
-    match digest {
+  pub fn merkle_root(&self, digest: Option<Digest>) -> String {
+    match digest.unwrap_or(self.1) {
       Digest::Sha256 => encode_b58(self.0.merkle_root::<Sha256>().as_slice()),
+      Digest::Blake2b256 => encode_b58(self.0.merkle_root::<Blake2b256>().as_slice()),
     }
   }
 
+  /// Returns the base58-encoded proof of membership of the public key at
+  /// the specified `index`, built with `digest` or the digest of the
+  /// collection if `undefined`.
   #[wasm_bindgen(js_name = merkleProof)]
-  pub fn merkle_proof(&self, digest: Digest, index: usize) -> Option<String> {
-    match digest {
-      Digest::Sha256 => {
-        let proof: Proof<Sha256> = match self.0.merkle_proof(index) {
-          Some(proof) => proof,
-          None => return None,
-        };
-
-        Some(encode_b58(&proof.encode()))
-      }
+  pub fn merkle_proof(&self, digest: Option<Digest>, index: usize) -> Option<String> {
+    match digest.unwrap_or(self.1) {
+      Digest::Sha256 => self
+        .0
+        .merkle_proof::<Sha256>(index)
+        .map(|proof| encode_b58(&proof.encode())),
+      Digest::Blake2b256 => self
+        .0
+        .merkle_proof::<Blake2b256>(index)
+        .map(|proof| encode_b58(&proof.encode())),
     }
   }
 
@@ -111,6 +131,7 @@ impl KeyCollection {
     let data: JsonData = JsonData {
       keys,
       type_: self.0.type_().into(),
+      digest: self.1,
     };
 
     JsValue::from_serde(&data).map_err(err)
@@ -130,6 +151,33 @@ impl KeyCollection {
 
     KeyCollection_::from_iterator(data.type_.into(), iter)
       .map_err(err)
-      .map(Self)
+      .map(|keys| Self(keys, data.digest))
+  }
+}
+
+/// Verifies that the base58-encoded `public` key is a member of the Merkle
+/// tree with the given base58-encoded `root`, using a proof created with
+/// `KeyCollection.merkleProof`.
+///
+/// The `digest` of the Merkle tree defaults to `Digest.Sha256`.
+#[wasm_bindgen(js_name = verifyMerkleProof)]
+pub fn verify_merkle_proof(root: &str, public: &str, proof: &str, digest: Option<Digest>) -> Result<bool, JsValue> {
+  let root: Vec<u8> = decode_b58(root).map_err(err)?;
+  let public: Vec<u8> = decode_b58(public).map_err(err)?;
+  let proof: Vec<u8> = decode_b58(proof).map_err(err)?;
+
+  match digest.unwrap_or_default() {
+    Digest::Sha256 => {
+      let root: Hash<Sha256> = Hash::from_slice(&root).ok_or_else(|| JsValue::from("Invalid Merkle Root"))?;
+      let proof: Proof<Sha256> = Proof::decode(&proof).ok_or_else(|| JsValue::from("Invalid Public Key Proof"))?;
+
+      Ok(proof.verify(&root, &public))
+    }
+    Digest::Blake2b256 => {
+      let root: Hash<Blake2b256> = Hash::from_slice(&root).ok_or_else(|| JsValue::from("Invalid Merkle Root"))?;
+      let proof: Proof<Blake2b256> = Proof::decode(&proof).ok_or_else(|| JsValue::from("Invalid Public Key Proof"))?;
+
+      Ok(proof.verify(&root, &public))
+    }
   }
 }
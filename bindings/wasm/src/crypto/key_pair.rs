@@ -9,6 +9,7 @@ use identity::crypto::SecretKey;
 use wasm_bindgen::prelude::*;
 
 use crate::crypto::KeyType;
+use crate::crypto::PublicKeyPair;
 use crate::utils::err;
 
 #[derive(Deserialize, Serialize)]
@@ -34,6 +35,15 @@ impl KeyPair {
     KeyPair_::new(type_.into()).map_err(err).map(Self)
   }
 
+  /// Deterministically derives a `KeyPair` object from the given seed.
+  ///
+  /// Intended for reproducible tests; use the constructor to generate keys
+  /// for production use.
+  #[wasm_bindgen(js_name = fromSeed)]
+  pub fn from_seed(type_: KeyType, seed: &[u8]) -> Result<KeyPair, JsValue> {
+    KeyPair_::from_seed(type_.into(), seed).map_err(err).map(Self)
+  }
+
   /// Parses a `KeyPair` object from base58-encoded public/secret keys.
   #[wasm_bindgen(js_name = fromBase58)]
   pub fn from_base58(type_: KeyType, public_key: &str, secret_key: &str) -> Result<KeyPair, JsValue> {
@@ -49,6 +59,15 @@ impl KeyPair {
     encode_b58(self.0.public())
   }
 
+  /// Returns a `PublicKeyPair` with a copy of the public key only.
+  ///
+  /// The returned object carries no secret key and is rejected by all
+  /// signing operations.
+  #[wasm_bindgen(js_name = publicKeyPair)]
+  pub fn public_key_pair(&self) -> PublicKeyPair {
+    PublicKeyPair(self.0.to_public())
+  }
+
   /// Returns the secret key as a base58-encoded string.
   #[wasm_bindgen(getter)]
   pub fn secret(&self) -> String {
@@ -5,8 +5,10 @@ mod digest;
 mod key_collection;
 mod key_pair;
 mod key_type;
+mod public_key_pair;
 
 pub use self::digest::Digest;
 pub use self::key_collection::KeyCollection;
 pub use self::key_pair::KeyPair;
 pub use self::key_type::KeyType;
+pub use self::public_key_pair::PublicKeyPair;
@@ -0,0 +1,63 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity::core::decode_b58;
+use identity::core::encode_b58;
+use identity::crypto::PublicKey;
+use identity::crypto::PublicKeyPair as PublicKeyPair_;
+use wasm_bindgen::prelude::*;
+
+use crate::crypto::KeyType;
+use crate::utils::err;
+
+#[derive(Deserialize, Serialize)]
+struct JsonData {
+  #[serde(rename = "type")]
+  type_: KeyType,
+  public: String,
+}
+
+// =============================================================================
+// =============================================================================
+
+/// The public half of a `KeyPair`; carries no secret key and cannot be used
+/// to sign.
+#[wasm_bindgen(inspectable)]
+#[derive(Clone, Debug)]
+pub struct PublicKeyPair(pub(crate) PublicKeyPair_);
+
+#[wasm_bindgen]
+impl PublicKeyPair {
+  /// Parses a `PublicKeyPair` object from a base58-encoded public key.
+  #[wasm_bindgen(js_name = fromBase58)]
+  pub fn from_base58(type_: KeyType, public_key: &str) -> Result<PublicKeyPair, JsValue> {
+    let public: PublicKey = decode_b58(public_key).map_err(err)?.into();
+
+    Ok(Self((type_.into(), public).into()))
+  }
+
+  /// Returns the public key as a base58-encoded string.
+  #[wasm_bindgen(getter)]
+  pub fn public(&self) -> String {
+    encode_b58(self.0.public())
+  }
+
+  /// Serializes a `PublicKeyPair` object as a JSON object.
+  #[wasm_bindgen(js_name = toJSON)]
+  pub fn to_json(&self) -> Result<JsValue, JsValue> {
+    let data: JsonData = JsonData {
+      type_: self.0.type_().into(),
+      public: self.public(),
+    };
+
+    JsValue::from_serde(&data).map_err(err)
+  }
+
+  /// Deserializes a `PublicKeyPair` object from a JSON object.
+  #[wasm_bindgen(js_name = fromJSON)]
+  pub fn from_json(json: &JsValue) -> Result<PublicKeyPair, JsValue> {
+    let data: JsonData = json.into_serde().map_err(err)?;
+
+    Self::from_base58(data.type_, &data.public)
+  }
+}
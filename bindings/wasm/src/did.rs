@@ -7,6 +7,7 @@ use wasm_bindgen::prelude::*;
 
 use crate::crypto::KeyPair;
 use crate::utils::err;
+use crate::utils::hash_code;
 
 /// @typicalname did
 #[wasm_bindgen(inspectable)]
@@ -66,9 +67,23 @@ impl DID {
   }
 
   /// Returns the `DID` object as a string.
+  ///
+  /// Equal DIDs have equal strings; use the string to key a JS `Map` or `Set`.
   #[allow(clippy::inherent_to_string)]
   #[wasm_bindgen(js_name = toString)]
   pub fn to_string(&self) -> String {
     self.0.to_string()
   }
+
+  /// Returns `true` if `other` is the same `DID`.
+  #[wasm_bindgen]
+  pub fn equals(&self, other: &DID) -> bool {
+    self.0 == other.0
+  }
+
+  /// Returns a stable hash of the `DID`; equal DIDs have equal hash codes.
+  #[wasm_bindgen(js_name = hashCode)]
+  pub fn hash_code(&self) -> u32 {
+    hash_code(self.0.as_str().as_bytes())
+  }
 }
@@ -2,19 +2,36 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use identity::core::decode_b58;
+use identity::core::encode_b16;
 use identity::core::FromJson;
+use identity::core::Object;
+use identity::core::OneOrMany;
+use identity::core::SerdeInto;
+use identity::core::Timestamp;
+use identity::core::Url;
+use identity::core::ToJson;
+use identity::core::Value;
 use identity::crypto::merkle_key::MerkleKey;
+use identity::crypto::merkle_key::Blake2b256;
 use identity::crypto::merkle_key::MerkleTag;
 use identity::crypto::merkle_key::Sha256;
 use identity::crypto::merkle_tree::Proof;
 use identity::crypto::PublicKey;
 use identity::crypto::SecretKey;
+use identity::crypto::Signature;
+use identity::crypto::TrySignature;
 use identity::did::verifiable;
+use identity::did::verifiable::SignatureSuiteRegistry;
+use identity::did::verifiable::SignatureType;
 use identity::did::Method as CoreMethod;
 use identity::did::MethodScope;
+use identity::did::MethodType;
+use identity::did::Service;
 use identity::iota::Document as IotaDocument;
 use identity::iota::DocumentDiff;
 use identity::iota::Method as IotaMethod;
+use identity::iota::ReconcilePolicy;
+use identity::iota::Reconciliation;
 use wasm_bindgen::prelude::*;
 
 use crate::credential::VerifiableCredential;
@@ -24,6 +41,7 @@ use crate::crypto::KeyType;
 use crate::did::DID;
 use crate::method::Method;
 use crate::utils::err;
+use crate::utils::hash_code;
 
 #[wasm_bindgen(inspectable)]
 pub struct NewDocument {
@@ -68,9 +86,53 @@ impl Document {
   }
 
   /// Creates a new DID Document from the given KeyPair.
+  ///
+  /// The authentication method can be configured with an optional `options`
+  /// object:
+  ///
+  /// - `fragment`: the fragment of the method id (`authentication` by default).
+  /// - `methodType`: the method type (`Ed25519VerificationKey2018` by default).
+  /// - `scope`: `Authentication` (default) to embed the method in
+  ///   `authentication`, or `VerificationMethod` to store it under
+  ///   `verificationMethod` and reference it from `authentication`.
   #[wasm_bindgen(js_name = fromKeyPair)]
-  pub fn from_keypair(key: &KeyPair) -> Result<Document, JsValue> {
-    IotaDocument::from_keypair(&key.0).map_err(err).map(Self)
+  pub fn from_keypair(key: &KeyPair, options: &JsValue) -> Result<Document, JsValue> {
+    #[derive(Default, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Options {
+      fragment: Option<String>,
+      method_type: Option<String>,
+      scope: Option<String>,
+    }
+
+    let options: Options = if options.is_undefined() || options.is_null() {
+      Options::default()
+    } else {
+      options.into_serde().map_err(err)?
+    };
+
+    let fragment: &str = options.fragment.as_deref().unwrap_or("authentication");
+    let method: IotaMethod = IotaMethod::from_keypair(&key.0, fragment).map_err(err)?;
+    let method_id: String = method.id().to_string();
+
+    let scope: MethodScope = match options.scope {
+      Some(scope) => scope.parse().map_err(err)?,
+      None => MethodScope::Authentication,
+    };
+
+    let mut document: IotaDocument = match scope {
+      MethodScope::Authentication => IotaDocument::from_authentication(method).map_err(err)?,
+      MethodScope::VerificationMethod => IotaDocument::from_verification_method(method).map_err(err)?,
+      _ => return Err("Invalid Method Scope".into()),
+    };
+
+    if let Some(method_type) = options.method_type {
+      let key_type: MethodType = method_type.parse().map_err(err)?;
+
+      document.migrate_method_type(&*method_id, key_type).map_err(err)?;
+    }
+
+    Ok(Self(document))
   }
 
   /// Creates a new DID Document from the given verification [`method`][`Method`].
@@ -100,6 +162,42 @@ impl Document {
     }
   }
 
+  /// Returns the `capabilityDelegation` verification relationships.
+  #[wasm_bindgen(getter, js_name = capabilityDelegation)]
+  pub fn capability_delegation(&self) -> Result<JsValue, JsValue> {
+    JsValue::from_serde(self.0.capability_delegation()).map_err(err)
+  }
+
+  /// Returns the `capabilityInvocation` verification relationships.
+  #[wasm_bindgen(getter, js_name = capabilityInvocation)]
+  pub fn capability_invocation(&self) -> Result<JsValue, JsValue> {
+    JsValue::from_serde(self.0.capability_invocation()).map_err(err)
+  }
+
+  /// Returns the entries of the JSON-LD `@context` of the DID Document.
+  #[wasm_bindgen]
+  pub fn contexts(&self) -> Result<JsValue, JsValue> {
+    JsValue::from_serde(&self.0.contexts().map_err(err)?).map_err(err)
+  }
+
+  /// Adds the JSON-LD context at `uri` to the `@context` of the DID Document;
+  /// returns `false` if the context is already present.
+  ///
+  /// Contexts must be added before the document is signed.
+  #[wasm_bindgen(js_name = addContext)]
+  pub fn add_context(&mut self, uri: &str) -> Result<bool, JsValue> {
+    let uri: Url = Url::parse(uri).map_err(err)?;
+
+    self.0.add_context(uri).map_err(err)
+  }
+
+  /// Validates that every term used by the DID Document is covered by an
+  /// entry of its `@context`; contexts are not dereferenced.
+  #[wasm_bindgen(js_name = checkTerms)]
+  pub fn check_terms(&self) -> Result<(), JsValue> {
+    self.0.check_terms().map_err(err)
+  }
+
   // ===========================================================================
   // Verification Methods
   // ===========================================================================
@@ -111,27 +209,168 @@ impl Document {
     Ok(self.0.insert_method(scope, method.0.clone()))
   }
 
+  /// Adds a verification method to the DID Document; returns the absolute
+  /// DID URL of the inserted method, or of the existing method with the same
+  /// id.
+  #[wasm_bindgen(js_name = insertMethodReturningId)]
+  pub fn insert_method_returning_id(&mut self, method: &Method, scope: Option<String>) -> Result<String, JsValue> {
+    let scope: MethodScope = scope.unwrap_or_default().parse().map_err(err)?;
+
+    self.0.insert_method(scope, method.0.clone());
+
+    self
+      .0
+      .try_resolve(method.0.id().as_str())
+      .map(|method| method.id().to_string())
+      .map_err(err)
+  }
+
   #[wasm_bindgen(js_name = removeMethod)]
   pub fn remove_method(&mut self, did: &DID) -> Result<(), JsValue> {
     self.0.remove_method(&did.0).map_err(err)
   }
 
+  // ===========================================================================
+  // Services
+  // ===========================================================================
+
+  /// Adds a new Service to the DID Document from a JSON object.
+  #[wasm_bindgen(js_name = insertService)]
+  pub fn insert_service(&mut self, service: &JsValue) -> Result<bool, JsValue> {
+    let service: Service = service.into_serde().map_err(err)?;
+
+    Ok(self.0.insert_service(service))
+  }
+
+  /// Removes the Service with the given id; accepts either a fragment
+  /// (`#domain`) or the absolute service id.
+  #[wasm_bindgen(js_name = removeService)]
+  pub fn remove_service(&mut self, query: &str) -> Result<(), JsValue> {
+    self.0.remove_service(query).map_err(err)
+  }
+
+  /// Adds a new `LinkedDomains` Service pointing at the given `origin`.
+  ///
+  /// The service id fragment defaults to `linked-domains`.
+  #[wasm_bindgen(js_name = addLinkedDomains)]
+  pub fn add_linked_domains(&mut self, origin: &str, fragment: Option<String>) -> Result<bool, JsValue> {
+    let origin: Url = Url::parse(origin).map_err(err)?;
+    let fragment: &str = fragment.as_deref().unwrap_or("linked-domains");
+
+    self.0.insert_linked_domains(fragment, origin).map_err(err)
+  }
+
+  /// Adds a new `DIDCommMessaging` Service with the given `endpoint`.
+  ///
+  /// `routingKeys` must be a non-empty array of key references; `accept` is
+  /// an optional array of media types. The service id fragment defaults to
+  /// `didcomm`.
+  #[wasm_bindgen(js_name = addDIDCommMessaging)]
+  pub fn add_didcomm_messaging(
+    &mut self,
+    endpoint: &str,
+    routing_keys: &JsValue,
+    accept: &JsValue,
+    fragment: Option<String>,
+  ) -> Result<bool, JsValue> {
+    let endpoint: Url = Url::parse(endpoint).map_err(err)?;
+    let routing_keys: Vec<String> = routing_keys.into_serde().map_err(err)?;
+
+    let accept: Vec<String> = if accept.is_undefined() || accept.is_null() {
+      Vec::new()
+    } else {
+      accept.into_serde().map_err(err)?
+    };
+
+    let fragment: &str = fragment.as_deref().unwrap_or("didcomm");
+
+    self
+      .0
+      .insert_didcomm_messaging(fragment, endpoint, routing_keys, accept)
+      .map_err(err)
+  }
+
   // ===========================================================================
   // Signatures
   // ===========================================================================
 
   /// Signs the DID Document with the default authentication method.
+  ///
+  /// Fails if the document already has a proof, unless `options` contains
+  /// `resign: true`. The error distinguishes a valid proof from a proof
+  /// invalidated by changes made after signing.
   #[wasm_bindgen]
-  pub fn sign(&mut self, key: &KeyPair) -> Result<(), JsValue> {
+  pub fn sign(&mut self, key: &KeyPair, options: &JsValue) -> Result<(), JsValue> {
+    #[derive(Default, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Options {
+      #[serde(default)]
+      resign: bool,
+    }
+
+    let options: Options = if options.is_undefined() || options.is_null() {
+      Options::default()
+    } else {
+      options.into_serde().map_err(err)?
+    };
+
+    if self.0.proof().is_some() && !options.resign {
+      return match self.0.verify() {
+        Ok(()) => Err("Invalid Document - Already Signed (use `resign: true` to replace the proof)".into()),
+        Err(_) => Err("Invalid Document - Modified After Signing (use `resign: true` to replace the proof)".into()),
+      };
+    }
+
     self.0.sign(key.0.secret()).map_err(err)
   }
 
+  /// Writes an unsigned proof referencing the `method` query (the default
+  /// authentication method if omitted) and returns the bytes to sign.
+  ///
+  /// Use this to sign with a key held outside of the process (e.g. in an
+  /// HSM): sign the returned bytes with Ed25519 and install the signature
+  /// with `attachSignature`. The document must not be modified in between.
+  #[wasm_bindgen(js_name = signingPayload)]
+  pub fn signing_payload(&mut self, method: Option<String>) -> Result<Vec<u8>, JsValue> {
+    let method: String = method.unwrap_or_else(|| self.0.authentication_id().to_string());
+
+    self.0.signing_payload(method.as_str()).map_err(err)
+  }
+
+  /// Installs the 64-byte Ed25519 `signature` of the payload returned by
+  /// `signingPayload` into the proof; `method` must be the method the payload
+  /// was created for.
+  #[wasm_bindgen(js_name = attachSignature)]
+  pub fn attach_signature(&mut self, method: &str, signature: &[u8]) -> Result<(), JsValue> {
+    self.0.attach_signature(method, signature).map_err(err)
+  }
+
   /// Verify the signature with the authentication_key
   #[wasm_bindgen]
   pub fn verify(&self) -> bool {
     self.0.verify().is_ok()
   }
 
+  /// Returns `true` if the DID Document has been deactivated.
+  #[wasm_bindgen(getter)]
+  pub fn deactivated(&self) -> bool {
+    self.0.deactivated()
+  }
+
+  /// Creates a deactivated DID Document whose proof is signed with the
+  /// authentication key of this document.
+  #[wasm_bindgen(js_name = signDeactivation)]
+  pub fn sign_deactivation(&self, key: &KeyPair) -> Result<Document, JsValue> {
+    self.0.sign_deactivation(key.0.secret()).map_err(err).map(Self)
+  }
+
+  /// Verifies that `other` is a deactivation of this DID Document signed with
+  /// its authentication key.
+  #[wasm_bindgen(js_name = verifyDeactivation)]
+  pub fn verify_deactivation(&self, other: &Document) -> bool {
+    self.0.verify_deactivation(&other.0).is_ok()
+  }
+
   #[wasm_bindgen(js_name = signCredential)]
   pub fn sign_credential(&self, data: &JsValue, args: &JsValue) -> Result<VerifiableCredential, JsValue> {
     let json: JsValue = self.sign_data(data, args)?;
@@ -152,7 +391,19 @@ impl Document {
   /// Verification Method.
   ///
   /// An additional `proof` property is required if using a Merkle Key
-  /// Collection verification Method.
+  /// Collection verification Method. Ed25519 Verification Methods accept an
+  /// optional `suite` property (`JcsEd25519Signature2020` by default or
+  /// `Ed25519Signature2020` for JSON-LD data).
+  ///
+  /// Optional `domain` and `challenge` properties are written into the proof
+  /// for use in authentication ceremonies.
+  ///
+  /// If a `purpose` (e.g. `assertionMethod`) is given, it is written into the
+  /// proof as `proofPurpose` and the proof is added to the existing proofs of
+  /// `data`, replacing only an existing proof with the same purpose.
+  ///
+  /// If `created` is `true`, the current time is written into the proof as
+  /// `created`, as required by the `maxAge` option of `verifyData`.
   #[wasm_bindgen(js_name = signData)]
   pub fn sign_data(&self, data: &JsValue, args: &JsValue) -> Result<JsValue, JsValue> {
     #[derive(Deserialize)]
@@ -163,22 +414,59 @@ impl Document {
         public: String,
         secret: String,
         proof: String,
+        #[serde(default)]
+        domain: Option<String>,
+        #[serde(default)]
+        challenge: Option<String>,
+        #[serde(default)]
+        purpose: Option<String>,
+        #[serde(default)]
+        created: bool,
       },
       Default {
         method: String,
         secret: String,
+        #[serde(default)]
+        suite: Option<String>,
+        #[serde(default)]
+        domain: Option<String>,
+        #[serde(default)]
+        challenge: Option<String>,
+        #[serde(default)]
+        purpose: Option<String>,
+        #[serde(default)]
+        created: bool,
       },
     }
 
-    let mut data: verifiable::Properties = data.into_serde().map_err(err)?;
+    let mut data: Object = data.into_serde().map_err(err)?;
     let args: Args = args.into_serde().map_err(err)?;
 
+    let purpose: Option<String> = match &args {
+      Args::MerkleKey { purpose, .. } | Args::Default { purpose, .. } => purpose.clone(),
+    };
+
+    let created: bool = match &args {
+      Args::MerkleKey { created, .. } | Args::Default { created, .. } => *created,
+    };
+
+    // Proofs with a purpose are signed without the existing proofs
+    let proofs: Option<Value> = match purpose {
+      Some(_) => data.remove("proof"),
+      None => None,
+    };
+
+    let mut data: verifiable::Properties = data.serde_into().map_err(err)?;
+
     match args {
       Args::MerkleKey {
         method,
         public,
         secret,
         proof,
+        domain,
+        challenge,
+        ..
       } => {
         let merkle_key: Vec<u8> = self
           .0
@@ -192,33 +480,255 @@ impl Document {
         let digest: MerkleTag = MerkleKey::extract_tags(&merkle_key).map_err(err)?.1;
         let proof: Vec<u8> = decode_b58(&proof).map_err(err)?;
 
-        let signer: _ = self.0.signer(&secret).method(&method);
+        let mut signer: _ = self.0.signer(&secret).method(&method);
+
+        if let Some(domain) = domain {
+          signer = signer.domain(domain);
+        }
+
+        if let Some(challenge) = challenge {
+          signer = signer.challenge(challenge);
+        }
+
+        if let Some(purpose) = purpose.as_deref() {
+          signer = signer.purpose(purpose);
+        }
+
+        if created {
+          signer = signer.created(Timestamp::now());
+        }
 
         match digest {
           MerkleTag::SHA256 => match Proof::<Sha256>::decode(&proof) {
             Some(proof) => signer.merkle_key((&public, &proof)).sign(&mut data).map_err(err)?,
             None => return Err("Invalid Public Key Proof".into()),
           },
+          MerkleTag::BLAKE2B_256 => match Proof::<Blake2b256>::decode(&proof) {
+            Some(proof) => signer.merkle_key((&public, &proof)).sign(&mut data).map_err(err)?,
+            None => return Err("Invalid Public Key Proof".into()),
+          },
           _ => return Err("Invalid Merkle Key Digest".into()),
         }
       }
-      Args::Default { method, secret } => {
+      Args::Default {
+        method,
+        secret,
+        suite,
+        domain,
+        challenge,
+        ..
+      } => {
         let secret: SecretKey = decode_b58(&secret).map_err(err).map(Into::into)?;
 
-        self.0.signer(&secret).method(&method).sign(&mut data).map_err(err)?;
+        let suite: SignatureType = match suite {
+          Some(suite) => suite.parse().map_err(err)?,
+          None => SignatureType::default(),
+        };
+
+        let mut signer: _ = self.0.signer(&secret).method(&method).suite(suite);
+
+        if let Some(domain) = domain {
+          signer = signer.domain(domain);
+        }
+
+        if let Some(challenge) = challenge {
+          signer = signer.challenge(challenge);
+        }
+
+        if let Some(purpose) = purpose.as_deref() {
+          signer = signer.purpose(purpose);
+        }
+
+        if created {
+          signer = signer.created(Timestamp::now());
+        }
+
+        signer.sign(&mut data).map_err(err)?;
       }
     }
 
-    JsValue::from_serde(&data).map_err(err)
+    match proofs {
+      Some(proofs) => {
+        let proof: Signature = data.proof().cloned().ok_or("Missing Proof")?;
+        let mut data: Object = Object::clone(&data);
+
+        data.insert("proof".into(), proofs);
+
+        IotaDocument::insert_proof(&mut data, proof).map_err(err)?;
+
+        JsValue::from_serde(&data).map_err(err)
+      }
+      None => JsValue::from_serde(&data).map_err(err),
+    }
+  }
+
+  /// Creates a signature for the given `data` with the specified DID Document
+  /// Verification Method; returns only the `proof` object.
+  ///
+  /// Accepts the same `args` as `signData`.
+  #[wasm_bindgen(js_name = signProof)]
+  pub fn sign_proof(&self, data: &JsValue, args: &JsValue) -> Result<JsValue, JsValue> {
+    let data: Object = self.sign_data(data, args)?.into_serde().map_err(err)?;
+    let proofs: OneOrMany<Signature> = data.get("proof").ok_or("Missing Proof")?.serde_into().map_err(err)?;
+
+    // The created proof is the last of the proof set
+    JsValue::from_serde(&proofs.iter().last()).map_err(err)
+  }
+
+  /// Returns the exact bytes covered by the `proof` of `data` as a hex-encoded
+  /// string - the JCS serialization of `data` without the proof value.
+  ///
+  /// This is the input `signData` and `verifyData` feed into the signature
+  /// algorithm and can be used to debug signatures created by other libraries.
+  #[wasm_bindgen(js_name = signingInput)]
+  pub fn signing_input(&self, data: &JsValue) -> Result<String, JsValue> {
+    let data: verifiable::Properties = data.into_serde().map_err(err)?;
+
+    data.signing_input().map_err(err).map(|input| encode_b16(&input))
   }
 
   /// Verifies the authenticity of `data` using the target verification method.
+  ///
+  /// If `options` contains a `maxAge` (in seconds), proofs with a `created`
+  /// timestamp older than `maxAge` (or in the future) are rejected, as are
+  /// proofs without a `created` timestamp. The current time can be overridden with an RFC 3339
+  /// `now` timestamp.
+  ///
+  /// If `options` contains an `expectedMethod` query, proofs created by any
+  /// other verification method are rejected.
+  ///
+  /// If `options` contains a `domain` or `challenge`, proofs without the
+  /// matching property are rejected.
+  ///
+  /// If `options` contains a `purpose`, the proof with the matching
+  /// `proofPurpose` is verified and other proofs of `data` are ignored; `data`
+  /// without such a proof is rejected.
   #[wasm_bindgen(js_name = verifyData)]
-  pub fn verify_data(&self, data: &JsValue) -> Result<bool, JsValue> {
+  pub fn verify_data(&self, data: &JsValue, options: &JsValue) -> Result<bool, JsValue> {
+    #[derive(Default, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Options {
+      max_age: Option<u64>,
+      now: Option<String>,
+      expected_method: Option<String>,
+      domain: Option<String>,
+      challenge: Option<String>,
+      purpose: Option<String>,
+    }
+
+    let options: Options = if options.is_undefined() || options.is_null() {
+      Options::default()
+    } else {
+      options.into_serde().map_err(err)?
+    };
+
+    let data: verifiable::Properties = match options.purpose.as_deref() {
+      Some(purpose) => match IotaDocument::find_proof(&data.into_serde().map_err(err)?, purpose) {
+        Ok(data) => data,
+        Err(_) => return Ok(false),
+      },
+      None => data.into_serde().map_err(err)?,
+    };
+
+    let mut verifier: _ = self.0.verifier();
+
+    if let Some(purpose) = options.purpose.as_deref() {
+      verifier = verifier.purpose(purpose);
+    }
+
+    if let Some(domain) = options.domain {
+      verifier = verifier.domain(domain);
+    }
+
+    if let Some(challenge) = options.challenge {
+      verifier = verifier.challenge(challenge);
+    }
+
+    let verified: bool = match options.expected_method.as_deref() {
+      Some(method) => verifier.verify_with(&data, method).is_ok(),
+      None => verifier.verify(&data).is_ok(),
+    };
+
+    if !verified {
+      return Ok(false);
+    }
+
+    if let Some(max_age) = options.max_age {
+      let now: Timestamp = match options.now {
+        Some(now) => Timestamp::parse(&now).map_err(err)?,
+        None => Timestamp::now(),
+      };
+
+      let created: Timestamp = match data.proof().and_then(|proof| proof.created()) {
+        Some(created) => created,
+        None => return Ok(false),
+      };
+
+      let age: i64 = now.to_unix() - created.to_unix();
+
+      if age < 0 || age as u64 > max_age {
+        return Ok(false);
+      }
+    }
+
+    Ok(true)
+  }
+
+  /// Verifies the signature of `data` against the given base58-encoded
+  /// public key, without resolving the verification method in the document.
+  ///
+  /// Only proofs created by the `JcsEd25519Signature2020` and
+  /// `Ed25519Signature2020` suites are supported.
+  #[wasm_bindgen(js_name = verifyDataWithKey)]
+  pub fn verify_data_with_key(&self, data: &JsValue, public: &str) -> Result<bool, JsValue> {
     let data: verifiable::Properties = data.into_serde().map_err(err)?;
-    let result: bool = self.0.verifier().verify(&data).is_ok();
+    let public: Vec<u8> = decode_b58(public).map_err(err)?;
 
-    Ok(result)
+    Ok(SignatureSuiteRegistry::default().verify(&data, &public).is_ok())
+  }
+
+  /// Verifies the signature of `data` and ensures the signing method has the
+  /// same key material as in the `pinned` document, e.g. a snapshot stored
+  /// when the DID was first resolved.
+  ///
+  /// Throws distinct errors for an invalid signature and a key that differs
+  /// from the `pinned` document.
+  #[wasm_bindgen(js_name = verifyAgainstPinned)]
+  pub fn verify_against_pinned(&self, data: &JsValue, pinned: &Document) -> Result<(), JsValue> {
+    let data: verifiable::Properties = data.into_serde().map_err(err)?;
+
+    self.0.verify_data_pinned(&data, &pinned.0).map_err(err)
+  }
+
+  /// Verifies that `data` has valid proofs from at least `threshold` distinct
+  /// authentication methods.
+  ///
+  /// The `proof` property of `data` may be a single proof or an array of
+  /// proofs, e.g. created with `signProof`.
+  #[wasm_bindgen(js_name = verifyThreshold)]
+  pub fn verify_threshold(&self, data: &JsValue, threshold: usize) -> Result<bool, JsValue> {
+    let data: Object = data.into_serde().map_err(err)?;
+
+    Ok(self.0.verify_threshold(&data, threshold).is_ok())
+  }
+
+  /// Verifies that `presentation` is held by this document's DID and was
+  /// signed with its default authentication method.
+  ///
+  /// Throws distinct errors for a mismatched holder and an invalid holder
+  /// signature; the embedded credentials are not verified.
+  #[wasm_bindgen(js_name = verifyPresentation)]
+  pub fn verify_presentation(&self, presentation: &VerifiablePresentation) -> Result<(), JsValue> {
+    self.0.verify_presentation(&presentation.0).map_err(err)
+  }
+
+  /// Returns a list of all verification methods in the DID Document,
+  /// including methods embedded in verification relationships.
+  #[wasm_bindgen(js_name = getMethods)]
+  pub fn methods(&self) -> Result<JsValue, JsValue> {
+    let methods: Vec<&CoreMethod> = self.0.methods().collect();
+
+    JsValue::from_serde(&methods).map_err(err)
   }
 
   #[wasm_bindgen(js_name = resolveKey)]
@@ -228,6 +738,84 @@ impl Document {
     IotaMethod::try_from_core(method).map_err(err).map(Method)
   }
 
+  /// Returns the verification method referenced by the `proof` of the DID
+  /// Document; the proof itself is not verified.
+  #[wasm_bindgen(js_name = methodForProof)]
+  pub fn method_for_proof(&self) -> Result<Method, JsValue> {
+    let method: CoreMethod = self.0.method_for_proof().map_err(err)?.clone();
+
+    IotaMethod::try_from_core(method).map_err(err).map(Method)
+  }
+
+  /// Changes the `type` of the verification method `did` to `new_type`,
+  /// re-encoding its key data without changing the key material.
+  #[wasm_bindgen(js_name = migrateMethodType)]
+  pub fn migrate_method_type(&mut self, did: &DID, new_type: &str) -> Result<(), JsValue> {
+    let key_type: MethodType = new_type.parse().map_err(err)?;
+
+    self.0.migrate_method_type(&*did.0, key_type).map_err(err)
+  }
+
+  /// Returns `true` if the verification method matching `method` is
+  /// authorized under `capabilityInvocation`.
+  #[wasm_bindgen(js_name = validateCapabilityInvocation)]
+  pub fn validate_capability_invocation(&self, method: &str) -> bool {
+    self.0.is_capability_invoker(method)
+  }
+
+  /// Returns the verification method whose key data matches the given
+  /// base58-encoded public key.
+  #[wasm_bindgen(js_name = resolveKeyByPublic)]
+  pub fn resolve_key_by_public(&self, public: &str) -> Result<Method, JsValue> {
+    let public: Vec<u8> = decode_b58(public).map_err(err)?;
+
+    let method: CoreMethod = self
+      .0
+      .methods()
+      .find(|method| matches!(method.key_data().try_decode(), Ok(data) if data == public))
+      .cloned()
+      .ok_or_else(|| JsValue::from("Verification Method Not Found"))?;
+
+    IotaMethod::try_from_core(method).map_err(err).map(Method)
+  }
+
+  /// Returns the verification relationships (e.g. `authentication`,
+  /// `assertionMethod`) of the verification method matching `query`, which
+  /// is either a method id or a base58-encoded public key.
+  #[wasm_bindgen(js_name = relationshipsFor)]
+  pub fn relationships_for(&self, query: &str) -> Result<JsValue, JsValue> {
+    let method: &CoreMethod = match self.0.resolve(query) {
+      Some(method) => method,
+      None => {
+        let public: Vec<u8> = decode_b58(query).map_err(|_| JsValue::from("Verification Method Not Found"))?;
+
+        self
+          .0
+          .methods()
+          .find(|method| matches!(method.key_data().try_decode(), Ok(data) if data == public))
+          .ok_or_else(|| JsValue::from("Verification Method Not Found"))?
+      }
+    };
+
+    let relationships: Vec<&str> = self
+      .0
+      .relationships(method.id().as_str())
+      .into_iter()
+      .map(|scope| scope.as_property())
+      .collect();
+
+    JsValue::from_serde(&relationships).map_err(err)
+  }
+
+  /// Removes verification methods with duplicate key material, transferring
+  /// their verification relationships; returns the ids of the removed methods.
+  #[wasm_bindgen(js_name = dedupeKeys)]
+  pub fn dedupe_keys(&mut self) -> Result<JsValue, JsValue> {
+    let removed: Vec<String> = self.0.dedupe_keys().iter().map(ToString::to_string).collect();
+
+    JsValue::from_serde(&removed).map_err(err)
+  }
+
   #[wasm_bindgen(js_name = revokeMerkleKey)]
   pub fn revoke_merkle_key(&mut self, query: &str, index: usize) -> Result<bool, JsValue> {
     let method: &mut IotaMethod = self
@@ -243,35 +831,274 @@ impl Document {
   // Diffs
   // ===========================================================================
 
-  /// Generate the difference between two DID Documents and sign it
+  /// Reconciles two divergent versions of the same DID Document with a
+  /// three-way merge against `base`, the last version both were derived from;
+  /// returns an object with the reconciled `document` and a list of
+  /// `conflicts`.
+  ///
+  /// Conflicts are resolved according to `policy`: "latest" (default), "local"
+  /// (`this` wins), or "remote" (`other` wins). The reconciled document must be
+  /// signed before it is published.
   #[wasm_bindgen]
-  pub fn diff(&self, other: &Document, message: &str, key: &KeyPair) -> Result<JsValue, JsValue> {
-    self
+  pub fn reconcile(&self, other: &Document, base: &Document, policy: Option<String>) -> Result<JsValue, JsValue> {
+    #[derive(Serialize)]
+    struct Output<'a> {
+      document: &'a IotaDocument,
+      conflicts: &'a [String],
+    }
+
+    let policy: ReconcilePolicy = match policy.as_deref() {
+      Some("latest") | None => ReconcilePolicy::Latest,
+      Some("local") => ReconcilePolicy::Local,
+      Some("remote") => ReconcilePolicy::Remote,
+      Some(_) => return Err("Invalid Reconcile Policy".into()),
+    };
+
+    let output: Reconciliation = self.0.reconcile(&other.0, &base.0, policy).map_err(err)?;
+
+    JsValue::from_serde(&Output {
+      document: output.document(),
+      conflicts: output.conflicts(),
+    })
+    .map_err(err)
+  }
+
+  /// Re-applies the changes of `diff`, which was created against this
+  /// document, onto `newBase` and signs a new diff against `newBase` chained
+  /// to the `message` id; returns an object with the new `diff` and a list of
+  /// `conflicts`.
+  ///
+  /// Fields changed by both `diff` and `newBase` are left as in `newBase` and
+  /// reported as conflicts.
+  #[wasm_bindgen(js_name = rebaseDiff)]
+  pub fn rebase_diff(&self, diff: &str, new_base: &Document, message: &str, key: &KeyPair) -> Result<JsValue, JsValue> {
+    #[derive(Serialize)]
+    struct Output<'a> {
+      diff: &'a DocumentDiff,
+      conflicts: &'a [String],
+    }
+
+    let diff: DocumentDiff = DocumentDiff::from_json(diff).map_err(err)?;
+    let output: Reconciliation = self.0.rebase_diff(&diff, &new_base.0).map_err(err)?;
+
+    let diff: DocumentDiff = new_base
       .0
-      .diff(&other.0, message.to_string().into(), key.0.secret())
-      .map_err(err)
-      .and_then(|diff| JsValue::from_serde(&diff).map_err(err))
+      .diff(output.document(), message.to_string().into(), key.0.secret())
+      .map_err(err)?;
+
+    JsValue::from_serde(&Output {
+      diff: &diff,
+      conflicts: output.conflicts(),
+    })
+    .map_err(err)
+  }
+
+  /// Generate the difference between two DID Documents and sign it
+  ///
+  /// The diff is signed with the default authentication method unless a
+  /// `method` query is given; `key` must belong to the signing method.
+  #[wasm_bindgen]
+  pub fn diff(
+    &self,
+    other: &Document,
+    message: &str,
+    key: &KeyPair,
+    method: Option<String>,
+  ) -> Result<JsValue, JsValue> {
+    let diff: DocumentDiff = match method {
+      Some(method) => self
+        .0
+        .diff_with_method(&other.0, message.to_string().into(), method.as_str(), key.0.secret()),
+      None => self.0.diff(&other.0, message.to_string().into(), key.0.secret()),
+    }
+    .map_err(err)?;
+
+    JsValue::from_serde(&diff).map_err(err)
+  }
+
+  /// Generate the difference between two DID Documents and sign it; returns
+  /// the signed diff as a compact string - the JCS-canonicalized diff,
+  /// DEFLATE-compressed and base64url-encoded.
+  ///
+  /// The diff is signed with the default authentication method unless a
+  /// `method` query is given; `key` must belong to the signing method.
+  #[wasm_bindgen(js_name = diffBase64)]
+  pub fn diff_base64(
+    &self,
+    other: &Document,
+    message: &str,
+    key: &KeyPair,
+    method: Option<String>,
+  ) -> Result<String, JsValue> {
+    let diff: DocumentDiff = match method {
+      Some(method) => self
+        .0
+        .diff_with_method(&other.0, message.to_string().into(), method.as_str(), key.0.secret()),
+      None => self.0.diff(&other.0, message.to_string().into(), key.0.secret()),
+    }
+    .map_err(err)?;
+
+    diff.to_compact().map_err(err)
+  }
+
+  /// Checks whether `diff` can be merged into `self` without overwriting
+  /// local changes; returns `{ ok, conflicts }` with the paths of the fields
+  /// that diverged from the document the diff was computed against.
+  #[wasm_bindgen(js_name = canMerge)]
+  pub fn can_merge(&self, diff: &str) -> Result<JsValue, JsValue> {
+    #[derive(Serialize)]
+    struct Output<'a> {
+      ok: bool,
+      conflicts: &'a [String],
+    }
+
+    let diff: DocumentDiff = DocumentDiff::from_json(diff).map_err(err)?;
+    let conflicts: Vec<String> = self.0.merge_conflicts(&diff).map_err(err)?;
+
+    JsValue::from_serde(&Output {
+      ok: conflicts.is_empty(),
+      conflicts: &conflicts,
+    })
+    .map_err(err)
   }
 
   /// Verifies the `diff` signature and merges the changes into `self`.
+  ///
+  /// If `options.rejectDuplicateKeys` is `true`, diffs introducing methods
+  /// with duplicate key material are rejected.
   #[wasm_bindgen]
-  pub fn merge(&mut self, diff: &str) -> Result<(), JsValue> {
+  pub fn merge(&mut self, diff: &str, options: &JsValue) -> Result<(), JsValue> {
+    #[derive(Default, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Options {
+      #[serde(default)]
+      reject_duplicate_keys: bool,
+    }
+
+    let options: Options = if options.is_undefined() || options.is_null() {
+      Options::default()
+    } else {
+      options.into_serde().map_err(err)?
+    };
+
     let diff: DocumentDiff = DocumentDiff::from_json(diff).map_err(err)?;
 
+    if options.reject_duplicate_keys {
+      self.0.merge_unique(&diff).map_err(err)?;
+    } else {
+      self.0.merge(&diff).map_err(err)?;
+    }
+
+    Ok(())
+  }
+
+  /// Decodes a compact diff created with `diffBase64`, verifies the diff
+  /// signature, and merges the changes into `self`.
+  #[wasm_bindgen(js_name = mergeCompact)]
+  pub fn merge_compact(&mut self, diff: &str) -> Result<(), JsValue> {
+    let diff: DocumentDiff = DocumentDiff::from_compact(diff).map_err(err)?;
+
     self.0.merge(&diff).map_err(err)?;
 
     Ok(())
   }
 
+  /// Returns the canonical (JCS) JSON serialization of the `Document`.
+  ///
+  /// Equal documents have equal strings; use the string to key a JS `Map` or
+  /// `Set`.
+  #[allow(clippy::inherent_to_string)]
+  #[wasm_bindgen(js_name = toString)]
+  pub fn to_string(&self) -> Result<String, JsValue> {
+    self
+      .0
+      .to_jcs()
+      .map_err(err)
+      .and_then(|data| String::from_utf8(data).map_err(err))
+  }
+
+  /// Returns `true` if `other` is the same `Document`.
+  #[wasm_bindgen]
+  pub fn equals(&self, other: &Document) -> bool {
+    self.0 == other.0
+  }
+
+  /// Returns a stable hash of the canonical (JCS) serialization of the
+  /// `Document`; equal documents have equal hash codes.
+  #[wasm_bindgen(js_name = hashCode)]
+  pub fn hash_code(&self) -> Result<u32, JsValue> {
+    self.0.to_jcs().map(|data| hash_code(&data)).map_err(err)
+  }
+
   /// Serializes a `Document` object as a JSON object.
+  ///
+  /// If `options.includeMetadata` is `false`, the properties used for Tangle
+  /// bookkeeping (`created`, `updated`, `immutable`, `deactivated`, and
+  /// `previous_message_id`) are omitted, along with the `proof` covering them.
   #[wasm_bindgen(js_name = toJSON)]
-  pub fn to_json(&self) -> Result<JsValue, JsValue> {
-    JsValue::from_serde(&self.0).map_err(err)
+  pub fn to_json(&self, options: &JsValue) -> Result<JsValue, JsValue> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Options {
+      #[serde(default = "include_metadata")]
+      include_metadata: bool,
+    }
+
+    fn include_metadata() -> bool {
+      true
+    }
+
+    // `JSON.stringify` calls `toJSON` with the property name as argument
+    let options: Options = if options.is_object() {
+      options.into_serde().map_err(err)?
+    } else {
+      Options {
+        include_metadata: include_metadata(),
+      }
+    };
+
+    if options.include_metadata {
+      JsValue::from_serde(&self.0).map_err(err)
+    } else {
+      JsValue::from_serde(&self.0.to_core_document().map_err(err)?).map_err(err)
+    }
   }
 
   /// Deserializes a `Document` object from a JSON object.
+  ///
+  /// If `options.validateContext` is `true`, documents without the base DID
+  /// context as the first `@context` entry are rejected.
+  ///
+  /// If `options.strict` is `true`, documents with unknown top-level properties
+  /// or without the base DID context are rejected; by default unknown properties
+  /// are preserved for forward compatibility.
   #[wasm_bindgen(js_name = fromJSON)]
-  pub fn from_json(json: &JsValue) -> Result<Document, JsValue> {
-    json.into_serde().map_err(err).map(Self)
+  pub fn from_json(json: &JsValue, options: &JsValue) -> Result<Document, JsValue> {
+    #[derive(Default, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Options {
+      #[serde(default)]
+      validate_context: bool,
+      #[serde(default)]
+      strict: bool,
+    }
+
+    let options: Options = if options.is_undefined() || options.is_null() {
+      Options::default()
+    } else {
+      options.into_serde().map_err(err)?
+    };
+
+    let document: IotaDocument = json.into_serde().map_err(err)?;
+
+    if options.validate_context || options.strict {
+      document.check_context().map_err(err)?;
+    }
+
+    if options.strict {
+      document.check_properties().map_err(err)?;
+    }
+
+    Ok(Self(document))
   }
 }
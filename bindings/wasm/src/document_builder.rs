@@ -0,0 +1,112 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity::core::Object;
+use identity::did::Document as CoreDocument;
+use identity::did::DocumentBuilder as CoreDocumentBuilder;
+use identity::did::Method as CoreMethod;
+use identity::did::MethodScope;
+use identity::did::Service;
+use identity::did::DID as CoreDID;
+use identity::iota::Document as IotaDocument;
+use identity::iota::Method as IotaMethod;
+use wasm_bindgen::prelude::*;
+
+use crate::did::DID;
+use crate::document::Document;
+use crate::method::Method;
+use crate::utils::err;
+
+/// Assembles a DID Document from verification methods, services, and a
+/// controller before producing a validated `Document`.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct DocumentBuilder {
+  authentication: IotaMethod,
+  controller: Option<CoreDID>,
+  methods: Vec<(MethodScope, IotaMethod)>,
+  services: Vec<Service>,
+}
+
+#[wasm_bindgen]
+impl DocumentBuilder {
+  /// Creates a new `DocumentBuilder` for the DID controlling the given
+  /// authentication `method`.
+  #[wasm_bindgen(constructor)]
+  pub fn new(method: &Method) -> DocumentBuilder {
+    Self {
+      authentication: method.0.clone(),
+      controller: None,
+      methods: Vec::new(),
+      services: Vec::new(),
+    }
+  }
+
+  /// Adds a verification method with the given relationship `scope`.
+  ///
+  /// The scope defaults to `VerificationMethod`.
+  #[wasm_bindgen]
+  pub fn method(mut self, method: &Method, scope: Option<String>) -> Result<DocumentBuilder, JsValue> {
+    let scope: MethodScope = match scope {
+      Some(scope) => scope.parse().map_err(err)?,
+      None => MethodScope::default(),
+    };
+
+    self.methods.push((scope, method.0.clone()));
+
+    Ok(self)
+  }
+
+  /// Adds a Service from a JSON object.
+  #[wasm_bindgen]
+  pub fn service(mut self, service: &JsValue) -> Result<DocumentBuilder, JsValue> {
+    let service: Service = service.into_serde().map_err(err)?;
+
+    self.services.push(service);
+
+    Ok(self)
+  }
+
+  /// Sets the `controller` of the DID Document.
+  #[wasm_bindgen]
+  pub fn controller(mut self, controller: &DID) -> DocumentBuilder {
+    self.controller = Some(controller.0.clone().into());
+    self
+  }
+
+  /// Returns a new `Document` based on the builder configuration.
+  ///
+  /// Fails if a method or service id is used more than once, or the result
+  /// is not a valid IOTA DID Document.
+  #[wasm_bindgen]
+  pub fn build(&self) -> Result<Document, JsValue> {
+    let mut builder: CoreDocumentBuilder = CoreDocument::builder(Object::new())
+      .id(self.authentication.controller().clone().into())
+      .authentication(CoreMethod::from(self.authentication.clone()));
+
+    if let Some(controller) = self.controller.clone() {
+      builder = builder.controller(controller);
+    }
+
+    for (scope, method) in self.methods.iter() {
+      let method: CoreMethod = method.clone().into();
+
+      builder = match scope {
+        MethodScope::VerificationMethod => builder.verification_method(method),
+        MethodScope::Authentication => builder.authentication(method),
+        MethodScope::AssertionMethod => builder.assertion_method(method),
+        MethodScope::KeyAgreement => builder.key_agreement(method),
+        MethodScope::CapabilityDelegation => builder.capability_delegation(method),
+        MethodScope::CapabilityInvocation => builder.capability_invocation(method),
+      };
+    }
+
+    for service in self.services.iter() {
+      builder = builder.service(service.clone());
+    }
+
+    let document: CoreDocument = builder.build().map_err(err)?;
+
+    IotaDocument::try_from_core(document).map_err(err).map(Document)
+  }
+}
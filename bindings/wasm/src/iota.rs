@@ -1,6 +1,8 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use identity::core::FromJson;
+use identity::credential::VerifiablePresentation;
 use identity::iota::Client;
 use identity::iota::ClientBuilder;
 use identity::iota::CredentialValidation;
@@ -52,7 +54,7 @@ fn client(params: JsValue) -> Result<Client, JsValue> {
 #[wasm_bindgen]
 pub async fn publish(document: JsValue, params: JsValue) -> Result<JsValue, JsValue> {
   let client: Client = client(params)?;
-  let document: Document = Document::from_json(&document)?;
+  let document: Document = Document::from_json(&document, &JsValue::UNDEFINED)?;
 
   client
     .publish_document(&document.0)
@@ -97,3 +99,18 @@ pub async fn check_presentation(data: String, params: JsValue) -> Result<JsValue
 
   JsValue::from_serde(&status).map_err(err)
 }
+
+/// Verifies the holder signature of a presentation and all embedded credentials with the DID Documents from the Tangle,
+/// params looks like { node: "http://localhost:14265", network: "main" }
+///
+/// Throws distinct errors for an invalid holder signature and an invalid credential.
+#[wasm_bindgen(js_name = verifyPresentation)]
+pub async fn verify_presentation(data: String, params: JsValue) -> Result<(), JsValue> {
+  let client: Client = client(params)?;
+  let presentation: VerifiablePresentation = VerifiablePresentation::from_json(&data).map_err(err)?;
+
+  CredentialValidator::new(&client)
+    .verify_presentation(&presentation)
+    .await
+    .map_err(err)
+}
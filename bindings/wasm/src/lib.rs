@@ -14,6 +14,7 @@ pub mod credential;
 pub mod crypto;
 pub mod did;
 pub mod document;
+pub mod document_builder;
 pub mod iota;
 pub mod method;
 
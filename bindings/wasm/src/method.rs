@@ -1,6 +1,8 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use identity::core::Object;
+use identity::crypto::merkle_key::Blake2b256;
 use identity::crypto::merkle_key::Sha256;
 use identity::iota::Method as Method_;
 use wasm_bindgen::prelude::*;
@@ -31,19 +33,35 @@ impl Method {
       .map(Self)
   }
 
+  /// Creates a new `Method` object from the given `did` and JSON Web Key.
+  ///
+  /// Supports `OKP` keys with the `Ed25519` and `X25519` curves.
+  #[wasm_bindgen(js_name = fromJwk)]
+  pub fn from_jwk(did: &DID, jwk: &JsValue, tag: Option<String>) -> Result<Method, JsValue> {
+    let jwk: Object = jwk.into_serde().map_err(err)?;
+
+    Method_::from_jwk(did.0.clone(), &jwk, tag.as_deref())
+      .map_err(err)
+      .map(Self)
+  }
+
   /// Creates a new Merkle Key Collection Method from the given key collection.
+  ///
+  /// The Merkle tree is built with `digest`, or the digest of `keys` if
+  /// `undefined`.
   #[wasm_bindgen(js_name = createMerkleKey)]
   pub fn create_merkle_key(
-    digest: Digest,
+    digest: Option<Digest>,
     did: &DID,
     keys: &KeyCollection,
     tag: Option<String>,
   ) -> Result<Method, JsValue> {
-    match digest {
-      Digest::Sha256 => Method_::create_merkle_key::<Sha256, _>(did.0.clone(), &keys.0, tag.as_deref())
-        .map_err(err)
-        .map(Self),
+    match digest.unwrap_or(keys.1) {
+      Digest::Sha256 => Method_::create_merkle_key::<Sha256, _>(did.0.clone(), &keys.0, tag.as_deref()),
+      Digest::Blake2b256 => Method_::create_merkle_key::<Blake2b256, _>(did.0.clone(), &keys.0, tag.as_deref()),
     }
+    .map_err(err)
+    .map(Self)
   }
 
   /// Returns the `id` DID of the `Method` object.
@@ -70,6 +88,16 @@ impl Method {
     JsValue::from_serde(self.0.key_data()).map_err(err)
   }
 
+  /// Returns the `Method` public key as a JSON Web Key.
+  #[wasm_bindgen(js_name = toJwk)]
+  pub fn to_jwk(&self) -> Result<JsValue, JsValue> {
+    self
+      .0
+      .to_jwk()
+      .map_err(err)
+      .and_then(|jwk| JsValue::from_serde(&jwk).map_err(err))
+  }
+
   /// Serializes a `Method` object as a JSON object.
   #[wasm_bindgen(js_name = toJSON)]
   pub fn to_json(&self) -> Result<JsValue, JsValue> {
@@ -1,12 +1,74 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use core::any::Any;
+use identity::core::Error as CoreError;
+use identity::credential::Error as CredentialError;
+use identity::did::Error as DocumentError;
+use identity::iota::Error as IotaError;
 use wasm_bindgen::JsValue;
 
 /// Convert errors so they are readable in JS
+///
+/// Core errors, including core errors wrapped by the DID, credential, and
+/// IOTA errors, are converted to an object of the form
+/// `{ kind, message, details }`; all other errors are converted to a string.
 pub fn err<T>(error: T) -> JsValue
 where
-  T: ToString,
+  T: ToString + 'static,
 {
+  if let Some(error) = core_error(&error) {
+    if let Ok(value) = JsValue::from_serde(error) {
+      return value;
+    }
+  }
+
   error.to_string().into()
 }
+
+// Returns the core error of `error`, if it is or wraps one.
+fn core_error(error: &dyn Any) -> Option<&CoreError> {
+  if let Some(error) = error.downcast_ref::<CoreError>() {
+    return Some(error);
+  }
+
+  if let Some(error) = error.downcast_ref::<DocumentError>() {
+    return document_core_error(error);
+  }
+
+  if let Some(error) = error.downcast_ref::<CredentialError>() {
+    return credential_core_error(error);
+  }
+
+  match error.downcast_ref::<IotaError>()? {
+    IotaError::CoreError(error) => Some(error),
+    IotaError::InvalidDoc(error) => document_core_error(error),
+    IotaError::CredError(error) => credential_core_error(error),
+    _ => None,
+  }
+}
+
+fn document_core_error(error: &DocumentError) -> Option<&CoreError> {
+  match error {
+    DocumentError::CoreError(error) => Some(error),
+    _ => None,
+  }
+}
+
+fn credential_core_error(error: &CredentialError) -> Option<&CoreError> {
+  match error {
+    CredentialError::CoreError(error) => Some(error),
+    CredentialError::DIDError(error) => document_core_error(error),
+    _ => None,
+  }
+}
+
+/// Computes a stable 32-bit FNV-1a hash of `data` for use as a JS `hashCode`.
+pub fn hash_code(data: &[u8]) -> u32 {
+  const OFFSET: u32 = 0x811c_9dc5;
+  const PRIME: u32 = 0x0100_0193;
+
+  data
+    .iter()
+    .fold(OFFSET, |hash, byte| (hash ^ u32::from(*byte)).wrapping_mul(PRIME))
+}
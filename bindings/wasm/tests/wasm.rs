@@ -1,14 +1,21 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use identity::core::json;
+use identity::core::Value;
+use wasm_bindgen::JsValue;
 use wasm_bindgen_test::*;
 
+use identity_wasm::credential::CredentialBuilder;
+use identity_wasm::credential::PresentationBuilder;
 use identity_wasm::crypto::Digest;
 use identity_wasm::crypto::KeyCollection;
 use identity_wasm::crypto::KeyPair;
 use identity_wasm::crypto::KeyType;
 use identity_wasm::did::DID;
 use identity_wasm::document::Document;
+use identity_wasm::document_builder::DocumentBuilder;
+use identity_wasm::method::Method;
 
 #[wasm_bindgen_test]
 fn test_keypair() {
@@ -33,7 +40,7 @@ fn test_keypair() {
 #[wasm_bindgen_test]
 fn test_key_collection() {
   let size = 1 << 5;
-  let keys = KeyCollection::new(KeyType::Ed25519, size).unwrap();
+  let keys = KeyCollection::new(KeyType::Ed25519, size, None).unwrap();
 
   assert_eq!(keys.length(), size);
   assert_eq!(keys.is_empty(), false);
@@ -44,11 +51,11 @@ fn test_key_collection() {
     assert_eq!(key.public(), keys.public(index).unwrap());
     assert_eq!(key.secret(), keys.secret(index).unwrap());
 
-    assert!(keys.merkle_proof(Digest::Sha256, index).is_some());
+    assert!(keys.merkle_proof(None, index).is_some());
   }
 
   assert!(keys.keypair(keys.length()).is_none());
-  assert!(keys.merkle_proof(Digest::Sha256, keys.length()).is_none());
+  assert!(keys.merkle_proof(None, keys.length()).is_none());
 
   let json = keys.to_json().unwrap();
   let from = KeyCollection::from_json(&json).unwrap();
@@ -57,6 +64,24 @@ fn test_key_collection() {
     assert_eq!(keys.public(index).unwrap(), from.public(index).unwrap());
     assert_eq!(keys.secret(index).unwrap(), from.secret(index).unwrap());
   }
+
+  assert_eq!(keys.digest(), Digest::Sha256);
+  assert_eq!(keys.merkle_root(None), from.merkle_root(None));
+  assert_eq!(keys.merkle_root(None), keys.merkle_root(Some(Digest::Sha256)));
+}
+
+#[wasm_bindgen_test]
+fn test_key_collection_digest() {
+  let keys = KeyCollection::new(KeyType::Ed25519, 8, Some(Digest::Blake2b256)).unwrap();
+  let from = KeyCollection::from_json(&keys.to_json().unwrap()).unwrap();
+
+  assert_eq!(keys.digest(), Digest::Blake2b256);
+  assert_eq!(from.digest(), Digest::Blake2b256);
+  assert_eq!(keys.merkle_root(None), from.merkle_root(None));
+
+  // An explicit digest takes precedence over the digest of the collection
+  assert_eq!(keys.merkle_root(None), keys.merkle_root(Some(Digest::Blake2b256)));
+  assert_ne!(keys.merkle_root(None), keys.merkle_root(Some(Digest::Sha256)));
 }
 
 #[test]
@@ -79,14 +104,424 @@ fn test_did() {
   assert_eq!(base58.shard().unwrap(), "xyz");
 }
 
-#[test]
+#[wasm_bindgen_test]
 fn test_document() {
   let output = Document::new(KeyType::Ed25519, None).unwrap();
 
   let mut doc = output.doc();
   let key = output.key();
 
-  doc.sign(&key).unwrap();
+  doc.sign(&key, &JsValue::UNDEFINED).unwrap();
 
   assert_eq!(doc.verify(), true);
+
+  // Signing again requires an explicit `resign`
+  assert!(doc.sign(&key, &JsValue::UNDEFINED).is_err());
+
+  let options: JsValue = JsValue::from_serde(&json!({ "resign": true })).unwrap();
+
+  doc.sign(&key, &options).unwrap();
+
+  assert_eq!(doc.verify(), true);
+}
+
+#[wasm_bindgen_test]
+fn test_document_from_json_validate_context() {
+  let output = Document::new(KeyType::Ed25519, None).unwrap();
+  let json = output.doc().to_json(&JsValue::UNDEFINED).unwrap();
+  let validate = JsValue::from_serde(&json!({ "validateContext": true })).unwrap();
+
+  // Documents without `@context` are only rejected when validation is enabled
+  assert!(Document::from_json(&json, &JsValue::UNDEFINED).is_ok());
+  assert!(Document::from_json(&json, &validate).is_err());
+
+  let mut value: Value = json.into_serde().unwrap();
+
+  value["@context"] = json!("https://www.w3.org/ns/did/v1");
+
+  let json = JsValue::from_serde(&value).unwrap();
+
+  assert!(Document::from_json(&json, &validate).is_ok());
+
+  value["@context"] = json!(["https://example.com/contexts/v1", "https://www.w3.org/ns/did/v1"]);
+
+  let json = JsValue::from_serde(&value).unwrap();
+
+  assert!(Document::from_json(&json, &JsValue::UNDEFINED).is_ok());
+  assert!(Document::from_json(&json, &validate).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_document_from_json_strict() {
+  let output = Document::new(KeyType::Ed25519, None).unwrap();
+  let strict = JsValue::from_serde(&json!({ "strict": true })).unwrap();
+
+  let mut value: Value = output.doc().to_json(&JsValue::UNDEFINED).unwrap().into_serde().unwrap();
+
+  value["@context"] = json!("https://www.w3.org/ns/did/v1");
+
+  let json = JsValue::from_serde(&value).unwrap();
+
+  assert!(Document::from_json(&json, &strict).is_ok());
+
+  // Unknown properties are preserved by default and rejected in strict mode
+  value["foo"] = json!(123);
+
+  let json = JsValue::from_serde(&value).unwrap();
+  let doc = Document::from_json(&json, &JsValue::UNDEFINED).unwrap();
+  let roundtrip: Value = doc.to_json(&JsValue::UNDEFINED).unwrap().into_serde().unwrap();
+
+  assert_eq!(roundtrip["foo"], 123);
+  assert!(Document::from_json(&json, &strict).is_err());
+
+  // Strict mode also requires the base context
+  value.as_object_mut().unwrap().remove("foo");
+  value.as_object_mut().unwrap().remove("@context");
+
+  let json = JsValue::from_serde(&value).unwrap();
+
+  assert!(Document::from_json(&json, &strict).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_document_resolve_key_by_public() {
+  let output = Document::new(KeyType::Ed25519, None).unwrap();
+
+  let mut doc = output.doc();
+  let key = KeyPair::new(KeyType::Ed25519).unwrap();
+  let method = Method::from_did(&doc.id(), &key, Some("key-2".into())).unwrap();
+
+  doc.insert_method(&method, None).unwrap();
+
+  let resolved = doc.resolve_key_by_public(&key.public()).unwrap();
+
+  assert_eq!(resolved.id().to_string(), method.id().to_string());
+
+  let resolved = doc.resolve_key_by_public(&output.key().public()).unwrap();
+
+  assert!(resolved.id().to_string().ends_with("#key"));
+
+  // The key is not part of the document
+  let other = KeyPair::new(KeyType::Ed25519).unwrap();
+
+  assert!(doc.resolve_key_by_public(&other.public()).is_err());
+  assert!(doc.resolve_key_by_public("0OIl").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_document_merge() {
+  let output = Document::new(KeyType::Ed25519, None).unwrap();
+
+  let base = output.doc();
+  let key = output.key();
+
+  let options: JsValue = JsValue::from_serde(&json!({ "rejectDuplicateKeys": true })).unwrap();
+
+  // The new method reuses the key of the authentication method
+  let mut updated = base.clone();
+  let method = Method::from_did(&base.id(), &key, Some("key-2".into())).unwrap();
+
+  updated.insert_method(&method, None).unwrap();
+
+  let diff: Value = base.diff(&updated, "1234", &key, None).unwrap().into_serde().unwrap();
+  let mut merged = base.clone();
+
+  assert!(merged.merge(&diff.to_string(), &options).is_err());
+  assert!(merged.resolve_key("#key-2").is_err());
+
+  merged.merge(&diff.to_string(), &JsValue::UNDEFINED).unwrap();
+
+  assert!(merged.resolve_key("#key-2").is_ok());
+
+  // The new method has a key of its own
+  let other = KeyPair::new(KeyType::Ed25519).unwrap();
+  let mut updated = base.clone();
+  let method = Method::from_did(&base.id(), &other, Some("key-3".into())).unwrap();
+
+  updated.insert_method(&method, None).unwrap();
+
+  let diff: Value = base.diff(&updated, "1234", &key, None).unwrap().into_serde().unwrap();
+  let mut merged = base.clone();
+
+  merged.merge(&diff.to_string(), &options).unwrap();
+
+  assert!(merged.resolve_key("#key-3").is_ok());
+}
+
+#[wasm_bindgen_test]
+fn test_document_builder() {
+  let key = KeyPair::new(KeyType::Ed25519).unwrap();
+  let did = DID::new(&key, None, None).unwrap();
+  let method = Method::from_did(&did, &key, None).unwrap();
+
+  let other = KeyPair::new(KeyType::Ed25519).unwrap();
+  let assertion = Method::from_did(&did, &other, Some("key-2".into())).unwrap();
+
+  let controller = DID::new(&KeyPair::new(KeyType::Ed25519).unwrap(), None, None).unwrap();
+
+  let service = JsValue::from_serde(&json!({
+    "id": format!("{}#linked-domain", did.to_string()),
+    "type": "LinkedDomains",
+    "serviceEndpoint": "https://example.com/",
+  }))
+  .unwrap();
+
+  let mut doc = DocumentBuilder::new(&method)
+    .method(&assertion, Some("AssertionMethod".into()))
+    .unwrap()
+    .service(&service)
+    .unwrap()
+    .controller(&controller)
+    .build()
+    .unwrap();
+
+  assert_eq!(doc.id().to_string(), did.to_string());
+  assert!(doc.resolve_key("#key").is_ok());
+  assert!(doc.resolve_key("#key-2").is_ok());
+
+  let json: Value = doc.to_json(&JsValue::UNDEFINED).unwrap().into_serde().unwrap();
+
+  assert_eq!(json["controller"], controller.to_string());
+  assert_eq!(json["assertionMethod"][0]["id"], assertion.id().to_string());
+  assert_eq!(json["service"][0]["type"], "LinkedDomains");
+
+  // Unknown scopes are rejected when the method is added
+  assert!(DocumentBuilder::new(&method)
+    .method(&assertion, Some("Unknown".into()))
+    .is_err());
+
+  // Duplicate methods are rejected when the document is built
+  let builder = DocumentBuilder::new(&method)
+    .method(&assertion, None)
+    .unwrap()
+    .method(&assertion, None)
+    .unwrap();
+
+  assert!(builder.build().is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_document_verify_against_pinned() {
+  let output = Document::new(KeyType::Ed25519, None).unwrap();
+
+  let mut pinned = output.doc();
+  let key = output.key();
+
+  let assertion = KeyPair::new(KeyType::Ed25519).unwrap();
+  let method = Method::from_did(&pinned.id(), &assertion, Some("key-2".into())).unwrap();
+
+  pinned.insert_method(&method, None).unwrap();
+
+  // Rotate the key of `#key-2`
+  let mut current = pinned.clone();
+  let rotated = KeyPair::new(KeyType::Ed25519).unwrap();
+  let method = Method::from_did(&pinned.id(), &rotated, Some("key-2".into())).unwrap();
+
+  current.remove_method(&method.id()).unwrap();
+  current.insert_method(&method, None).unwrap();
+
+  let data: JsValue = JsValue::from_serde(&json!({ "value": 123 })).unwrap();
+
+  // The key of the authentication method is unchanged
+  let args: JsValue = JsValue::from_serde(&json!({ "method": "#key", "secret": key.secret() })).unwrap();
+  let signed = current.sign_data(&data, &args).unwrap();
+
+  assert!(current.verify_against_pinned(&signed, &pinned).is_ok());
+
+  // The key of `#key-2` differs from the pinned document
+  let args: JsValue = JsValue::from_serde(&json!({ "method": "#key-2", "secret": rotated.secret() })).unwrap();
+  let signed = current.sign_data(&data, &args).unwrap();
+
+  assert_eq!(current.verify_data(&signed, &JsValue::UNDEFINED).unwrap(), true);
+  assert!(current.verify_against_pinned(&signed, &pinned).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_credential_builder() {
+  let output = Document::new(KeyType::Ed25519, None).unwrap();
+  let doc = output.doc();
+  let issuer = doc.id().to_string();
+
+  let subject = JsValue::from_serde(&json!({
+    "id": "did:example:subject",
+    "degree": "Bachelor of Science",
+  }))
+  .unwrap();
+
+  let status = JsValue::from_serde(&json!({
+    "id": "https://example.com/status/1",
+    "type": "RevocationList2020Status",
+  }))
+  .unwrap();
+
+  let credential = CredentialBuilder::new()
+    .issuer(&issuer)
+    .unwrap()
+    .subject(&subject)
+    .unwrap()
+    .type_("UniversityDegreeCredential")
+    .expiration_date("2030-01-01T00:00:00Z")
+    .unwrap()
+    .credential_status(&status)
+    .unwrap()
+    .build()
+    .unwrap();
+
+  assert_eq!(credential.issuer(), issuer);
+  assert_eq!(credential.expiration_date(), "2030-01-01T00:00:00Z");
+
+  let types: Vec<String> = credential.types().unwrap().into_serde().unwrap();
+
+  assert_eq!(types, ["VerifiableCredential", "UniversityDegreeCredential"]);
+
+  // The unsigned credential is accepted by `signCredential`
+  let args = JsValue::from_serde(&json!({
+    "method": "#key",
+    "secret": output.key().secret(),
+  }))
+  .unwrap();
+
+  let signed = doc.sign_credential(&credential.to_json().unwrap(), &args).unwrap();
+
+  assert_eq!(
+    doc
+      .verify_data(&signed.to_json().unwrap(), &JsValue::UNDEFINED)
+      .unwrap(),
+    true
+  );
+
+  // The issuer and a credential subject are required
+  assert!(CredentialBuilder::new().subject(&subject).unwrap().build().is_err());
+  assert!(CredentialBuilder::new().issuer(&issuer).unwrap().build().is_err());
+  assert!(CredentialBuilder::new().issuer("not a url").is_err());
+  assert!(CredentialBuilder::new().expiration_date("tomorrow").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_presentation_builder() {
+  let output = Document::new(KeyType::Ed25519, None).unwrap();
+  let doc = output.doc();
+  let holder = doc.id().to_string();
+
+  let args = JsValue::from_serde(&json!({
+    "method": "#key",
+    "secret": output.key().secret(),
+  }))
+  .unwrap();
+
+  let subject = JsValue::from_serde(&json!({ "id": holder })).unwrap();
+  let credential = CredentialBuilder::new()
+    .issuer(&holder)
+    .unwrap()
+    .subject(&subject)
+    .unwrap()
+    .build()
+    .unwrap();
+  let credential = doc.sign_credential(&credential.to_json().unwrap(), &args).unwrap();
+
+  let presentation = PresentationBuilder::new()
+    .holder(&holder)
+    .unwrap()
+    .credential(&credential.to_json().unwrap())
+    .unwrap()
+    .type_("CredentialManagerPresentation")
+    .build()
+    .unwrap();
+
+  let json: Value = presentation.to_json().unwrap().into_serde().unwrap();
+
+  assert_eq!(json["holder"], holder);
+  assert_eq!(
+    json["type"],
+    json!(["VerifiablePresentation", "CredentialManagerPresentation"])
+  );
+  assert_eq!(json["verifiableCredential"]["issuer"], holder);
+
+  // The unsigned presentation is accepted by `signPresentation`
+  let signed = doc.sign_presentation(&presentation.to_json().unwrap(), &args).unwrap();
+
+  assert_eq!(
+    doc
+      .verify_data(&signed.to_json().unwrap(), &JsValue::UNDEFINED)
+      .unwrap(),
+    true
+  );
+
+  // The holder is required
+  assert!(PresentationBuilder::new()
+    .credential(&credential.to_json().unwrap())
+    .unwrap()
+    .build()
+    .is_err());
+  assert!(PresentationBuilder::new().holder("not a url").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_document_verify_data_with_key() {
+  let output = Document::new(KeyType::Ed25519, None).unwrap();
+  let doc = output.doc();
+
+  let data = JsValue::from_serde(&json!({ "value": 123 })).unwrap();
+  let args = JsValue::from_serde(&json!({
+    "method": "#key",
+    "secret": output.key().secret(),
+  }))
+  .unwrap();
+
+  let signed = doc.sign_data(&data, &args).unwrap();
+
+  assert_eq!(doc.verify_data_with_key(&signed, &output.key().public()).unwrap(), true);
+
+  // The signing method is not resolved from the document
+  let other = Document::new(KeyType::Ed25519, None).unwrap();
+
+  assert_eq!(
+    other
+      .doc()
+      .verify_data_with_key(&signed, &output.key().public())
+      .unwrap(),
+    true
+  );
+
+  // The signature was not created with the given key
+  assert_eq!(doc.verify_data_with_key(&signed, &other.key().public()).unwrap(), false);
+  assert!(doc.verify_data_with_key(&signed, "0OIl").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_document_reconcile() {
+  let mut base = Document::new(KeyType::Ed25519, None).unwrap().doc();
+  let key = KeyPair::new(KeyType::Ed25519).unwrap();
+  let method = Method::from_did(&base.id(), &key, Some("key-1".into())).unwrap();
+
+  base.insert_method(&method, None).unwrap();
+
+  let mut local = base.clone();
+  let mut remote = base.clone();
+
+  // Removed on one device only
+  local.remove_method(&method.id()).unwrap();
+
+  let output: Value = local.reconcile(&remote, &base, None).unwrap().into_serde().unwrap();
+  let mut document = Document::from_json(&JsValue::from_serde(&output["document"]).unwrap(), &JsValue::NULL).unwrap();
+
+  assert_eq!(output["conflicts"], json!([]));
+  assert!(document.resolve_key("#key-1").is_err());
+
+  // Removed on one device and replaced on the other
+  let key = KeyPair::new(KeyType::Ed25519).unwrap();
+  let other = Method::from_did(&base.id(), &key, Some("key-1".into())).unwrap();
+
+  remote.remove_method(&method.id()).unwrap();
+  remote.insert_method(&other, None).unwrap();
+
+  let output: Value = local
+    .reconcile(&remote, &base, Some("remote".into()))
+    .unwrap()
+    .into_serde()
+    .unwrap();
+  let mut document = Document::from_json(&JsValue::from_serde(&output["document"]).unwrap(), &JsValue::NULL).unwrap();
+
+  assert_eq!(output["conflicts"], json!([method.id().to_string()]));
+  assert!(document.resolve_key("#key-1").is_ok());
 }
@@ -1,8 +1,7 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use identity::iota::Network;
-use identity::iota::TangleRef;
+use identity::iota::PublishResult;
 use identity::prelude::*;
 
 // A helper function to generate and new DID Document/KeyPair, sign the
@@ -21,12 +20,9 @@ pub async fn document(client: &Client) -> Result<(Document, KeyPair)> {
   println!("DID Document (signed) > {:#}", document);
   println!();
 
-  document.publish(client).await?;
+  let result: PublishResult = document.publish(client).await?;
 
-  let network: Network = document.id().into();
-  let explore: String = format!("{}/transaction/{}", network.explorer_url(), document.message_id());
-
-  println!("DID Document Transaction > {}", explore);
+  println!("DID Document Transaction > {}", result.explorer_url());
   println!();
 
   Ok((document, keypair))
@@ -1,8 +1,7 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use identity::iota::Network;
-use identity::iota::TangleRef;
+use identity::iota::PublishResult;
 use identity::prelude::*;
 
 #[smol_potat::main]
@@ -16,12 +15,9 @@ async fn main() -> Result<()> {
   document.sign(keypair.secret())?;
 
   // Use the client to publish the DID Document to the Tangle.
-  document.publish(&client).await?;
+  let result: PublishResult = document.publish(&client).await?;
 
-  let network: Network = document.id().into();
-  let explore: String = format!("{}/transaction/{}", network.explorer_url(), document.message_id());
-
-  println!("DID Document Transaction > {}", explore);
+  println!("DID Document Transaction > {}", result.explorer_url());
 
   Ok(())
 }
@@ -13,6 +13,11 @@ pub enum Error {
   StrongholdPasswordNotSet,
   StrongholdProcedureFailure,
   StrongholdInvalidAddress,
+  StrongholdVaultExists,
+  StrongholdVaultFull,
+  StrongholdVaultReserved,
+  StrongholdSnapshotExists,
+  StrongholdSnapshotUnauthenticated,
   MutexPoisoned,
 }
 
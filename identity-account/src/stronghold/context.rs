@@ -1,10 +1,12 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use core::cmp::Ordering;
 use core::ops::Deref;
 use core::ops::DerefMut;
 use hashbrown::HashMap;
 use hashbrown::HashSet;
+use iota_stronghold::Location;
 use iota_stronghold::Stronghold;
 use iota_stronghold::StrongholdFlags;
 use once_cell::sync::OnceCell;
@@ -21,20 +23,25 @@ use std::time::Duration;
 use std::time::Instant;
 use tokio::runtime::Runtime as AsyncRuntime;
 use tokio::sync::Mutex as AsyncMutex;
-use tokio::sync::MutexGuard as AsyncMutexGuard;
+use tokio::sync::OwnedMutexGuard as AsyncMutexGuard;
 use zeroize::Zeroize;
 
 use crate::error::Error;
 use crate::error::PleaseDontMakeYourOwnResult;
 use crate::error::Result;
+use crate::stronghold::store::STRONG_404;
 use crate::stronghold::SnapshotStatus;
 use crate::utils::fs;
 use crate::utils::EncryptionKey;
 
 pub type Password = EncryptionKey;
 
+type DatabaseMap = HashMap<PathBuf, Arc<AsyncMutex<Database>>>;
+
 pub struct Context {
-  database: Arc<AsyncMutex<Database>>,
+  // Databases are keyed by canonical snapshot path so that operations on
+  // different snapshots don't contend on a single lock
+  databases: Mutex<DatabaseMap>,
   runtime: Runtime,
 }
 
@@ -65,13 +72,23 @@ async fn clear_expired_passwords() -> Result<()> {
     .map(|(path, _)| path)
     .collect();
 
-  let mut database: _ = this.database.lock().await;
-
   for path in cleared {
-    if database.current_snapshot_eq(&path) {
-      database.unload(&this.runtime, &path, true).await?;
+    if let Some(database) = this.loaded_database(&path)? {
+      let mut guard: _ = Arc::clone(&database).lock_owned().await;
+
+      // The database may have been closed while waiting for the lock
+      if this.is_current(&path, &database)? {
+        if guard.current_snapshot_eq(&path) {
+          guard.unload(&this.runtime, &path, true).await?;
+        }
+
+        this.close(&path, &mut guard).await?;
+      }
     }
 
+    #[cfg(feature = "tracing")]
+    tracing::debug!(snapshot = %path.display(), "Password Expired");
+
     this.runtime.emit(&path, SnapshotStatus::locked())?;
   }
 
@@ -84,20 +101,10 @@ impl Context {
     static __POOL: OnceCell<AsyncRuntime> = OnceCell::new();
     static __SWEEP: Once = Once::new();
 
-    let this: &'static Self = __THIS.get_or_try_init::<_, Error>(|| {
-      let system: ActorSystem = SystemBuilder::new()
-        // // Disable the default actor system logger
-        // .log(slog::Logger::root(slog::Discard, slog::o!()))
-        .create()?;
-
-      let stronghold: Stronghold = Stronghold::init_stronghold_system(system, Vec::new(), Vec::new());
-      let database: Arc<AsyncMutex<Database>> = Arc::new(AsyncMutex::new(Database::new(stronghold)));
-
-      Ok(Self {
-        database,
-        runtime: Runtime::new(),
-      })
-    })?;
+    let this: &'static Self = __THIS.get_or_init(|| Self {
+      databases: Mutex::new(DatabaseMap::new()),
+      runtime: Runtime::new(),
+    });
 
     // Spawn a background-process to clear expired passwords
     __SWEEP.call_once(|| {
@@ -113,18 +120,151 @@ impl Context {
     Ok(this)
   }
 
-  pub(crate) async fn scope(
+  // Returns the database of the snapshot at `path`, creating it if needed.
+  fn database(&self, path: &Path) -> Result<Arc<AsyncMutex<Database>>> {
+    let key: PathBuf = fs::canonicalize(path);
+    let mut databases: MutexGuard<'_, DatabaseMap> = self.databases()?;
+
+    if let Some(database) = databases.get(&key) {
+      return Ok(Arc::clone(database));
+    }
+
+    // Each database has a separate actor system - Stronghold actors are named
+    // after their client and clients of different snapshots may share names
+    let system: ActorSystem = SystemBuilder::new()
+      // // Disable the default actor system logger
+      // .log(slog::Logger::root(slog::Discard, slog::o!()))
+      .create()?;
+
+    let stronghold: Stronghold = Stronghold::init_stronghold_system(system.clone(), Vec::new(), Vec::new());
+    let database: Arc<AsyncMutex<Database>> = Arc::new(AsyncMutex::new(Database::new(system, stronghold)));
+
+    databases.insert(key, Arc::clone(&database));
+
+    Ok(database)
+  }
+
+  // Returns the database of the snapshot at `path` if it was created.
+  fn loaded_database(&self, path: &Path) -> Result<Option<Arc<AsyncMutex<Database>>>> {
+    let key: PathBuf = fs::canonicalize(path);
+
+    self.databases().map(|databases| databases.get(&key).cloned())
+  }
+
+  // Returns `true` if `database` is the database of the snapshot at `path`.
+  fn is_current(&self, path: &Path, database: &Arc<AsyncMutex<Database>>) -> Result<bool> {
+    let key: PathBuf = fs::canonicalize(path);
+
+    self.databases().map(|databases| {
+      databases
+        .get(&key)
+        .map(|current| Arc::ptr_eq(current, database))
+        .unwrap_or(false)
+    })
+  }
+
+  // Removes the database of the snapshot at `path` and shuts down its actor
+  // system; the database must be unloaded.
+  async fn close(&self, path: &Path, database: &mut AsyncMutexGuard<Database>) -> Result<()> {
+    let key: PathBuf = fs::canonicalize(path);
+
+    self.databases()?.remove(&key);
+
+    database.shutdown().await;
+
+    Ok(())
+  }
+
+  fn databases(&self) -> Result<MutexGuard<'_, DatabaseMap>> {
+    self.databases.lock().map_err(|_| Error::MutexPoisoned)
+  }
+
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, path), fields(snapshot = %path.display())))]
+  async fn lock(&self, path: &Path) -> Result<AsyncMutexGuard<Database>> {
+    loop {
+      let database: Arc<AsyncMutex<Database>> = self.database(path)?;
+      let guard: AsyncMutexGuard<Database> = Arc::clone(&database).lock_owned().await;
+
+      // Retry if the database was closed while waiting for the lock
+      if self.is_current(path, &database)? {
+        return Ok(guard);
+      }
+    }
+  }
+
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+      skip(path, name, flags),
+      fields(snapshot = %path.display(), vault = %String::from_utf8_lossy(name)),
+      err
+    )
+  )]
+  pub(crate) async fn scope(path: &Path, name: &[u8], flags: &[StrongholdFlags]) -> Result<AsyncMutexGuard<Database>> {
+    let this: &Self = Self::get()?;
+    let mut database: _ = this.lock(path).await?;
+
+    if name == Database::INDEX_CLIENT {
+      return Err(Error::StrongholdVaultReserved);
+    }
+
+    database.switch_snapshot(&this.runtime, path).await?;
+
+    if database.activate(&this.runtime, path, name, flags).await? {
+      database.backfill(&this.runtime, path, name, flags).await?;
+    }
+
+    Ok(database)
+  }
+
+  // Returns the scope of a client that is about to be modified; the client is
+  // added to the snapshot index when the snapshot is written.
+  pub(crate) async fn scope_mut(
     path: &Path,
     name: &[u8],
     flags: &[StrongholdFlags],
-  ) -> Result<AsyncMutexGuard<'static, Database>> {
+  ) -> Result<AsyncMutexGuard<Database>> {
+    let mut database: AsyncMutexGuard<Database> = Self::scope(path, name, flags).await?;
+
+    database.clients_written.insert(name.into());
+
+    Ok(database)
+  }
+
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+      skip(path, from, into, flags),
+      fields(
+        snapshot = %path.display(),
+        from = %String::from_utf8_lossy(from),
+        into = %String::from_utf8_lossy(into)
+      ),
+      err
+    )
+  )]
+  pub(crate) async fn rename(path: &Path, from: &[u8], into: &[u8], flags: &[StrongholdFlags]) -> Result<()> {
     let this: &Self = Self::get()?;
-    let mut database: _ = this.database.lock().await;
+    let mut database: _ = this.lock(path).await?;
+
+    if from == Database::INDEX_CLIENT || into == Database::INDEX_CLIENT {
+      return Err(Error::StrongholdVaultReserved);
+    }
 
     database.switch_snapshot(&this.runtime, path).await?;
-    database.activate(&this.runtime, path, name, flags).await?;
+    database.migrate(&this.runtime, path, from, into, flags).await?;
+    database.clients_written.insert(into.into());
 
-    Ok(database)
+    Ok(())
+  }
+
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(path), fields(snapshot = %path.display()), err))]
+  pub(crate) async fn list_vaults(path: &Path) -> Result<Vec<Vec<u8>>> {
+    let this: &Self = Self::get()?;
+    let mut database: _ = this.lock(path).await?;
+
+    database.switch_snapshot(&this.runtime, path).await?;
+    database.vaults(&this.runtime, path).await
   }
 
   pub(crate) fn on_change<T>(listener: T) -> Result<()>
@@ -146,9 +286,10 @@ impl Context {
     Self::get().and_then(|this| this.runtime.snapshot_status(path))
   }
 
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(path, password), fields(snapshot = %path.display()), err))]
   pub(crate) async fn load(path: &Path, password: Password) -> Result<()> {
     let this: &Self = Self::get()?;
-    let mut database: _ = this.database.lock().await;
+    let mut database: _ = this.lock(path).await?;
 
     this.runtime.set_password(path, password)?;
     database.switch_snapshot(&this.runtime, path).await?;
@@ -157,18 +298,98 @@ impl Context {
     Ok(())
   }
 
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(path), fields(snapshot = %path.display()), err))]
   pub(crate) async fn unload(path: &Path, persist: bool) -> Result<()> {
     let this: &Self = Self::get()?;
-    let mut database: _ = this.database.lock().await;
+    let mut database: _ = this.lock(path).await?;
 
     database.flush(&this.runtime, path, persist).await?;
 
+    this.close(path, &mut database).await?;
+
     Ok(())
   }
 
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip(from, into), fields(from = %from.display(), into = %into.display()), err)
+  )]
+  pub(crate) async fn relocate(from: &Path, into: &Path) -> Result<()> {
+    let this: &Self = Self::get()?;
+
+    // Holding both database locks ensures no other operation on either
+    // snapshot is in progress. The locks are taken in a fixed order so
+    // concurrent relocations in opposite directions can't deadlock.
+    let (mut database, _target): (_, Option<_>) = match fs::canonicalize(from).cmp(&fs::canonicalize(into)) {
+      Ordering::Less => {
+        let database: _ = this.lock(from).await?;
+        (database, Some(this.lock(into).await?))
+      }
+      Ordering::Greater => {
+        let target: _ = this.lock(into).await?;
+        (this.lock(from).await?, Some(target))
+      }
+      Ordering::Equal => (this.lock(from).await?, None),
+    };
+
+    if into.exists() {
+      return Err(Error::StrongholdSnapshotExists);
+    }
+
+    let password: Password = this.runtime.password(from)?;
+
+    // Persist the current state and shutdown all actors of the snapshot
+    database.switch_snapshot(&this.runtime, from).await?;
+    database.flush(&this.runtime, from, true).await?;
+
+    this.close(from, &mut database).await?;
+
+    if from.exists() {
+      fs::ensure_directory(into)?;
+      std::fs::copy(from, into)?;
+      std::fs::remove_file(from)?;
+    }
+
+    this.runtime.remove_password(from)?;
+    this.runtime.set_password(into, password)?;
+    this.runtime.emit(from, SnapshotStatus::locked())?;
+    this.runtime.emit(into, this.runtime.snapshot_status(into)?)?;
+
+    Ok(())
+  }
+
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(path, password), fields(snapshot = %path.display()), err))]
+  pub(crate) async fn verify_snapshot(path: &Path, password: Password) -> Result<bool> {
+    let this: &Self = Self::get()?;
+    // Holding the database lock ensures the snapshot isn't written while it
+    // is being verified
+    let _database: _ = this.lock(path).await?;
+
+    if !path.exists() {
+      return Ok(false);
+    }
+
+    // Decrypt the snapshot with a detached Stronghold instance so the state of
+    // the active runtime is left untouched
+    let system: ActorSystem = SystemBuilder::new().create()?;
+    let result: Result<()> = read_detached(system.clone(), path, password).await;
+
+    // The actor system has no further use if the shutdown fails
+    let _: _ = system.shutdown().await;
+
+    match result {
+      Ok(()) => Ok(true),
+      // The snapshot is authenticated as a whole; a wrong password and a
+      // corrupt or truncated file can't be told apart
+      Err(Error::StrongholdResult(_)) => Err(Error::StrongholdSnapshotUnauthenticated),
+      Err(error) => Err(error),
+    }
+  }
+
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(path), fields(snapshot = %path.display()), err))]
   pub(crate) async fn save(path: &Path) -> Result<()> {
     let this: &Self = Self::get()?;
-    let mut database: _ = this.database.lock().await;
+    let mut database: _ = this.lock(path).await?;
 
     database.write(&this.runtime, path).await?;
 
@@ -180,12 +401,16 @@ impl Context {
 // =============================================================================
 
 pub(crate) struct Database {
+  // Actor system of the Stronghold actors
+  system: ActorSystem,
   // Stronghold client adapter
   stronghold: Stronghold,
   // Set of clients with initialized actors
   clients_active: HashSet<Vec<u8>>,
   // Set of clients with loaded snapshots
   clients_loaded: HashSet<Vec<u8>>,
+  // Set of clients modified since the snapshot was last written
+  clients_written: HashSet<Vec<u8>>,
   // Currently active Stronghold actor
   current_snapshot: Option<PathBuf>,
 }
@@ -193,22 +418,29 @@ pub(crate) struct Database {
 impl Database {
   const ACTOR_TIMEOUT: Duration = Duration::from_millis(300);
 
-  fn new(stronghold: Stronghold) -> Self {
+  // Client holding the names of all clients stored in the snapshot
+  const INDEX_CLIENT: &'static [u8] = b"__vaults";
+
+  fn new(system: ActorSystem, stronghold: Stronghold) -> Self {
     Self {
+      system,
       stronghold,
       clients_active: HashSet::new(),
       clients_loaded: HashSet::new(),
+      clients_written: HashSet::new(),
       current_snapshot: None,
     }
   }
 
+  // Activates the given client; returns `true` if the client state was loaded
+  // for the first time.
   async fn activate(
     &mut self,
     runtime: &Runtime,
     snapshot: &Path,
     client: &[u8],
     flags: &[StrongholdFlags],
-  ) -> Result<()> {
+  ) -> Result<bool> {
     runtime.set_password_access(snapshot)?;
 
     // Spawn a new actor or switch targets if this client was already spawned
@@ -241,8 +473,125 @@ impl Database {
       }
 
       self.clients_loaded.insert(client.into());
+
+      return Ok(true);
     }
 
+    Ok(false)
+  }
+
+  // Adds a client loaded from a snapshot written before the snapshot index
+  // existed to the index and re-activates the client.
+  async fn backfill(
+    &mut self,
+    runtime: &Runtime,
+    snapshot: &Path,
+    client: &[u8],
+    flags: &[StrongholdFlags],
+  ) -> Result<()> {
+    if !snapshot.exists() {
+      return Ok(());
+    }
+
+    if self.read_index(runtime, snapshot).await?.is_none() {
+      self.clients_written.insert(client.into());
+    }
+
+    self.activate(runtime, snapshot, client, flags).await?;
+
+    Ok(())
+  }
+
+  // Returns the names of all clients in the snapshot index and all clients
+  // modified since the snapshot was last written.
+  async fn vaults(&mut self, runtime: &Runtime, snapshot: &Path) -> Result<Vec<Vec<u8>>> {
+    let mut names: Vec<Vec<u8>> = self.read_index(runtime, snapshot).await?.unwrap_or_default();
+
+    for client in self.clients_written.iter() {
+      if !names.contains(client) {
+        names.push(client.clone());
+      }
+    }
+
+    Ok(names)
+  }
+
+  // Reads the snapshot index; returns `None` if the index was never written.
+  async fn read_index(&mut self, runtime: &Runtime, snapshot: &Path) -> Result<Option<Vec<Vec<u8>>>> {
+    self.activate(runtime, snapshot, Self::INDEX_CLIENT, &[]).await?;
+
+    let (data, status): (Vec<u8>, _) = self.stronghold.read_from_store(Self::index_location()).await;
+
+    match status.to_result() {
+      Ok(()) => decode_names(&data).map(Some),
+      Err(Error::StrongholdResult(message)) if message == STRONG_404 => Ok(None),
+      Err(error) => Err(error),
+    }
+  }
+
+  // Writes the names of all modified clients to the snapshot index.
+  async fn write_index(&mut self, runtime: &Runtime, snapshot: &Path) -> Result<()> {
+    if self.clients_written.is_empty() {
+      return Ok(());
+    }
+
+    let names: Vec<Vec<u8>> = self.vaults(runtime, snapshot).await?;
+
+    self
+      .stronghold
+      .write_to_store(Self::index_location(), encode_names(&names), None)
+      .await
+      .to_result()
+  }
+
+  fn index_location() -> Location {
+    Location::generic("__index", "")
+  }
+
+  async fn migrate(
+    &mut self,
+    runtime: &Runtime,
+    snapshot: &Path,
+    from: &[u8],
+    into: &[u8],
+    flags: &[StrongholdFlags],
+  ) -> Result<()> {
+    if self.clients_loaded.contains(into) {
+      return Err(Error::StrongholdVaultExists);
+    }
+
+    // Load the current client and persist its state to the snapshot
+    self.activate(runtime, snapshot, from, flags).await?;
+    self.write(runtime, snapshot).await?;
+
+    if self.clients_active.contains(into) {
+      self.stronghold.switch_actor_target(into.into()).await.to_result()?;
+    } else {
+      self
+        .stronghold
+        .spawn_stronghold_actor(into.into(), flags.to_vec())
+        .await
+        .to_result()?;
+
+      self.clients_active.insert(into.into());
+    }
+
+    let mut password: Vec<u8> = runtime.password(snapshot)?.to_vec();
+    let location: Option<PathBuf> = Some(snapshot.to_path_buf());
+
+    // Load the state of the former client into the new client
+    let result: Result<()> = self
+      .stronghold
+      .read_snapshot(into.into(), Some(from.into()), &password, None, location)
+      .await
+      .to_result();
+
+    password.zeroize();
+
+    result?;
+
+    self.clients_loaded.insert(into.into());
+
     Ok(())
   }
 
@@ -279,10 +628,19 @@ impl Database {
     // Clear local caches
     self.clients_active.clear();
     self.clients_loaded.clear();
+    self.clients_written.clear();
 
     Ok(())
   }
 
+  // Shuts down the actor system of the database.
+  async fn shutdown(&mut self) {
+    // The actor system has no further use if the shutdown fails
+    let _: _ = self.system.shutdown().await;
+
+    self.current_snapshot = None;
+  }
+
   fn current_snapshot_eq(&self, other: &Path) -> bool {
     matches!(
       self.current_snapshot.as_deref(),
@@ -310,6 +668,8 @@ impl Database {
   async fn write(&mut self, runtime: &Runtime, snapshot: &Path) -> Result<()> {
     fs::ensure_directory(snapshot)?;
 
+    self.write_index(runtime, snapshot).await?;
+
     let mut password: Vec<u8> = runtime.password(snapshot)?.to_vec();
     let location: Option<PathBuf> = Some(snapshot.to_path_buf());
 
@@ -321,7 +681,11 @@ impl Database {
 
     password.zeroize();
 
-    result
+    result?;
+
+    self.clients_written.clear();
+
+    Ok(())
   }
 
   async fn switch_snapshot(&mut self, runtime: &Runtime, snapshot: &Path) -> Result<()> {
@@ -340,6 +704,67 @@ impl Database {
   }
 }
 
+// Reads the snapshot at `path` into a Stronghold instance running on `system`.
+async fn read_detached(system: ActorSystem, path: &Path, password: Password) -> Result<()> {
+  let mut stronghold: Stronghold = Stronghold::init_stronghold_system(system, Vec::new(), Vec::new());
+  let client: Vec<u8> = Database::INDEX_CLIENT.to_vec();
+
+  stronghold
+    .spawn_stronghold_actor(client.clone(), Vec::new())
+    .await
+    .to_result()?;
+
+  let mut password: Vec<u8> = password.to_vec();
+  let location: Option<PathBuf> = Some(path.to_path_buf());
+
+  let result: Result<()> = stronghold
+    .read_snapshot(client.clone(), None, &password, None, location)
+    .await
+    .to_result();
+
+  password.zeroize();
+
+  stronghold.kill_stronghold(client, true).await.to_result()?;
+
+  result
+}
+
+// Encodes a list of names as a sequence of length-prefixed byte strings.
+fn encode_names(names: &[Vec<u8>]) -> Vec<u8> {
+  let mut output: Vec<u8> = Vec::new();
+
+  for name in names {
+    output.extend_from_slice(&(name.len() as u32).to_be_bytes());
+    output.extend_from_slice(name);
+  }
+
+  output
+}
+
+fn decode_names(mut data: &[u8]) -> Result<Vec<Vec<u8>>> {
+  let mut names: Vec<Vec<u8>> = Vec::new();
+
+  while !data.is_empty() {
+    if data.len() < 4 {
+      return Err(Error::InvalidResourceIndex);
+    }
+
+    let (size, rest): (&[u8], &[u8]) = data.split_at(4);
+    let size: usize = u32::from_be_bytes([size[0], size[1], size[2], size[3]]) as usize;
+
+    if rest.len() < size {
+      return Err(Error::InvalidResourceIndex);
+    }
+
+    let (name, rest): (&[u8], &[u8]) = rest.split_at(size);
+
+    names.push(name.to_vec());
+    data = rest;
+  }
+
+  Ok(names)
+}
+
 impl Deref for Database {
   type Target = Stronghold;
 
@@ -434,6 +859,12 @@ impl Runtime {
     Ok(())
   }
 
+  fn remove_password(&self, path: &Path) -> Result<()> {
+    self.password_store()?.remove(path);
+
+    Ok(())
+  }
+
   fn set_password_access(&self, path: &Path) -> Result<()> {
     if let Some((_, ref mut time)) = self.password_store()?.get_mut(path) {
       *time = Instant::now();
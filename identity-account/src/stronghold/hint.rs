@@ -3,6 +3,8 @@
 
 use iota_stronghold::Error;
 use iota_stronghold::RecordHint;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
 use crate::error::Result;
 
@@ -16,3 +18,39 @@ where
 pub fn default_hint() -> RecordHint {
   RecordHint::new([0; 24]).unwrap()
 }
+
+// Marks the last 16 bytes of hints created with `timestamp_hint`.
+const TIMESTAMP_TAG: &[u8; 16] = b"__timestamp_hint";
+
+/// Returns a hint encoding the current time as big-endian milliseconds since
+/// the Unix epoch in the first 8 bytes, followed by a fixed tag, as used by
+/// [`evict_oldest`][crate::stronghold::evict_oldest].
+pub fn timestamp_hint() -> RecordHint {
+  let millis: u64 = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|duration| duration.as_millis() as u64)
+    .unwrap_or_default();
+
+  let mut data: [u8; 24] = [0; 24];
+
+  data[..8].copy_from_slice(&millis.to_be_bytes());
+  data[8..].copy_from_slice(TIMESTAMP_TAG);
+
+  RecordHint::new(data).unwrap()
+}
+
+// Returns the timestamp of a hint created with `timestamp_hint`, or `None` if
+// the hint is not a timestamp hint.
+pub(crate) fn hint_timestamp(hint: &RecordHint) -> Option<u64> {
+  let data: &[u8] = hint.as_ref();
+
+  if data.len() != 24 || &data[8..] != TIMESTAMP_TAG {
+    return None;
+  }
+
+  let mut timestamp: [u8; 8] = [0; 8];
+
+  timestamp.copy_from_slice(&data[..8]);
+
+  Some(u64::from_be_bytes(timestamp))
+}
@@ -0,0 +1,60 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_stronghold::Location;
+
+use crate::stronghold::hint::hint_timestamp;
+use crate::stronghold::vault::Record;
+
+/// A hook selecting the record to evict from the `records` of the vault at
+/// `vault_path`; returns `None` if no record may be evicted.
+pub type Evict = fn(vault_path: &[u8], records: &[Record]) -> Option<Location>;
+
+/// A policy limiting the number of records stored at a vault path.
+///
+/// Inserting a record into a full vault path evicts records selected by the
+/// [`Evict`] hook first; the default hook is [`evict_oldest`]. Overwriting an
+/// existing record evicts nothing.
+#[derive(Clone, Copy, Debug)]
+pub struct RecordLimit {
+  max_records: usize,
+  evict: Evict,
+}
+
+impl RecordLimit {
+  /// Creates a new `RecordLimit` of `max_records` records per vault path.
+  pub fn new(max_records: usize) -> Self {
+    Self {
+      max_records,
+      evict: evict_oldest,
+    }
+  }
+
+  /// Sets the hook selecting the record to evict.
+  pub fn evict(mut self, value: Evict) -> Self {
+    self.evict = value;
+    self
+  }
+
+  /// Returns the maximum number of records per vault path.
+  pub fn max_records(&self) -> usize {
+    self.max_records
+  }
+
+  pub(crate) fn select(&self, vault_path: &[u8], records: &[Record]) -> Option<Location> {
+    (self.evict)(vault_path, records)
+  }
+}
+
+/// Selects the record with the oldest timestamp encoded with
+/// [`timestamp_hint`][crate::stronghold::timestamp_hint].
+///
+/// Records with any other hint are never evicted. Records are expected to be
+/// stored at counter locations.
+pub fn evict_oldest(vault_path: &[u8], records: &[Record]) -> Option<Location> {
+  records
+    .iter()
+    .filter_map(|(counter, hint)| hint_timestamp(hint).map(|timestamp| (timestamp, *counter)))
+    .min()
+    .map(|(_, counter)| Location::counter::<_, usize>(vault_path.to_vec(), Some(counter)))
+}
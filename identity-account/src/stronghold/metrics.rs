@@ -0,0 +1,253 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::convert::TryFrom;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use iota_stronghold::Procedure;
+use once_cell::sync::OnceCell;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::error::Error;
+use crate::error::Result;
+
+type HistogramMap = BTreeMap<ProcedureKind, Histogram>;
+
+/// The upper bounds (in microseconds) of the histogram buckets; durations
+/// above the last bound are counted in an additional overflow bucket.
+const BUCKETS: [u64; 12] = [
+  100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000, 250_000, 1_000_000,
+];
+
+/// The kind of a Stronghold runtime [`procedure`][`Procedure`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProcedureKind {
+  SLIP10Generate,
+  SLIP10Derive,
+  BIP39Recover,
+  BIP39Generate,
+  BIP39MnemonicSentence,
+  Ed25519PublicKey,
+  Ed25519Sign,
+}
+
+impl ProcedureKind {
+  /// Returns the kind of the given `procedure`.
+  pub fn of(procedure: &Procedure) -> Self {
+    match procedure {
+      Procedure::SLIP10Generate { .. } => Self::SLIP10Generate,
+      Procedure::SLIP10Derive { .. } => Self::SLIP10Derive,
+      Procedure::BIP39Recover { .. } => Self::BIP39Recover,
+      Procedure::BIP39Generate { .. } => Self::BIP39Generate,
+      Procedure::BIP39MnemonicSentence { .. } => Self::BIP39MnemonicSentence,
+      Procedure::Ed25519PublicKey { .. } => Self::Ed25519PublicKey,
+      Procedure::Ed25519Sign { .. } => Self::Ed25519Sign,
+    }
+  }
+
+  /// Returns the name of the procedure kind.
+  pub const fn as_str(&self) -> &'static str {
+    match self {
+      Self::SLIP10Generate => "SLIP10Generate",
+      Self::SLIP10Derive => "SLIP10Derive",
+      Self::BIP39Recover => "BIP39Recover",
+      Self::BIP39Generate => "BIP39Generate",
+      Self::BIP39MnemonicSentence => "BIP39MnemonicSentence",
+      Self::Ed25519PublicKey => "Ed25519PublicKey",
+      Self::Ed25519Sign => "Ed25519Sign",
+    }
+  }
+}
+
+impl Display for ProcedureKind {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.write_str(self.as_str())
+  }
+}
+
+/// An aggregated histogram of procedure durations.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Histogram {
+  buckets: [u64; BUCKETS.len() + 1],
+  count: u64,
+  total: Duration,
+  min: Duration,
+  max: Duration,
+}
+
+impl Histogram {
+  /// Creates a new `Histogram` without any recorded durations.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Records a single `duration`.
+  pub fn record(&mut self, duration: Duration) {
+    let micros: u64 = duration.as_micros() as u64;
+    let index: usize = BUCKETS
+      .iter()
+      .position(|bound| micros <= *bound)
+      .unwrap_or(BUCKETS.len());
+
+    self.buckets[index] += 1;
+
+    if self.count == 0 || duration < self.min {
+      self.min = duration;
+    }
+
+    if duration > self.max {
+      self.max = duration;
+    }
+
+    self.count += 1;
+    self.total += duration;
+  }
+
+  /// Returns the number of recorded durations.
+  pub fn count(&self) -> u64 {
+    self.count
+  }
+
+  /// Returns the sum of all recorded durations.
+  pub fn total(&self) -> Duration {
+    self.total
+  }
+
+  /// Returns the shortest recorded duration.
+  pub fn min(&self) -> Duration {
+    self.min
+  }
+
+  /// Returns the longest recorded duration.
+  pub fn max(&self) -> Duration {
+    self.max
+  }
+
+  /// Returns the mean of all recorded durations.
+  pub fn mean(&self) -> Duration {
+    if self.count == 0 {
+      Duration::default()
+    } else if let Ok(count) = u32::try_from(self.count) {
+      self.total / count
+    } else {
+      Duration::from_nanos((self.total.as_nanos() / self.count as u128) as u64)
+    }
+  }
+
+  /// Returns an iterator over the upper bound and number of durations of each
+  /// bucket.
+  ///
+  /// The bound of the last bucket is `None`; it counts all durations above the
+  /// preceding bound.
+  pub fn buckets(&self) -> impl Iterator<Item = (Option<Duration>, u64)> + '_ {
+    BUCKETS
+      .iter()
+      .map(|bound| Some(Duration::from_micros(*bound)))
+      .chain(Some(None))
+      .zip(self.buckets.iter().copied())
+  }
+}
+
+/// Timing metrics of all procedures executed by a [`Vault`][crate::stronghold::Vault].
+///
+/// Metrics are collected process-wide and keyed by [`ProcedureKind`].
+#[derive(Clone, Copy, Debug)]
+pub struct ProcedureMetrics;
+
+impl ProcedureMetrics {
+  /// Returns a snapshot of the histograms of all executed procedure kinds.
+  pub fn snapshot() -> Result<BTreeMap<ProcedureKind, Histogram>> {
+    histograms().map(|histograms| histograms.clone())
+  }
+
+  /// Returns a snapshot of the histogram of the given procedure `kind`.
+  pub fn get(kind: ProcedureKind) -> Result<Histogram> {
+    histograms().map(|histograms| histograms.get(&kind).cloned().unwrap_or_default())
+  }
+
+  /// Clears all recorded durations.
+  pub fn reset() -> Result<()> {
+    histograms().map(|mut histograms| histograms.clear())
+  }
+}
+
+/// Measures the duration of a single procedure execution.
+pub(crate) struct ProcedureTimer {
+  kind: ProcedureKind,
+  start: Instant,
+}
+
+impl ProcedureTimer {
+  pub(crate) fn start(procedure: &Procedure) -> Self {
+    Self {
+      kind: ProcedureKind::of(procedure),
+      start: Instant::now(),
+    }
+  }
+
+  pub(crate) fn stop(self) -> Result<()> {
+    let duration: Duration = self.start.elapsed();
+
+    histograms().map(|mut histograms| histograms.entry(self.kind).or_default().record(duration))
+  }
+}
+
+fn histograms() -> Result<MutexGuard<'static, HistogramMap>> {
+  static __THIS: OnceCell<Mutex<HistogramMap>> = OnceCell::new();
+
+  __THIS
+    .get_or_init(|| Mutex::new(HistogramMap::new()))
+    .lock()
+    .map_err(|_| Error::MutexPoisoned)
+}
+
+#[cfg(test)]
+mod tests {
+  use core::time::Duration;
+
+  use super::Histogram;
+
+  #[test]
+  fn test_histogram() {
+    let mut histogram: Histogram = Histogram::new();
+
+    assert_eq!(histogram.count(), 0);
+    assert_eq!(histogram.mean(), Duration::default());
+
+    histogram.record(Duration::from_micros(50));
+    histogram.record(Duration::from_micros(150));
+    histogram.record(Duration::from_secs(2));
+
+    assert_eq!(histogram.count(), 3);
+    assert_eq!(histogram.min(), Duration::from_micros(50));
+    assert_eq!(histogram.max(), Duration::from_secs(2));
+    assert_eq!(histogram.total(), Duration::from_micros(2_000_200));
+    assert_eq!(histogram.mean(), Duration::from_nanos(666_733_333));
+
+    let buckets: Vec<(Option<Duration>, u64)> = histogram.buckets().collect();
+
+    assert_eq!(buckets[0], (Some(Duration::from_micros(100)), 1));
+    assert_eq!(buckets[1], (Some(Duration::from_micros(250)), 1));
+    assert_eq!(buckets[buckets.len() - 1], (None, 1));
+    assert_eq!(buckets.iter().map(|(_, count)| count).sum::<u64>(), 3);
+  }
+
+  #[test]
+  fn test_histogram_mean_large_count() {
+    let count: u64 = u64::from(u32::MAX) * 4;
+
+    let histogram: Histogram = Histogram {
+      count,
+      total: Duration::from_micros(count),
+      ..Histogram::new()
+    };
+
+    // The count does not fit the divisor of `Duration`
+    assert_eq!(histogram.mean(), Duration::from_micros(1));
+  }
+}
@@ -3,6 +3,9 @@
 
 mod context;
 mod hint;
+mod limit;
+#[cfg(feature = "metrics")]
+mod metrics;
 mod records;
 mod result;
 mod snapshot;
@@ -14,6 +17,16 @@ pub use self::context::Context;
 pub use self::context::Password;
 pub use self::hint::default_hint;
 pub use self::hint::hint;
+pub use self::hint::timestamp_hint;
+pub use self::limit::evict_oldest;
+pub use self::limit::Evict;
+pub use self::limit::RecordLimit;
+#[cfg(feature = "metrics")]
+pub use self::metrics::Histogram;
+#[cfg(feature = "metrics")]
+pub use self::metrics::ProcedureKind;
+#[cfg(feature = "metrics")]
+pub use self::metrics::ProcedureMetrics;
 pub use self::records::RecordIndex;
 pub use self::records::RecordTag;
 pub use self::records::Records;
@@ -9,6 +9,7 @@ use std::time::Duration;
 use crate::error::Result;
 use crate::stronghold::Context;
 use crate::stronghold::Password;
+use crate::stronghold::RecordLimit;
 use crate::stronghold::Records;
 use crate::stronghold::SnapshotStatus;
 use crate::stronghold::Store;
@@ -31,6 +32,21 @@ impl Snapshot {
     Context::on_change(listener)
   }
 
+  /// Checks the integrity of the snapshot at `path` without loading it into
+  /// the active runtime.
+  ///
+  /// Returns `false` if no snapshot exists at `path`. Fails with
+  /// [`StrongholdSnapshotUnauthenticated`][crate::error::Error::StrongholdSnapshotUnauthenticated]
+  /// if the snapshot can't be decrypted and authenticated with `password` -
+  /// the snapshot is encrypted as a whole, so a wrong password can't be told
+  /// apart from a corrupt or truncated file.
+  pub async fn verify_snapshot<P>(path: &P, password: Password) -> Result<bool>
+  where
+    P: AsRef<Path> + ?Sized,
+  {
+    Context::verify_snapshot(path.as_ref(), password).await
+  }
+
   pub fn new<P>(path: &P) -> Self
   where
     P: AsRef<Path> + ?Sized,
@@ -51,6 +67,14 @@ impl Snapshot {
     Vault::new(&self.path, name, flags)
   }
 
+  /// Returns a vault enforcing the record `limit` on each vault path.
+  pub fn vault_with_limit<T>(&self, name: &T, flags: &[StrongholdFlags], limit: RecordLimit) -> Vault<'_>
+  where
+    T: AsRef<[u8]> + ?Sized,
+  {
+    Vault::with_limit(&self.path, name, flags, limit)
+  }
+
   pub fn store<T>(&self, name: &T, flags: &[StrongholdFlags]) -> Store<'_>
   where
     T: AsRef<[u8]> + ?Sized,
@@ -65,6 +89,14 @@ impl Snapshot {
     Records::new(&self.path, name, flags)
   }
 
+  /// Returns the names of all vaults stored in the snapshot.
+  ///
+  /// Vaults are listed once a record was written to them. Vaults of snapshots
+  /// written by earlier versions are listed after they were first opened.
+  pub async fn list_vaults(&self) -> Result<Vec<Vec<u8>>> {
+    Context::list_vaults(&self.path).await
+  }
+
   pub fn status(&self) -> Result<SnapshotStatus> {
     Context::snapshot_status(&self.path)
   }
@@ -84,4 +116,19 @@ impl Snapshot {
   pub async fn save(&self) -> Result<()> {
     Context::save(&self.path).await
   }
+
+  /// Persists the snapshot and moves it to `path`; the password is carried
+  /// over to the new location.
+  ///
+  /// Fails if a file already exists at `path`.
+  pub async fn relocate<P>(&mut self, path: &P) -> Result<()>
+  where
+    P: AsRef<Path> + ?Sized,
+  {
+    Context::relocate(&self.path, path.as_ref()).await?;
+
+    self.path = path.as_ref().to_path_buf();
+
+    Ok(())
+  }
 }
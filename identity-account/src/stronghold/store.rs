@@ -11,7 +11,7 @@ use crate::error::PleaseDontMakeYourOwnResult;
 use crate::error::Result;
 use crate::stronghold::Context;
 
-const STRONG_404: &str = "Unable to read from store";
+pub(crate) const STRONG_404: &str = "Unable to read from store";
 
 #[derive(Debug)]
 pub struct Store<'snapshot> {
@@ -74,7 +74,7 @@ impl Store<'_> {
   where
     T: Into<Vec<u8>>,
   {
-    Context::scope(self.path, &self.name, &self.flags)
+    Context::scope_mut(self.path, &self.name, &self.flags)
       .await?
       .write_to_store(location, payload.into(), ttl)
       .await
@@ -83,7 +83,7 @@ impl Store<'_> {
 
   /// Removes a record.
   pub async fn del(&self, location: Location) -> Result<()> {
-    Context::scope(self.path, &self.name, &self.flags)
+    Context::scope_mut(self.path, &self.name, &self.flags)
       .await?
       .delete_from_store(location)
       .await
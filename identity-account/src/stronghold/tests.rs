@@ -2,12 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use core::iter;
+use crypto::keys::slip10::Chain;
 use futures::executor::block_on;
+use futures::future::join;
 use iota_stronghold::Location;
+use iota_stronghold::Procedure;
+use iota_stronghold::SLIP10DeriveInput;
 use rand::distributions::Alphanumeric;
 use rand::rngs::OsRng;
 use rand::Rng;
 use rusty_fork::rusty_fork_test;
+#[cfg(feature = "metrics")]
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 use std::path::PathBuf;
@@ -16,9 +22,21 @@ use std::time::Duration;
 use std::time::Instant;
 
 use crate::error::Error;
+use crate::stronghold::default_hint;
+use crate::stronghold::timestamp_hint;
+use crate::stronghold::vault::Record;
+#[cfg(feature = "metrics")]
+use crate::stronghold::Histogram;
+#[cfg(feature = "metrics")]
+use crate::stronghold::ProcedureKind;
+#[cfg(feature = "metrics")]
+use crate::stronghold::ProcedureMetrics;
+use crate::stronghold::ProcedureResult;
+use crate::stronghold::RecordLimit;
 use crate::stronghold::Snapshot;
 use crate::stronghold::SnapshotStatus;
 use crate::stronghold::Store;
+use crate::stronghold::Vault;
 use crate::utils::derive_encryption_key;
 use crate::utils::EncryptionKey;
 
@@ -224,6 +242,39 @@ rusty_fork_test! {
     })
   }
 
+  #[test]
+  fn test_store_concurrent_snapshots() {
+    block_on(async {
+      let password: EncryptionKey = derive_encryption_key("my-password:test_store_concurrent_snapshots");
+      let snapshot1: Snapshot = open_snapshot(&generate_filename(), password).await;
+      let snapshot2: Snapshot = open_snapshot(&generate_filename(), password).await;
+
+      // Both snapshots use a store with the same name
+      let store1: Store = snapshot1.store(b"store", &[]);
+      let store2: Store = snapshot2.store(b"store", &[]);
+
+      let (result1, result2): _ = join(
+        store1.set(location("A"), b"foo".to_vec(), None),
+        store2.set(location("A"), b"bar".to_vec(), None),
+      )
+      .await;
+
+      result1.unwrap();
+      result2.unwrap();
+
+      let (data1, data2): _ = join(store1.get(location("A")), store2.get(location("A"))).await;
+
+      assert_eq!(data1.unwrap(), b"foo".to_vec());
+      assert_eq!(data2.unwrap(), b"bar".to_vec());
+
+      snapshot1.unload(true).await.unwrap();
+      snapshot2.unload(true).await.unwrap();
+
+      fs::remove_file(store1.path()).unwrap();
+      fs::remove_file(store2.path()).unwrap();
+    })
+  }
+
   #[test]
   fn test_store_persistence() {
     block_on(async {
@@ -261,4 +312,471 @@ rusty_fork_test! {
       }
     })
   }
+
+  #[test]
+  fn test_vault_slip10_derive_ed25519() {
+    block_on(async {
+      let password: EncryptionKey = derive_encryption_key("my-password:test_vault_slip10_derive_ed25519");
+      let snapshot: Snapshot = open_snapshot(&generate_filename(), password).await;
+
+      let vault: Vault<'_> = snapshot.vault(b"vault", &[]);
+      let chain: Chain = Chain::from_u32_hardened(vec![0, 0, 0]);
+
+      vault.slip10_generate(location("seed"), default_hint(), None).await.unwrap();
+
+      let public: [u8; 32] = vault
+        .slip10_derive_ed25519(
+          chain,
+          SLIP10DeriveInput::Seed(location("seed")),
+          location("key"),
+          default_hint(),
+        )
+        .await
+        .unwrap();
+
+      assert_eq!(public, vault.ed25519_public_key(location("key")).await.unwrap());
+
+      snapshot.unload(true).await.unwrap();
+
+      fs::remove_file(vault.path()).unwrap();
+    })
+  }
+
+  #[test]
+  fn test_vault_generate_ed25519() {
+    block_on(async {
+      let password: EncryptionKey = derive_encryption_key("my-password:test_vault_generate_ed25519");
+      let snapshot: Snapshot = open_snapshot(&generate_filename(), password).await;
+      let vault: Vault<'_> = snapshot.vault(b"vault", &[]);
+
+      let public: [u8; 32] = vault.generate_ed25519(location("key"), default_hint()).await.unwrap();
+
+      assert_eq!(public, vault.ed25519_public_key(location("key")).await.unwrap());
+
+      snapshot.unload(false).await.unwrap();
+    })
+  }
+
+  #[cfg(feature = "metrics")]
+  #[test]
+  fn test_vault_metrics() {
+    block_on(async {
+      let password: EncryptionKey = derive_encryption_key("my-password:test_vault_metrics");
+      let snapshot: Snapshot = open_snapshot(&generate_filename(), password).await;
+      let vault: Vault<'_> = snapshot.vault(b"metrics", &[]);
+
+      ProcedureMetrics::reset().unwrap();
+
+      vault.generate_ed25519(location("key"), default_hint()).await.unwrap();
+      vault.ed25519_public_key(location("key")).await.unwrap();
+
+      let histograms: BTreeMap<ProcedureKind, Histogram> = ProcedureMetrics::snapshot().unwrap();
+
+      assert_eq!(histograms[&ProcedureKind::SLIP10Generate].count(), 1);
+      assert_eq!(histograms[&ProcedureKind::Ed25519PublicKey].count(), 2);
+      assert!(!histograms.contains_key(&ProcedureKind::Ed25519Sign));
+
+      snapshot.unload(false).await.unwrap();
+    })
+  }
+
+  #[test]
+  fn test_vault_write_then() {
+    block_on(async {
+      let password: EncryptionKey = derive_encryption_key("my-password:test_vault_write_then");
+      let snapshot: Snapshot = open_snapshot(&generate_filename(), password).await;
+      let vault: Vault<'_> = snapshot.vault(b"persistence", &[]);
+
+      let procedure: Procedure = Procedure::Ed25519PublicKey {
+        private_key: location("key"),
+      };
+
+      let result: ProcedureResult = vault
+        .write_then(location("key"), [7; 32].to_vec(), default_hint(), &[], procedure)
+        .await
+        .unwrap();
+
+      let public: [u8; 32] = match result {
+        ProcedureResult::Ed25519PublicKey(public) => public,
+        _ => panic!("unexpected procedure result"),
+      };
+
+      assert_eq!(public, vault.ed25519_public_key(location("key")).await.unwrap());
+
+      snapshot.unload(false).await.unwrap();
+    })
+  }
+
+  #[test]
+  fn test_vault_derive_and_sign() {
+    block_on(async {
+      let password: EncryptionKey = derive_encryption_key("my-password:test_vault_derive_and_sign");
+      let snapshot: Snapshot = open_snapshot(&generate_filename(), password).await;
+      let vault: Vault<'_> = snapshot.vault(b"ephemeral", &[]);
+
+      vault.slip10_generate(location("seed"), default_hint(), None).await.unwrap();
+
+      let signature: [u8; 64] = vault
+        .derive_and_sign(
+          Chain::from_u32_hardened(vec![0, 1]),
+          SLIP10DeriveInput::Seed(location("seed")),
+          b"message".to_vec(),
+        )
+        .await
+        .unwrap();
+
+      vault
+        .slip10_derive(
+          Chain::from_u32_hardened(vec![0, 1]),
+          SLIP10DeriveInput::Seed(location("seed")),
+          location("key"),
+          default_hint(),
+        )
+        .await
+        .unwrap();
+
+      // Ed25519 signatures are deterministic
+      assert_eq!(
+        signature,
+        vault.ed25519_sign(b"message".to_vec(), location("key")).await.unwrap()
+      );
+
+      snapshot.unload(false).await.unwrap();
+    })
+  }
+
+  #[test]
+  fn test_vault_rename() {
+    block_on(async {
+      let password: EncryptionKey = derive_encryption_key("my-password:test_vault_rename");
+      let snapshot: Snapshot = open_snapshot(&generate_filename(), password).await;
+
+      let mut vault: Vault<'_> = snapshot.vault(b"rename-a", &[]);
+      let chain: Chain = Chain::from_u32_hardened(vec![0, 0, 0]);
+
+      vault.slip10_generate(location("seed"), default_hint(), None).await.unwrap();
+
+      let public: [u8; 32] = vault
+        .slip10_derive_ed25519(
+          chain,
+          SLIP10DeriveInput::Seed(location("seed")),
+          location("key"),
+          default_hint(),
+        )
+        .await
+        .unwrap();
+
+      vault.rename(b"rename-b").await.unwrap();
+
+      assert_eq!(vault.name(), b"rename-b");
+      assert_eq!(public, vault.ed25519_public_key(location("key")).await.unwrap());
+
+      // The new name is already in use
+      assert!(matches!(
+        snapshot.vault(b"rename-c", &[]).rename(b"rename-b").await.unwrap_err(),
+        Error::StrongholdVaultExists
+      ));
+
+      snapshot.unload(true).await.unwrap();
+
+      fs::remove_file(vault.path()).unwrap();
+    })
+  }
+
+  #[test]
+  fn test_list_vaults() {
+    block_on(async {
+      let password: EncryptionKey = derive_encryption_key("my-password:test_list_vaults");
+      let filename: PathBuf = generate_filename();
+      let snapshot: Snapshot = open_snapshot(&filename, password).await;
+
+      assert!(snapshot.list_vaults().await.unwrap().is_empty());
+
+      snapshot
+        .vault(b"vault-a", &[])
+        .slip10_generate(location("seed"), default_hint(), None)
+        .await
+        .unwrap();
+
+      snapshot
+        .vault(b"vault-b", &[])
+        .slip10_generate(location("seed"), default_hint(), None)
+        .await
+        .unwrap();
+
+      let mut vault: Vault<'_> = snapshot.vault(b"vault-c", &[]);
+      vault.slip10_generate(location("seed"), default_hint(), None).await.unwrap();
+      vault.rename(b"vault-d").await.unwrap();
+
+      snapshot.unload(true).await.unwrap();
+
+      let snapshot: Snapshot = load_snapshot(&filename, password).await;
+      let vaults: Vec<Vec<u8>> = snapshot.list_vaults().await.unwrap();
+
+      assert_eq!(vaults.len(), 4);
+      assert!(vaults.contains(&b"vault-a".to_vec()));
+      assert!(vaults.contains(&b"vault-b".to_vec()));
+      assert!(vaults.contains(&b"vault-c".to_vec()));
+      assert!(vaults.contains(&b"vault-d".to_vec()));
+
+      snapshot.unload(false).await.unwrap();
+
+      fs::remove_file(&filename).unwrap();
+    })
+  }
+
+  #[test]
+  fn test_list_vaults_written() {
+    block_on(async {
+      let password: EncryptionKey = derive_encryption_key("my-password:test_list_vaults_written");
+      let filename: PathBuf = generate_filename();
+      let snapshot: Snapshot = open_snapshot(&filename, password).await;
+
+      snapshot
+        .vault(b"written", &[])
+        .slip10_generate(location("seed"), default_hint(), None)
+        .await
+        .unwrap();
+
+      assert!(snapshot.vault(b"read", &[]).records(b"").await.unwrap().is_empty());
+      assert!(snapshot.store("unused", &[]).get(location("none")).await.is_ok());
+
+      assert_eq!(snapshot.list_vaults().await.unwrap(), vec![b"written".to_vec()]);
+
+      assert!(matches!(
+        snapshot.vault(b"__vaults", &[]).records(b"").await.unwrap_err(),
+        Error::StrongholdVaultReserved
+      ));
+
+      assert!(matches!(
+        snapshot.vault(b"written", &[]).rename(b"__vaults").await.unwrap_err(),
+        Error::StrongholdVaultReserved
+      ));
+
+      snapshot.unload(true).await.unwrap();
+
+      let snapshot: Snapshot = load_snapshot(&filename, password).await;
+
+      assert_eq!(snapshot.list_vaults().await.unwrap(), vec![b"written".to_vec()]);
+
+      snapshot.unload(false).await.unwrap();
+
+      fs::remove_file(&filename).unwrap();
+    })
+  }
+
+  #[test]
+  fn test_snapshot_relocate() {
+    block_on(async {
+      let password: EncryptionKey = derive_encryption_key("my-password:test_snapshot_relocate");
+      let filename: PathBuf = generate_filename();
+      let mut snapshot: Snapshot = open_snapshot(&filename, password).await;
+
+      snapshot
+        .store("", &[])
+        .set(location("persists"), "STRONGHOLD".to_string(), None)
+        .await
+        .unwrap();
+
+      let relocated: PathBuf = generate_filename();
+
+      snapshot.relocate(&relocated).await.unwrap();
+
+      assert_eq!(snapshot.path(), relocated);
+      assert!(!filename.exists());
+      assert!(relocated.exists());
+
+      let data: Vec<u8> = snapshot.store("", &[]).get(location("persists")).await.unwrap();
+
+      assert_eq!(data, b"STRONGHOLD");
+
+      // The target path is already in use
+      let mut other: Snapshot = open_snapshot(&generate_filename(), password).await;
+
+      assert!(matches!(
+        other.relocate(&relocated).await.unwrap_err(),
+        Error::StrongholdSnapshotExists
+      ));
+
+      snapshot.unload(false).await.unwrap();
+
+      fs::remove_file(&relocated).unwrap();
+    })
+  }
+
+  #[test]
+  fn test_snapshot_path_alias() {
+    block_on(async {
+      let password: EncryptionKey = derive_encryption_key("my-password:test_snapshot_path_alias");
+      let filename: PathBuf = generate_filename();
+      let alias: PathBuf = Path::new(ROOT).join("..").join(ROOT).join(filename.file_name().unwrap());
+
+      let snapshot1: Snapshot = open_snapshot(&filename, password).await;
+      let snapshot2: Snapshot = load_snapshot(&alias, password).await;
+
+      let store1: Store = snapshot1.store(b"store", &[]);
+      let store2: Store = snapshot2.store(b"store", &[]);
+
+      // Both paths refer to the same snapshot and share a single database
+      store1.set(location("A"), b"foo".to_vec(), None).await.unwrap();
+      store2.set(location("B"), b"bar".to_vec(), None).await.unwrap();
+
+      assert_eq!(store1.get(location("B")).await.unwrap(), b"bar".to_vec());
+      assert_eq!(store2.get(location("A")).await.unwrap(), b"foo".to_vec());
+
+      snapshot1.unload(true).await.unwrap();
+      snapshot2.unload(false).await.unwrap();
+
+      fs::remove_file(&filename).unwrap();
+    })
+  }
+
+  #[test]
+  fn test_verify_snapshot() {
+    block_on(async {
+      let password: EncryptionKey = derive_encryption_key("my-password:test_verify_snapshot");
+      let filename: PathBuf = generate_filename();
+
+      assert!(!Snapshot::verify_snapshot(&filename, password).await.unwrap());
+
+      let snapshot: Snapshot = open_snapshot(&filename, password).await;
+
+      snapshot
+        .store("", &[])
+        .set(location("persists"), "STRONGHOLD".to_string(), None)
+        .await
+        .unwrap();
+
+      snapshot.unload(true).await.unwrap();
+
+      assert!(Snapshot::verify_snapshot(&filename, password).await.unwrap());
+
+      let invalid: EncryptionKey = derive_encryption_key("my-password:invalid");
+
+      assert!(matches!(
+        Snapshot::verify_snapshot(&filename, invalid).await.unwrap_err(),
+        Error::StrongholdSnapshotUnauthenticated
+      ));
+
+      let data: Vec<u8> = fs::read(&filename).unwrap();
+
+      // Flip a bit in the encrypted body
+      let mut tampered: Vec<u8> = data.clone();
+      let index: usize = tampered.len() - 1;
+      tampered[index] ^= 0x01;
+      fs::write(&filename, &tampered).unwrap();
+
+      assert!(matches!(
+        Snapshot::verify_snapshot(&filename, password).await.unwrap_err(),
+        Error::StrongholdSnapshotUnauthenticated
+      ));
+
+      // Cut the snapshot in half, past the header
+      fs::write(&filename, &data[..data.len() / 2]).unwrap();
+
+      assert!(matches!(
+        Snapshot::verify_snapshot(&filename, password).await.unwrap_err(),
+        Error::StrongholdSnapshotUnauthenticated
+      ));
+
+      // Restore the snapshot
+      fs::write(&filename, &data).unwrap();
+
+      assert!(Snapshot::verify_snapshot(&filename, password).await.unwrap());
+
+      fs::remove_file(&filename).unwrap();
+    })
+  }
+
+  #[test]
+  fn test_vault_record_limit() {
+    block_on(async {
+      let password: EncryptionKey = derive_encryption_key("my-password:test_vault_record_limit");
+      let snapshot: Snapshot = open_snapshot(&generate_filename(), password).await;
+      let vault: Vault<'_> = snapshot.vault_with_limit(b"vault", &[], RecordLimit::new(2));
+
+      let counters = |records: Vec<Record>| -> Vec<usize> {
+        let mut counters: Vec<usize> = records.into_iter().map(|(counter, _)| counter).collect();
+        counters.sort_unstable();
+        counters
+      };
+
+      for counter in 0..3_usize {
+        vault
+          .insert(
+            Location::counter::<_, usize>("limited", Some(counter)),
+            [counter as u8; 32].to_vec(),
+            timestamp_hint(),
+            &[],
+          )
+          .await
+          .unwrap();
+
+        // Ensure distinct timestamps
+        thread::sleep(Duration::from_millis(2));
+      }
+
+      // The record with the oldest timestamp was evicted
+      assert_eq!(counters(vault.records("limited").await.unwrap()), [1, 2]);
+
+      // Overwriting a record evicts nothing
+      vault
+        .insert(
+          Location::counter::<_, usize>("limited", Some(1)),
+          [1; 32].to_vec(),
+          timestamp_hint(),
+          &[],
+        )
+        .await
+        .unwrap();
+
+      assert_eq!(counters(vault.records("limited").await.unwrap()), [1, 2]);
+
+      // Records without a timestamp hint are never evicted
+      for counter in 0..2_usize {
+        vault
+          .insert(
+            Location::counter::<_, usize>("untagged", Some(counter)),
+            [counter as u8; 32].to_vec(),
+            default_hint(),
+            &[],
+          )
+          .await
+          .unwrap();
+      }
+
+      assert!(matches!(
+        vault
+          .insert(
+            Location::counter::<_, usize>("untagged", Some(2)),
+            [2; 32].to_vec(),
+            default_hint(),
+            &[]
+          )
+          .await
+          .unwrap_err(),
+        Error::StrongholdVaultFull
+      ));
+
+      // Records of other vault paths are not counted
+      vault
+        .insert(location("other"), [7; 32].to_vec(), default_hint(), &[])
+        .await
+        .unwrap();
+
+      assert_eq!(vault.records("limited").await.unwrap().len(), 2);
+
+      let vault: Vault<'_> = snapshot.vault_with_limit(b"vault", &[], RecordLimit::new(2).evict(|_, _| None));
+
+      assert!(matches!(
+        vault
+          .insert(location("limited"), [7; 32].to_vec(), default_hint(), &[])
+          .await
+          .unwrap_err(),
+        Error::StrongholdVaultFull
+      ));
+
+      snapshot.unload(false).await.unwrap();
+    })
+  }
 }
@@ -4,9 +4,11 @@
 use crypto::keys::slip10::Chain;
 use crypto::keys::slip10::ChainCode;
 use iota_stronghold::Location;
+use iota_stronghold::ProcResult;
 use iota_stronghold::Procedure;
 use iota_stronghold::RecordHint;
 use iota_stronghold::SLIP10DeriveInput;
+use iota_stronghold::Stronghold;
 use iota_stronghold::StrongholdFlags;
 use iota_stronghold::VaultFlags;
 use std::path::Path;
@@ -14,16 +16,26 @@ use std::path::Path;
 use crate::error::Error;
 use crate::error::PleaseDontMakeYourOwnResult;
 use crate::error::Result;
+use crate::stronghold::default_hint;
+#[cfg(feature = "metrics")]
+use crate::stronghold::metrics::ProcedureTimer;
 use crate::stronghold::Context;
 use crate::stronghold::ProcedureResult;
+use crate::stronghold::RecordLimit;
 
 pub type Record = (usize, RecordHint);
 
+// Derived keys of `derive_and_sign` are stored here while the snapshot is
+// locked and removed before the lock is released.
+const EPHEMERAL_VAULT: &str = "__ephemeral";
+const EPHEMERAL_RECORD: &str = "";
+
 #[derive(Debug)]
 pub struct Vault<'snapshot> {
   path: &'snapshot Path,
   name: Vec<u8>,
   flags: Vec<StrongholdFlags>,
+  limit: Option<RecordLimit>,
 }
 
 impl<'snapshot> Vault<'snapshot> {
@@ -36,6 +48,18 @@ impl<'snapshot> Vault<'snapshot> {
       path: path.as_ref(),
       name: name.as_ref().to_vec(),
       flags: flags.to_vec(),
+      limit: None,
+    }
+  }
+
+  pub(crate) fn with_limit<P, T>(path: &'snapshot P, name: &T, flags: &[StrongholdFlags], limit: RecordLimit) -> Self
+  where
+    P: AsRef<Path> + ?Sized,
+    T: AsRef<[u8]> + ?Sized,
+  {
+    Self {
+      limit: Some(limit),
+      ..Self::new(path, name, flags)
     }
   }
 }
@@ -56,21 +80,97 @@ impl Vault<'_> {
     &self.flags
   }
 
+  /// Returns the record limit of the vault, if any.
+  pub fn limit(&self) -> Option<&RecordLimit> {
+    self.limit.as_ref()
+  }
+
+  /// Renames the vault, migrating all records (including hints) to the
+  /// new name.
+  ///
+  /// The state of the vault is persisted to the snapshot before migrating;
+  /// records stored under the previous name are not removed.
+  pub async fn rename<T>(&mut self, name: &T) -> Result<()>
+  where
+    T: AsRef<[u8]> + ?Sized,
+  {
+    Context::rename(self.path, &self.name, name.as_ref(), &self.flags).await?;
+
+    self.name = name.as_ref().to_vec();
+
+    Ok(())
+  }
+
   /// Inserts a record.
+  ///
+  /// If the vault has a [`RecordLimit`], records are evicted from the vault
+  /// path of `location` first so the limit isn't exceeded.
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+      skip(self, location, payload, hint, flags),
+      fields(snapshot = %self.path.display(), vault = %String::from_utf8_lossy(&self.name)),
+      err
+    )
+  )]
   pub async fn insert<T>(&self, location: Location, payload: T, hint: RecordHint, flags: &[VaultFlags]) -> Result<()>
   where
     T: Into<Vec<u8>>,
   {
-    Context::scope(self.path, &self.name, &self.flags)
-      .await?
+    let mut scope: _ = Context::scope_mut(self.path, &self.name, &self.flags).await?;
+
+    self.reserve(&mut scope, &location).await?;
+
+    scope
       .write_to_vault(location, payload.into(), hint, flags.to_vec())
       .await
       .to_result()
   }
 
+  /// Inserts a record and executes a runtime [`procedure`][`Procedure`]
+  /// without releasing the snapshot lock in between.
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+      skip(self, location, payload, hint, flags, procedure),
+      fields(snapshot = %self.path.display(), vault = %String::from_utf8_lossy(&self.name)),
+      err
+    )
+  )]
+  pub async fn write_then<T>(
+    &self,
+    location: Location,
+    payload: T,
+    hint: RecordHint,
+    flags: &[VaultFlags],
+    procedure: Procedure,
+  ) -> Result<ProcedureResult>
+  where
+    T: Into<Vec<u8>>,
+  {
+    let mut scope: _ = Context::scope_mut(self.path, &self.name, &self.flags).await?;
+
+    self.reserve(&mut scope, &location).await?;
+
+    scope
+      .write_to_vault(location, payload.into(), hint, flags.to_vec())
+      .await
+      .to_result()?;
+
+    runtime_exec(&mut scope, procedure).await
+  }
+
   /// Deletes a record.
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+      skip(self, location, gc),
+      fields(snapshot = %self.path.display(), vault = %String::from_utf8_lossy(&self.name)),
+      err
+    )
+  )]
   pub async fn delete(&self, location: Location, gc: bool) -> Result<()> {
-    Context::scope(self.path, &self.name, &self.flags)
+    Context::scope_mut(self.path, &self.name, &self.flags)
       .await?
       .delete_data(location, gc)
       .await
@@ -78,12 +178,47 @@ impl Vault<'_> {
   }
 
   /// Executes a runtime [`procedure`][`Procedure`].
+  ///
+  /// With the `metrics` feature enabled, the duration of the procedure is
+  /// recorded in `ProcedureMetrics`.
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+      skip(self, procedure),
+      fields(snapshot = %self.path.display(), vault = %String::from_utf8_lossy(&self.name)),
+      err
+    )
+  )]
   pub async fn execute(&self, procedure: Procedure) -> Result<ProcedureResult> {
-    Context::scope(self.path, &self.name, &self.flags)
-      .await?
-      .runtime_exec(procedure)
-      .await
-      .to_result()
+    let mut scope: _ = if is_write(&procedure) {
+      Context::scope_mut(self.path, &self.name, &self.flags).await?
+    } else {
+      Context::scope(self.path, &self.name, &self.flags).await?
+    };
+
+    runtime_exec(&mut scope, procedure).await
+  }
+
+  /// Executes a runtime [`procedure`][`Procedure`] and extracts the expected
+  /// output from the result.
+  ///
+  /// Returns [`Error::StrongholdProcedureFailure`] if the `extractor` rejects
+  /// the result.
+  pub async fn execute_expect<T, F>(&self, procedure: Procedure, extractor: F) -> Result<T>
+  where
+    F: FnOnce(ProcedureResult) -> Option<T>,
+  {
+    self.execute(procedure).await.and_then(|result| expect(result, extractor))
+  }
+
+  // Executes a runtime procedure writing a record to `output`, evicting
+  // records first if needed.
+  async fn execute_into(&self, output: &Location, procedure: Procedure) -> Result<ProcedureResult> {
+    let mut scope: _ = Context::scope_mut(self.path, &self.name, &self.flags).await?;
+
+    self.reserve(&mut scope, output).await?;
+
+    runtime_exec(&mut scope, procedure).await
   }
 
   /// Returns a list of available records and hints.
@@ -92,24 +227,58 @@ impl Vault<'_> {
     T: AsRef<[u8]> + ?Sized,
   {
     let scope: _ = Context::scope(self.path, &self.name, &self.flags).await?;
-    let (data, status): (Vec<Record>, _) = scope.list_hints_and_ids(vault.as_ref()).await;
 
-    status.to_result()?;
+    list_records(&scope, vault.as_ref()).await
+  }
+
+  // Evicts records until a record can be written to `location` without
+  // exceeding the record limit. Records are deleted with garbage collection
+  // so the space they occupied is reclaimed.
+  //
+  // Eviction runs in the scope of the subsequent write so no other operation
+  // can fill the vault path in between.
+  async fn reserve(&self, stronghold: &mut Stronghold, location: &Location) -> Result<()> {
+    let limit: RecordLimit = match self.limit {
+      Some(limit) => limit,
+      None => return Ok(()),
+    };
+
+    // Overwriting a record doesn't increase the number of records
+    if stronghold.record_exists(location.clone()).await {
+      return Ok(());
+    }
+
+    let vault_path: &[u8] = location.vault_path();
+    let mut records: Vec<Record> = list_records(stronghold, vault_path).await?;
+
+    while records.len() >= limit.max_records() {
+      let count: usize = records.len();
+      let evict: Location = limit.select(vault_path, &records).ok_or(Error::StrongholdVaultFull)?;
 
-    Ok(data)
+      stronghold.delete_data(evict, true).await.to_result()?;
+
+      records = list_records(stronghold, vault_path).await?;
+
+      // The hook selected a location without a record
+      if records.len() >= count {
+        return Err(Error::StrongholdVaultFull);
+      }
+    }
+
+    Ok(())
   }
 
   pub async fn slip10_generate(&self, output: Location, hint: RecordHint, bytes: Option<usize>) -> Result<()> {
     let procedure: Procedure = Procedure::SLIP10Generate {
-      output,
+      output: output.clone(),
       hint,
       size_bytes: bytes,
     };
 
-    match self.execute(procedure).await? {
-      ProcedureResult::SLIP10Generate => Ok(()),
-      _ => Err(Error::StrongholdProcedureFailure),
-    }
+    expect(self.execute_into(&output, procedure).await?, |result| match result {
+      ProcedureResult::SLIP10Generate => Some(()),
+      _ => None,
+    })
   }
 
   pub async fn slip10_derive(
@@ -122,14 +291,138 @@ impl Vault<'_> {
     let procedure: Procedure = Procedure::SLIP10Derive {
       chain,
       input,
-      output,
+      output: output.clone(),
       hint,
     };
 
-    match self.execute(procedure).await? {
-      ProcedureResult::SLIP10Derive(chaincode) => Ok(chaincode),
-      _ => Err(Error::StrongholdProcedureFailure),
-    }
+    expect(self.execute_into(&output, procedure).await?, |result| match result {
+      ProcedureResult::SLIP10Derive(chaincode) => Some(chaincode),
+      _ => None,
+    })
+  }
+
+  /// Derives a SLIP10 key and returns the Ed25519 public key of the derived
+  /// private key, using a single runtime session.
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+      skip(self, chain, input, output, hint),
+      fields(snapshot = %self.path.display(), vault = %String::from_utf8_lossy(&self.name)),
+      err
+    )
+  )]
+  pub async fn slip10_derive_ed25519(
+    &self,
+    chain: Chain,
+    input: SLIP10DeriveInput,
+    output: Location,
+    hint: RecordHint,
+  ) -> Result<[u8; 32]> {
+    let mut scope: _ = Context::scope_mut(self.path, &self.name, &self.flags).await?;
+
+    self.reserve(&mut scope, &output).await?;
+
+    let derive: Procedure = Procedure::SLIP10Derive {
+      chain,
+      input,
+      output: output.clone(),
+      hint,
+    };
+
+    let public: Procedure = Procedure::Ed25519PublicKey { private_key: output };
+
+    expect(runtime_exec(&mut scope, derive).await?, |result| match result {
+      ProcedureResult::SLIP10Derive(chaincode) => Some(chaincode),
+      _ => None,
+    })?;
+
+    expect(runtime_exec(&mut scope, public).await?, |result| match result {
+      ProcedureResult::Ed25519PublicKey(public_key) => Some(public_key),
+      _ => None,
+    })
+  }
+
+  /// Derives a SLIP10 key into a temporary location, signs `msg` with it, and
+  /// removes the derived key, using a single runtime session.
+  ///
+  /// The derived key is never persisted to the snapshot.
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+      skip(self, chain, input, msg),
+      fields(snapshot = %self.path.display(), vault = %String::from_utf8_lossy(&self.name)),
+      err
+    )
+  )]
+  pub async fn derive_and_sign(&self, chain: Chain, input: SLIP10DeriveInput, msg: Vec<u8>) -> Result<[u8; 64]> {
+    let location: Location = Location::generic(EPHEMERAL_VAULT, EPHEMERAL_RECORD);
+
+    let derive: Procedure = Procedure::SLIP10Derive {
+      chain,
+      input,
+      output: location.clone(),
+      hint: default_hint(),
+    };
+
+    let sign: Procedure = Procedure::Ed25519Sign {
+      private_key: location.clone(),
+      msg,
+    };
+
+    let mut scope: _ = Context::scope(self.path, &self.name, &self.flags).await?;
+
+    let result: Result<ProcedureResult> = match runtime_exec(&mut scope, derive).await {
+      Ok(_) => runtime_exec(&mut scope, sign).await,
+      Err(error) => Err(error),
+    };
+
+    // Remove the derived key even if signing failed; the signing error takes
+    // precedence over a failed removal
+    let cleanup: Result<()> = scope.delete_data(location, true).await.to_result();
+    let result: ProcedureResult = result?;
+
+    cleanup?;
+
+    expect(result, |result| match result {
+      ProcedureResult::Ed25519Sign(signature) => Some(signature),
+      _ => None,
+    })
+  }
+
+  /// Generates an Ed25519 private key at `output` and returns its public key,
+  /// using a single runtime session.
+  ///
+  /// The private key never leaves the vault.
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+      skip(self, output, hint),
+      fields(snapshot = %self.path.display(), vault = %String::from_utf8_lossy(&self.name)),
+      err
+    )
+  )]
+  pub async fn generate_ed25519(&self, output: Location, hint: RecordHint) -> Result<[u8; 32]> {
+    let mut scope: _ = Context::scope_mut(self.path, &self.name, &self.flags).await?;
+
+    self.reserve(&mut scope, &output).await?;
+
+    let generate: Procedure = Procedure::SLIP10Generate {
+      output: output.clone(),
+      hint,
+      size_bytes: Some(32),
+    };
+
+    let public: Procedure = Procedure::Ed25519PublicKey { private_key: output };
+
+    expect(runtime_exec(&mut scope, generate).await?, |result| match result {
+      ProcedureResult::SLIP10Generate => Some(()),
+      _ => None,
+    })?;
+
+    expect(runtime_exec(&mut scope, public).await?, |result| match result {
+      ProcedureResult::Ed25519PublicKey(public_key) => Some(public_key),
+      _ => None,
+    })
   }
 
   pub async fn bip39_recover<P>(
@@ -145,14 +438,14 @@ impl Vault<'_> {
     let procedure: Procedure = Procedure::BIP39Recover {
       mnemonic,
       passphrase: passphrase.into(),
-      output,
+      output: output.clone(),
       hint,
     };
 
-    match self.execute(procedure).await? {
-      ProcedureResult::BIP39Recover => Ok(()),
-      _ => Err(Error::StrongholdProcedureFailure),
-    }
+    expect(self.execute_into(&output, procedure).await?, |result| match result {
+      ProcedureResult::BIP39Recover => Some(()),
+      _ => None,
+    })
   }
 
   pub async fn bip39_generate<P>(&self, output: Location, passphrase: P, hint: RecordHint) -> Result<()>
@@ -161,40 +454,91 @@ impl Vault<'_> {
   {
     let procedure: Procedure = Procedure::BIP39Generate {
       passphrase: passphrase.into(),
-      output,
+      output: output.clone(),
       hint,
     };
 
-    match self.execute(procedure).await? {
-      ProcedureResult::BIP39Generate => Ok(()),
-      _ => Err(Error::StrongholdProcedureFailure),
-    }
+    expect(self.execute_into(&output, procedure).await?, |result| match result {
+      ProcedureResult::BIP39Generate => Some(()),
+      _ => None,
+    })
   }
 
   pub async fn bip39_mnemonic_sentence(&self, seed: Location) -> Result<String> {
     let procedure: Procedure = Procedure::BIP39MnemonicSentence { seed };
 
-    match self.execute(procedure).await? {
-      ProcedureResult::BIP39MnemonicSentence(mnemonic) => Ok(mnemonic),
-      _ => Err(Error::StrongholdProcedureFailure),
-    }
+    self
+      .execute_expect(procedure, |result| match result {
+        ProcedureResult::BIP39MnemonicSentence(mnemonic) => Some(mnemonic),
+        _ => None,
+      })
+      .await
   }
 
   pub async fn ed25519_public_key(&self, private_key: Location) -> Result<[u8; 32]> {
     let procedure: Procedure = Procedure::Ed25519PublicKey { private_key };
 
-    match self.execute(procedure).await? {
-      ProcedureResult::Ed25519PublicKey(public_key) => Ok(public_key),
-      _ => Err(Error::StrongholdProcedureFailure),
-    }
+    self
+      .execute_expect(procedure, |result| match result {
+        ProcedureResult::Ed25519PublicKey(public_key) => Some(public_key),
+        _ => None,
+      })
+      .await
   }
 
   pub async fn ed25519_sign(&self, msg: Vec<u8>, private_key: Location) -> Result<[u8; 64]> {
     let procedure: Procedure = Procedure::Ed25519Sign { private_key, msg };
 
-    match self.execute(procedure).await? {
-      ProcedureResult::Ed25519Sign(signature) => Ok(signature),
-      _ => Err(Error::StrongholdProcedureFailure),
-    }
+    self
+      .execute_expect(procedure, |result| match result {
+        ProcedureResult::Ed25519Sign(signature) => Some(signature),
+        _ => None,
+      })
+      .await
   }
 }
+
+fn expect<T, F>(result: ProcedureResult, extractor: F) -> Result<T>
+where
+  F: FnOnce(ProcedureResult) -> Option<T>,
+{
+  extractor(result).ok_or(Error::StrongholdProcedureFailure)
+}
+
+// Returns `true` if the procedure writes a record to the vault.
+fn is_write(procedure: &Procedure) -> bool {
+  matches!(
+    procedure,
+    Procedure::SLIP10Generate { .. }
+      | Procedure::SLIP10Derive { .. }
+      | Procedure::BIP39Generate { .. }
+      | Procedure::BIP39Recover { .. }
+  )
+}
+
+async fn list_records(stronghold: &Stronghold, vault_path: &[u8]) -> Result<Vec<Record>> {
+  let (data, status): (Vec<Record>, _) = stronghold.list_hints_and_ids(vault_path).await;
+
+  status.to_result()?;
+
+  Ok(data)
+}
+
+// All procedures are executed here so they can be instrumented uniformly.
+async fn runtime_exec(stronghold: &mut Stronghold, procedure: Procedure) -> Result<ProcedureResult> {
+  #[cfg(feature = "metrics")]
+  let timer: ProcedureTimer = ProcedureTimer::start(&procedure);
+
+  let result: ProcResult = stronghold.runtime_exec(procedure).await;
+
+  // Failing to record metrics must not fail the procedure
+  #[cfg(feature = "metrics")]
+  if let Err(error) = timer.stop() {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(?error, "Failed to record procedure metrics");
+    #[cfg(not(feature = "tracing"))]
+    let _: Error = error;
+  }
+
+  result.to_result()
+}
@@ -3,7 +3,9 @@
 
 #![allow(dead_code)]
 
+use std::env;
 use std::fs;
+use std::path::Component;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -23,6 +25,47 @@ where
   path.join(file)
 }
 
+// Returns the canonical form of `path`, which may not exist yet.
+//
+// The longest existing ancestor is canonicalized and the remaining components
+// are appended after resolving `.` and `..` lexically.
+pub(crate) fn canonicalize<P>(path: &P) -> PathBuf
+where
+  P: AsRef<Path> + ?Sized,
+{
+  let path: &Path = path.as_ref();
+
+  if let Ok(path) = fs::canonicalize(path) {
+    return path;
+  }
+
+  let absolute: PathBuf = if path.is_absolute() {
+    path.to_path_buf()
+  } else {
+    env::current_dir().map(|dir| dir.join(path)).unwrap_or_else(|_| path.to_path_buf())
+  };
+
+  let mut output: PathBuf = PathBuf::new();
+
+  for component in absolute.components() {
+    match component {
+      Component::CurDir => {}
+      Component::ParentDir => {
+        output.pop();
+      }
+      component => {
+        output.push(component);
+
+        if let Ok(canonical) = fs::canonicalize(&output) {
+          output = canonical;
+        }
+      }
+    }
+  }
+
+  output
+}
+
 pub(crate) fn ensure_directory<P>(path: &P) -> Result<()>
 where
   P: AsRef<Path> + ?Sized,
@@ -12,6 +12,9 @@ use core::fmt::Formatter;
 use core::fmt::Result as FmtResult;
 use core::str::FromStr;
 
+use crate::diff;
+use crate::diff::Diff;
+use crate::diff::DiffString;
 use crate::error::Error;
 use crate::error::Result;
 
@@ -57,6 +60,29 @@ impl Default for Timestamp {
   }
 }
 
+impl Diff for Timestamp {
+  type Type = DiffString;
+
+  fn diff(&self, other: &Self) -> diff::Result<Self::Type> {
+    self.to_rfc3339().diff(&other.to_rfc3339())
+  }
+
+  fn merge(&self, diff: Self::Type) -> diff::Result<Self> {
+    self
+      .to_rfc3339()
+      .merge(diff)
+      .and_then(|this| Self::parse(&this).map_err(diff::Error::merge))
+  }
+
+  fn from_diff(diff: Self::Type) -> diff::Result<Self> {
+    String::from_diff(diff).and_then(|this| Self::parse(&this).map_err(diff::Error::convert))
+  }
+
+  fn into_diff(self) -> diff::Result<Self::Type> {
+    self.to_rfc3339().into_diff()
+  }
+}
+
 impl Debug for Timestamp {
   fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
     write!(f, "{:?}", self.to_rfc3339())
@@ -0,0 +1,132 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+/// The encoding of a serialized object, as detected by
+/// [`FromJson::from_any`][crate::convert::FromJson::from_any].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Encoding {
+  /// A JSON object or array.
+  JSON,
+  /// Base64url-encoded data of a supported encoding, with or without padding.
+  Base64Url,
+  /// A single CBOR (RFC 8949) map, array, or tagged item.
+  CBOR,
+}
+
+impl Encoding {
+  /// Detects the encoding of `data`; returns `None` if the encoding is unknown.
+  ///
+  /// Leading and trailing whitespace is ignored. CBOR data must be a complete
+  /// CBOR item and base64url data must decode to data of a known encoding, so
+  /// inputs like `true` or `1234` are not mistaken for either.
+  pub fn detect(data: &[u8]) -> Option<Self> {
+    // CBOR is binary - trailing bytes may look like whitespace
+    if is_cbor(data) {
+      return Some(Self::CBOR);
+    }
+
+    let data: &[u8] = trim(data);
+
+    match data.first()? {
+      b'{' | b'[' => Some(Self::JSON),
+      _ if decode_b64url(data).map_or(false, |data| Self::detect(&data).is_some()) => Some(Self::Base64Url),
+      _ => None,
+    }
+  }
+
+  /// Returns the name of the encoding.
+  pub const fn as_str(&self) -> &'static str {
+    match self {
+      Self::JSON => "JSON",
+      Self::Base64Url => "Base64Url",
+      Self::CBOR => "CBOR",
+    }
+  }
+}
+
+impl Display for Encoding {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.write_str(self.as_str())
+  }
+}
+
+/// Removes leading and trailing ASCII whitespace from `data`.
+pub(crate) fn trim(data: &[u8]) -> &[u8] {
+  let start: usize = data
+    .iter()
+    .position(|byte| !byte.is_ascii_whitespace())
+    .unwrap_or(data.len());
+
+  let end: usize = data
+    .iter()
+    .rposition(|byte| !byte.is_ascii_whitespace())
+    .map_or(start, |index| index + 1);
+
+  &data[start..end]
+}
+
+/// Removes trailing base64 padding from `data`.
+pub(crate) fn trim_padding(data: &[u8]) -> &[u8] {
+  let end: usize = data.iter().rposition(|byte| *byte != b'=').map_or(0, |index| index + 1);
+
+  &data[..end]
+}
+
+/// Decodes base64url-encoded `data`, with or without padding; returns `None`
+/// if `data` contains other characters or has an undecodable length.
+pub(crate) fn decode_b64url(data: &[u8]) -> Option<Vec<u8>> {
+  let data: &[u8] = trim_padding(data);
+
+  if data.iter().all(|byte| is_base64url(*byte)) {
+    base64::decode_config(data, base64::URL_SAFE_NO_PAD).ok()
+  } else {
+    None
+  }
+}
+
+fn is_base64url(byte: u8) -> bool {
+  byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_'
+}
+
+fn is_cbor(data: &[u8]) -> bool {
+  // CBOR major types 4 (array), 5 (map), and 6 (tag)
+  matches!(data.first(), Some(0x80..=0xdb)) && serde_cbor::from_slice::<serde_cbor::Value>(data).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_detect() {
+    assert_eq!(Encoding::detect(br#"{"a":1}"#), Some(Encoding::JSON));
+    assert_eq!(Encoding::detect(b"\n  [1, 2]\n"), Some(Encoding::JSON));
+    assert_eq!(Encoding::detect(b"eyJhIjoxfQ"), Some(Encoding::Base64Url));
+    assert_eq!(Encoding::detect(b"eyJhIjoxfQ==\n"), Some(Encoding::Base64Url));
+    assert_eq!(Encoding::detect(&[0xa1, 0x61, 0x61, 0x01]), Some(Encoding::CBOR));
+    assert_eq!(Encoding::detect(&[0x82, 0x01, 0x02]), Some(Encoding::CBOR));
+    assert_eq!(Encoding::detect(&[0xd9, 0xd9, 0xf7, 0xa0]), Some(Encoding::CBOR));
+    assert_eq!(Encoding::detect(&[0x82, 0x01, 0x0a]), Some(Encoding::CBOR));
+
+    assert_eq!(Encoding::detect(b""), None);
+    assert_eq!(Encoding::detect(b"  \n"), None);
+    assert_eq!(Encoding::detect(b"eyJh+IjoxfQ/"), None);
+    assert_eq!(Encoding::detect(br#""string""#), None);
+    assert_eq!(Encoding::detect(b"true"), None);
+    assert_eq!(Encoding::detect(b"1234"), None);
+    assert_eq!(Encoding::detect(b"eyJhIjoxfQa"), None);
+    assert_eq!(Encoding::detect(&[0xa1, 0x61]), None);
+  }
+
+  #[test]
+  fn test_trim() {
+    assert_eq!(trim(b"  abc \n"), b"abc");
+    assert_eq!(trim(b" \t "), b"");
+    assert_eq!(trim_padding(b"abc=="), b"abc");
+    assert_eq!(trim_padding(b"=="), b"");
+  }
+}
@@ -6,9 +6,29 @@ use crypto::hashes::Digest;
 use crypto::hashes::Output;
 use erased_serde::Serialize;
 use serde::Deserialize;
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::io::Read;
+use std::mem::take;
 
+use crate::convert::encoding::decode_b64url;
+use crate::convert::encoding::trim;
+use crate::convert::Encoding;
 use crate::error::Error;
 use crate::error::Result;
+use crate::utils::encode_b32;
+
+// The CIDv1 prefix of JSON content hashed with SHA-256: the CID version, the
+// `json` multicodec (0x0200), the `sha2-256` multihash code, and the digest
+// length - all varint-encoded.
+const CID_V1_JSON_SHA256: &[u8] = &[0x01, 0x80, 0x04, 0x12, 0x20];
+
+// The number of bytes of the big-endian length prefix of a framed payload.
+const FRAME_PREFIX_LENGTH: usize = 4;
+
+// The number of bytes included on either side of the position of a decoding
+// error in the snippet of the error.
+const SNIPPET_RADIUS: usize = 24;
 
 /// A convenience-trait for types that can be serialized as JSON.
 pub trait ToJson: Serialize + Sized {
@@ -32,6 +52,19 @@ pub trait ToJson: Serialize + Sized {
     serde_json::to_string_pretty(self as &dyn Serialize).map_err(Error::EncodeJSON)
   }
 
+  /// Serialize `self` as a string of JSON, omitting object members that are
+  /// `null` or empty arrays/objects.
+  ///
+  /// This is intended for transport and display only; JCS serialization is
+  /// not affected.
+  fn to_json_compact(&self) -> Result<String> {
+    let mut json: serde_json::Value = self.to_json_value()?;
+
+    compact(&mut json);
+
+    serde_json::to_string(&json).map_err(Error::EncodeJSON)
+  }
+
   /// Serialize `self` as a JSON byte vector, normalized using JSON
   /// Canonicalization Scheme (JCS).
   fn to_jcs(&self) -> Result<Vec<u8>> {
@@ -43,6 +76,36 @@ pub trait ToJson: Serialize + Sized {
   fn to_jcs_sha256(&self) -> Result<Output<Sha256>> {
     self.to_jcs().map(|json| Sha256::digest(&json))
   }
+
+  /// Returns the given `data` serialized using JSON Canonicalization Scheme and
+  /// encoded as unpadded base64url, as used by JOSE.
+  fn to_jcs_base64url(&self) -> Result<String> {
+    self
+      .to_jcs()
+      .map(|json| base64::encode_config(&json, base64::URL_SAFE_NO_PAD))
+  }
+
+  /// Returns a content identifier (CIDv1) of the given `data` serialized using
+  /// JSON Canonicalization Scheme and hashed using SHA-256.
+  ///
+  /// The CID is multibase-encoded as base32, compatible with IPFS.
+  fn to_cid(&self) -> Result<String> {
+    self
+      .to_jcs_sha256()
+      .map(|hash| format!("b{}", encode_b32(&[CID_V1_JSON_SHA256, &hash[..]].concat())))
+  }
+
+  /// Serialize `self` as a JSON byte vector prefixed with its length as a
+  /// 4-byte big-endian integer.
+  ///
+  /// Framed payloads can be appended to a single file or stream and read back
+  /// sequentially with [`FromJson::from_framed_reader`].
+  fn to_framed(&self) -> Result<Vec<u8>> {
+    let json: Vec<u8> = self.to_json_vec()?;
+    let length: u32 = u32::try_from(json.len()).map_err(|_| Error::InvalidFrameLength(json.len()))?;
+
+    Ok([&length.to_be_bytes()[..], &json].concat())
+  }
 }
 
 impl<T> ToJson for T where T: serde::Serialize {}
@@ -53,19 +116,80 @@ impl<T> ToJson for T where T: serde::Serialize {}
 /// A convenience-trait for types that can be deserialized from JSON.
 pub trait FromJson: for<'de> Deserialize<'de> + Sized {
   /// Deserialize `Self` from a string of JSON text.
+  ///
+  /// Errors include the line and column of the failure and a snippet of the
+  /// surrounding text.
   fn from_json(json: &(impl AsRef<str> + ?Sized)) -> Result<Self> {
-    serde_json::from_str(json.as_ref()).map_err(Error::DecodeJSON)
+    serde_json::from_str(json.as_ref()).map_err(|error| decode_error(json.as_ref().as_bytes(), error))
   }
 
   /// Deserialize `Self` from bytes of JSON text.
+  ///
+  /// Errors include the line and column of the failure and a snippet of the
+  /// surrounding text.
   fn from_json_slice(json: &(impl AsRef<[u8]> + ?Sized)) -> Result<Self> {
-    serde_json::from_slice(json.as_ref()).map_err(Error::DecodeJSON)
+    serde_json::from_slice(json.as_ref()).map_err(|error| decode_error(json.as_ref(), error))
   }
 
   /// Deserialize `Self` from a [`serde_json::Value`].
   fn from_json_value(json: serde_json::Value) -> Result<Self> {
     serde_json::from_value(json).map_err(Error::DecodeJSON)
   }
+
+  /// Deserialize `Self` from JSON text encoded as unpadded base64url.
+  fn from_jcs_base64url(data: &(impl AsRef<[u8]> + ?Sized)) -> Result<Self> {
+    base64::decode_config(data.as_ref(), base64::URL_SAFE_NO_PAD)
+      .map_err(Error::DecodeBase64)
+      .and_then(|json| Self::from_json_slice(&json))
+  }
+
+  /// Deserialize `Self` from `data` of any supported encoding.
+  ///
+  /// The [`Encoding`] is detected from the contents of `data`: JSON and CBOR
+  /// are parsed directly and base64url-encoded data is decoded and detected
+  /// again.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the encoding is unknown or if decoding fails.
+  fn from_any(data: &(impl AsRef<[u8]> + ?Sized)) -> Result<Self> {
+    let data: &[u8] = data.as_ref();
+
+    match Encoding::detect(data) {
+      Some(Encoding::JSON) => Self::from_json_slice(data),
+      Some(Encoding::CBOR) => serde_cbor::from_slice(data).map_err(Error::DecodeCBOR),
+      Some(Encoding::Base64Url) => decode_b64url(trim(data))
+        .ok_or(Error::UnknownEncoding)
+        .and_then(|data| Self::from_any(&data)),
+      None => Err(Error::UnknownEncoding),
+    }
+  }
+
+  /// Deserialize `Self` from the next payload framed with
+  /// [`ToJson::to_framed`] read from `reader`.
+  ///
+  /// Exactly the bytes of the frame are consumed, leaving `reader` at the
+  /// start of the next frame.
+  fn from_framed_reader<R: Read>(mut reader: R) -> Result<Self> {
+    let mut prefix: [u8; FRAME_PREFIX_LENGTH] = [0; FRAME_PREFIX_LENGTH];
+
+    reader.read_exact(&mut prefix).map_err(Error::DecodeFrame)?;
+
+    let length: usize = u32::from_be_bytes(prefix) as usize;
+    let mut json: Vec<u8> = Vec::new();
+
+    // The length is not trusted to pre-allocate the buffer
+    reader
+      .take(length as u64)
+      .read_to_end(&mut json)
+      .map_err(Error::DecodeFrame)?;
+
+    if json.len() != length {
+      return Err(Error::InvalidFrameLength(length));
+    }
+
+    Self::from_json_slice(&json)
+  }
 }
 
 impl<T> FromJson for T where T: for<'de> Deserialize<'de> + Sized {}
@@ -90,6 +214,22 @@ pub trait AsJson: FromJson + ToJson {
     <Self as FromJson>::from_json_value(json)
   }
 
+  /// Deserialize `Self` from JSON text encoded as unpadded base64url.
+  fn from_jcs_base64url(data: &(impl AsRef<[u8]> + ?Sized)) -> Result<Self> {
+    <Self as FromJson>::from_jcs_base64url(data)
+  }
+
+  /// Deserialize `Self` from `data` of any supported encoding.
+  fn from_any(data: &(impl AsRef<[u8]> + ?Sized)) -> Result<Self> {
+    <Self as FromJson>::from_any(data)
+  }
+
+  /// Deserialize `Self` from the next payload framed with
+  /// [`ToJson::to_framed`] read from `reader`.
+  fn from_framed_reader<R: Read>(reader: R) -> Result<Self> {
+    <Self as FromJson>::from_framed_reader(reader)
+  }
+
   /// Serialize `self` as a string of JSON.
   fn to_json(&self) -> Result<String> {
     <Self as ToJson>::to_json(self)
@@ -110,6 +250,12 @@ pub trait AsJson: FromJson + ToJson {
     <Self as ToJson>::to_json_pretty(self)
   }
 
+  /// Serialize `self` as a string of JSON, omitting object members that are
+  /// `null` or empty arrays/objects.
+  fn to_json_compact(&self) -> Result<String> {
+    <Self as ToJson>::to_json_compact(self)
+  }
+
   /// Serialize `self` as a JSON byte vector, normalized using JSON
   /// Canonicalization Scheme (JCS).
   fn to_jcs(&self) -> Result<Vec<u8>> {
@@ -121,6 +267,279 @@ pub trait AsJson: FromJson + ToJson {
   fn to_jcs_sha256(&self) -> Result<Output<Sha256>> {
     <Self as ToJson>::to_jcs_sha256(self)
   }
+
+  /// Returns the given `data` serialized using JSON Canonicalization Scheme and
+  /// encoded as unpadded base64url, as used by JOSE.
+  fn to_jcs_base64url(&self) -> Result<String> {
+    <Self as ToJson>::to_jcs_base64url(self)
+  }
+
+  /// Returns a content identifier (CIDv1) of the given `data` serialized using
+  /// JSON Canonicalization Scheme and hashed using SHA-256.
+  fn to_cid(&self) -> Result<String> {
+    <Self as ToJson>::to_cid(self)
+  }
+
+  /// Serialize `self` as a JSON byte vector prefixed with its length as a
+  /// 4-byte big-endian integer.
+  fn to_framed(&self) -> Result<Vec<u8>> {
+    <Self as ToJson>::to_framed(self)
+  }
 }
 
 impl<T> AsJson for T where T: FromJson + ToJson {}
+
+// =============================================================================
+// =============================================================================
+
+/// Returns `true` if `a` and `b` are semantically equal JSON values.
+///
+/// Both values are compared by their JSON Canonicalization Scheme (JCS)
+/// serialization, which makes the comparison independent of object member
+/// order and whitespace.
+pub fn json_eq<A, B>(a: &A, b: &B) -> Result<bool>
+where
+  A: AsJson,
+  B: AsJson,
+{
+  Ok(AsJson::to_jcs(a)? == AsJson::to_jcs(b)?)
+}
+
+fn decode_error(json: &[u8], error: serde_json::Error) -> Error {
+  // Errors without a position (e.g. I/O errors) have a line of zero
+  if error.line() == 0 {
+    return Error::DecodeJSON(error);
+  }
+
+  let text: Cow<'_, str> = String::from_utf8_lossy(json);
+  let (line, column): (String, usize) = redact(text.lines().nth(error.line() - 1).unwrap_or_default(), error.column());
+
+  let mut start: usize = column.saturating_sub(SNIPPET_RADIUS);
+  let mut end: usize = (column + SNIPPET_RADIUS).min(line.len());
+
+  while !line.is_char_boundary(start) {
+    start -= 1;
+  }
+
+  while !line.is_char_boundary(end) {
+    end += 1;
+  }
+
+  Error::DecodeJSONText {
+    error,
+    snippet: line[start..end].trim().to_string(),
+  }
+}
+
+// Replaces the contents of the string literals of `line` with `***` so the
+// snippet never contains secret values (e.g. the `d` member of a JWK);
+// returns the redacted line and the position of `column` within it.
+//
+// JSON strings can't span lines, so the line is tokenized on its own.
+fn redact(line: &str, column: usize) -> (String, usize) {
+  let mut output: String = String::with_capacity(line.len());
+  let mut position: Option<usize> = None;
+  let mut string: bool = false;
+  let mut escaped: bool = false;
+
+  for (index, char) in line.char_indices() {
+    if position.is_none() && index >= column {
+      position = Some(output.len());
+    }
+
+    if !string {
+      if char == '"' {
+        output.push_str("\"***");
+        string = true;
+      } else {
+        output.push(char);
+      }
+    } else if escaped {
+      escaped = false;
+    } else if char == '\\' {
+      escaped = true;
+    } else if char == '"' {
+      output.push('"');
+      string = false;
+    }
+  }
+
+  let position: usize = position.unwrap_or_else(|| output.len());
+
+  (output, position)
+}
+
+fn compact(json: &mut serde_json::Value) {
+  match json {
+    serde_json::Value::Array(array) => array.iter_mut().for_each(compact),
+    serde_json::Value::Object(object) => {
+      object.values_mut().for_each(compact);
+
+      *object = take(object)
+        .into_iter()
+        .filter(|(_, value)| match value {
+          serde_json::Value::Null => false,
+          serde_json::Value::Array(array) => !array.is_empty(),
+          serde_json::Value::Object(object) => !object.is_empty(),
+          _ => true,
+        })
+        .collect();
+    }
+    _ => {}
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use crate::common::Object;
+
+  fn object(json: &str) -> Object {
+    FromJson::from_json(json).unwrap()
+  }
+
+  #[test]
+  fn test_json_eq() {
+    let a: Object = object(r#"{"a": 1, "b": [1, 2], "c": {"d": true, "e": null}}"#);
+    let b: Object = object(r#"{"c":{"e":null,"d":true},"b":[1,2],"a":1}"#);
+    let c: Object = object(r#"{"a": 1, "b": [2, 1], "c": {"d": true, "e": null}}"#);
+
+    assert!(json_eq(&a, &b).unwrap());
+    assert!(json_eq(&b, &a).unwrap());
+    assert!(!json_eq(&a, &c).unwrap());
+  }
+
+  #[test]
+  fn test_to_json_compact() {
+    let data: Object = object(r#"{"a": null, "b": [], "c": {"d": null}, "e": [null, {"f": {}}], "g": 1}"#);
+
+    assert_eq!(ToJson::to_json_compact(&data).unwrap(), r#"{"e":[null,{}],"g":1}"#);
+  }
+
+  #[test]
+  fn test_to_jcs_base64url() {
+    let data: Object = object(r#"{"b": "~~~", "a": 1}"#);
+
+    // `{"a":1,"b":"~~~"}`
+    assert_eq!(ToJson::to_jcs_base64url(&data).unwrap(), "eyJhIjoxLCJiIjoifn5-In0");
+  }
+
+  #[test]
+  fn test_to_cid() {
+    let a: Object = object(r#"{"b": "~~~", "a": 1}"#);
+    let b: Object = object(r#"{"a":1,"b":"~~~"}"#);
+
+    assert_eq!(
+      ToJson::to_cid(&a).unwrap(),
+      "bagaaierarsqvmcimxhtvcz4d44uwgfgqoayactdwkchspvxfnq3a5ysmpcpa"
+    );
+    assert_eq!(ToJson::to_cid(&a).unwrap(), ToJson::to_cid(&b).unwrap());
+  }
+
+  #[test]
+  fn test_from_json_error_context() {
+    let json: &str = "{\n  \"a\": 1,\n  \"b\": [1, 2,, 3]\n}";
+    let error: Error = <Object as FromJson>::from_json(json).unwrap_err();
+
+    match error {
+      Error::DecodeJSONText { ref error, ref snippet } => {
+        assert_eq!(error.line(), 3);
+        assert_eq!(snippet, r#""***": [1, 2,, 3]"#);
+      }
+      _ => panic!("unexpected error"),
+    }
+
+    assert_eq!(error.kind(), "DecodeJSON");
+    assert!(error.to_string().contains("line 3 column"));
+  }
+
+  #[test]
+  fn test_from_json_error_redacted() {
+    let json: &str = r#"{"kty": "OKP", "d": "c2VjcmV0\"a", "x": 1,, "y": 2}"#;
+    let error: Error = <Object as FromJson>::from_json(json).unwrap_err();
+
+    assert!(!error.to_string().contains("c2VjcmV0"));
+
+    match error {
+      Error::DecodeJSONText { ref snippet, .. } => {
+        assert!(snippet.contains(r#""***": 1,,"#));
+        assert!(!snippet.contains("c2VjcmV0"));
+      }
+      _ => panic!("unexpected error"),
+    }
+  }
+
+  #[test]
+  fn test_from_jcs_base64url() {
+    let data: Object = FromJson::from_jcs_base64url("eyJhIjoxLCJiIjoifn5-In0").unwrap();
+
+    assert_eq!(data, object(r#"{"a": 1, "b": "~~~"}"#));
+    assert!(<Object as FromJson>::from_jcs_base64url("eyJhIjoxLCJiIjoifn5-In0=").is_err());
+  }
+
+  #[test]
+  fn test_from_any() {
+    let expected: Object = object(r#"{"a": 1, "b": "~~~"}"#);
+    let encoded: String = base64::encode_config("eyJhIjoxLCJiIjoifn5-In0", base64::URL_SAFE_NO_PAD);
+
+    assert_eq!(
+      <Object as FromJson>::from_any(r#" {"a": 1, "b": "~~~"}"#).unwrap(),
+      expected
+    );
+    assert_eq!(
+      <Object as FromJson>::from_any("eyJhIjoxLCJiIjoifn5-In0").unwrap(),
+      expected
+    );
+    assert_eq!(
+      <Object as FromJson>::from_any("eyJhIjoxLCJiIjoifn5-In0=\n").unwrap(),
+      expected
+    );
+    assert_eq!(<Object as FromJson>::from_any(&encoded).unwrap(), expected);
+
+    assert_eq!(
+      <Object as FromJson>::from_any(&[0xa1, 0x61, 0x61, 0x01]).unwrap(),
+      object(r#"{"a": 1}"#)
+    );
+    assert_eq!(<Object as FromJson>::from_any("oWFhAQ").unwrap(), object(r#"{"a": 1}"#));
+    assert_eq!(
+      <Object as FromJson>::from_any(&[0x82, 0x01, 0x02]).unwrap_err().kind(),
+      "DecodeCBOR"
+    );
+
+    for data in &["~~~", "true", "1234"] {
+      assert!(matches!(
+        <Object as FromJson>::from_any(data),
+        Err(Error::UnknownEncoding)
+      ));
+    }
+    assert_eq!(<Object as FromJson>::from_any("eyJh").unwrap_err().kind(), "DecodeJSON");
+  }
+
+  #[test]
+  fn test_framed() {
+    let a: Object = object(r#"{"a": 1}"#);
+    let b: Object = object(r#"{"b": [true, null]}"#);
+
+    let framed: Vec<u8> = ToJson::to_framed(&a).unwrap();
+
+    assert_eq!(&framed[..4], &[0, 0, 0, 7]);
+    assert_eq!(&framed[4..], br#"{"a":1}"#);
+
+    let log: Vec<u8> = [framed, ToJson::to_framed(&b).unwrap()].concat();
+    let mut reader: &[u8] = &log;
+
+    assert_eq!(<Object as FromJson>::from_framed_reader(&mut reader).unwrap(), a);
+    assert_eq!(<Object as FromJson>::from_framed_reader(&mut reader).unwrap(), b);
+    assert!(reader.is_empty());
+
+    assert!(matches!(
+      <Object as FromJson>::from_framed_reader(&mut reader),
+      Err(Error::DecodeFrame(_))
+    ));
+    assert!(matches!(
+      <Object as FromJson>::from_framed_reader(&log[..10]),
+      Err(Error::InvalidFrameLength(7))
+    ));
+  }
+}
@@ -0,0 +1,472 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde_json::Map;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use crate::common::Value;
+use crate::convert::ld::keyword::is_keyword;
+use crate::convert::ld::keyword::is_keyword_like;
+use crate::error::Error;
+use crate::error::Result;
+
+/// The contexts available to documents, embedded at build time.
+///
+/// Contexts are never fetched: documents referencing any other context are
+/// rejected.
+const CONTEXTS: &[(&str, &str)] = &[
+  ("https://www.w3.org/ns/did/v1", include_str!("contexts/did-v1.jsonld")),
+  (
+    "https://www.w3.org/2018/credentials/v1",
+    include_str!("contexts/credentials-v1.jsonld"),
+  ),
+  (
+    "https://w3id.org/security/suites/ed25519-2020/v1",
+    include_str!("contexts/ed25519-2020-v1.jsonld"),
+  ),
+  (
+    "https://w3id.org/security/suites/x25519-2019/v1",
+    include_str!("contexts/x25519-2019-v1.jsonld"),
+  ),
+];
+
+/// The maximum number of nested remote contexts, which bounds the recursion
+/// of self-referencing contexts.
+const MAX_REMOTE_CONTEXTS: usize = 16;
+
+/// Characters ending the IRI of a term usable as the prefix of compact IRIs.
+const GEN_DELIMS: &[char] = &[':', '/', '?', '#', '[', ']', '@'];
+
+/// A term definition of an active context.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Term {
+  /// The IRI or keyword the term expands to; `None` for terms explicitly
+  /// mapped to `null`.
+  pub(crate) iri: Option<String>,
+  /// The type coercion of values of the term, e.g. `@id` or a datatype IRI.
+  pub(crate) type_: Option<String>,
+  /// The container mappings of the term, e.g. `@set` or `@graph`.
+  pub(crate) container: Vec<String>,
+  /// The scoped context of the term.
+  pub(crate) context: Option<Value>,
+  pub(crate) prefix: bool,
+  pub(crate) protected: bool,
+}
+
+impl Term {
+  pub(crate) fn has_container(&self, container: &str) -> bool {
+    self.container.iter().any(|item| item == container)
+  }
+}
+
+/// A JSON-LD active context.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ActiveContext {
+  terms: BTreeMap<String, Term>,
+  vocab: Option<String>,
+  /// The context to revert to when entering a new node object, set by
+  /// type-scoped contexts which do not propagate.
+  previous: Option<Box<ActiveContext>>,
+}
+
+impl ActiveContext {
+  /// Creates a new empty `ActiveContext`.
+  pub(crate) fn new() -> Self {
+    Self::default()
+  }
+
+  /// Returns the definition of `term`, if any.
+  pub(crate) fn term(&self, term: &str) -> Option<&Term> {
+    self.terms.get(term)
+  }
+
+  /// Returns the context active before a non-propagating context was
+  /// applied, if any.
+  pub(crate) fn previous(&self) -> Option<&ActiveContext> {
+    self.previous.as_deref()
+  }
+
+  /// Returns the result of processing the `local` context against `self`.
+  pub(crate) fn process(&self, local: &Value, override_protected: bool, propagate: bool) -> Result<Self> {
+    self.process_with(local, &mut Vec::new(), override_protected, propagate)
+  }
+
+  fn process_with(
+    &self,
+    local: &Value,
+    remote: &mut Vec<String>,
+    override_protected: bool,
+    propagate: bool,
+  ) -> Result<Self> {
+    let mut result: Self = self.clone();
+
+    let propagate: bool = match local.get("@propagate") {
+      Some(Value::Bool(value)) => *value,
+      Some(_) => return Err(Error::InvalidLinkedData("invalid @propagate value")),
+      None => propagate,
+    };
+
+    if !propagate && result.previous.is_none() {
+      result.previous = Some(Box::new(self.clone()));
+    }
+
+    let contexts: &[Value] = match local {
+      Value::Array(contexts) => contexts,
+      context => core::slice::from_ref(context),
+    };
+
+    for context in contexts {
+      match context {
+        Value::Null => {
+          if !override_protected && result.terms.values().any(|term| term.protected) {
+            return Err(Error::InvalidLinkedData("invalid context nullification"));
+          }
+
+          let previous: Option<Box<Self>> = result.previous.take();
+
+          result = Self::new();
+
+          if !propagate {
+            result.previous = previous;
+          }
+        }
+        Value::String(url) => {
+          if remote.len() >= MAX_REMOTE_CONTEXTS {
+            return Err(Error::InvalidLinkedData("context overflow"));
+          }
+
+          let document: Value = load(url)?;
+          let context: &Value = document
+            .get("@context")
+            .ok_or(Error::InvalidLinkedData("invalid remote context"))?;
+
+          remote.push(url.clone());
+          result = result.process_with(context, remote, false, true)?;
+          remote.pop();
+        }
+        Value::Object(context) => {
+          result.process_definitions(context, override_protected)?;
+        }
+        _ => return Err(Error::InvalidLinkedData("invalid local context")),
+      }
+    }
+
+    Ok(result)
+  }
+
+  fn process_definitions(&mut self, context: &Map<String, Value>, override_protected: bool) -> Result<()> {
+    match context.get("@version") {
+      Some(version) if version.as_f64() != Some(1.1) => {
+        return Err(Error::InvalidLinkedData("invalid @version value"));
+      }
+      Some(_) | None => {}
+    }
+
+    for keyword in &["@base", "@direction", "@import", "@language"] {
+      if context.get(*keyword).map_or(false, |value| !value.is_null()) {
+        return Err(Error::InvalidLinkedData("unsupported context keyword"));
+      }
+    }
+
+    let protected: bool = match context.get("@protected") {
+      Some(Value::Bool(value)) => *value,
+      Some(_) => return Err(Error::InvalidLinkedData("invalid @protected value")),
+      None => false,
+    };
+
+    let mut definitions: Definitions<'_> = Definitions {
+      active: self,
+      local: context,
+      defined: HashMap::new(),
+      protected,
+      override_protected,
+    };
+
+    match context.get("@vocab") {
+      Some(Value::Null) => definitions.active.vocab = None,
+      Some(Value::String(vocab)) => {
+        let vocab: Option<String> = definitions.expand_iri(vocab, true)?;
+
+        match vocab {
+          Some(vocab) if is_absolute_iri(&vocab) => definitions.active.vocab = Some(vocab),
+          Some(_) | None => return Err(Error::InvalidLinkedData("invalid vocab mapping")),
+        }
+      }
+      Some(_) => return Err(Error::InvalidLinkedData("invalid vocab mapping")),
+      None => {}
+    }
+
+    for term in context.keys() {
+      match term.as_str() {
+        "@base" | "@direction" | "@import" | "@language" | "@propagate" | "@protected" | "@version" | "@vocab" => {}
+        _ => definitions.define(term)?,
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Expands `value` to an IRI, a blank node identifier or a keyword.
+  ///
+  /// Returns `None` for terms mapped to `null` and values which look like
+  /// keywords.
+  pub(crate) fn expand_iri(&self, value: &str, vocab: bool) -> Option<String> {
+    expand_iri(self, value, vocab)
+  }
+}
+
+/// The state of the term definitions of a local context being processed.
+struct Definitions<'a> {
+  active: &'a mut ActiveContext,
+  local: &'a Map<String, Value>,
+  defined: HashMap<String, bool>,
+  protected: bool,
+  override_protected: bool,
+}
+
+impl Definitions<'_> {
+  fn expand_iri(&mut self, value: &str, vocab: bool) -> Result<Option<String>> {
+    if is_keyword(value) {
+      return Ok(Some(value.to_string()));
+    }
+
+    if is_keyword_like(value) {
+      return Ok(None);
+    }
+
+    // Terms the value depends on are defined first
+    if self.local.contains_key(value) {
+      self.define(value)?;
+    }
+
+    if let Some((prefix, _)) = split_compact_iri(value) {
+      if self.local.contains_key(prefix) {
+        self.define(prefix)?;
+      }
+    }
+
+    Ok(expand_iri(self.active, value, vocab))
+  }
+
+  fn define(&mut self, term: &str) -> Result<()> {
+    match self.defined.get(term) {
+      Some(true) => return Ok(()),
+      Some(false) => return Err(Error::InvalidLinkedData("cyclic IRI mapping")),
+      None => {}
+    }
+
+    if term.is_empty() {
+      return Err(Error::InvalidLinkedData("invalid term definition"));
+    }
+
+    if is_keyword(term) {
+      return Err(Error::InvalidLinkedData("keyword redefinition"));
+    }
+
+    if is_keyword_like(term) {
+      return Ok(());
+    }
+
+    self.defined.insert(term.to_string(), false);
+
+    let previous: Option<Term> = self.active.terms.remove(term);
+
+    let value: Map<String, Value> = match self.local.get(term) {
+      Some(Value::Null) => {
+        let mut value: Map<String, Value> = Map::new();
+        value.insert("@id".into(), Value::Null);
+        value
+      }
+      Some(Value::String(iri)) => {
+        let mut value: Map<String, Value> = Map::new();
+        value.insert("@id".into(), iri.as_str().into());
+        value
+      }
+      Some(Value::Object(value)) => value.clone(),
+      Some(_) | None => return Err(Error::InvalidLinkedData("invalid term definition")),
+    };
+
+    for keyword in &["@direction", "@index", "@language", "@nest", "@reverse"] {
+      if value.contains_key(*keyword) {
+        return Err(Error::InvalidLinkedData("unsupported term definition"));
+      }
+    }
+
+    let simple: bool = matches!(self.local.get(term), Some(Value::String(_)));
+
+    let mut definition: Term = Term {
+      iri: None,
+      type_: None,
+      container: Vec::new(),
+      context: value.get("@context").cloned(),
+      prefix: false,
+      protected: match value.get("@protected") {
+        Some(Value::Bool(protected)) => *protected,
+        Some(_) => return Err(Error::InvalidLinkedData("invalid @protected value")),
+        None => self.protected,
+      },
+    };
+
+    if let Some(type_) = value.get("@type") {
+      let type_: &str = type_.as_str().ok_or(Error::InvalidLinkedData("invalid type mapping"))?;
+
+      definition.type_ = match type_ {
+        "@id" | "@json" | "@none" | "@vocab" => Some(type_.to_string()),
+        _ => match self.expand_iri(type_, true)? {
+          Some(iri) if is_absolute_iri(&iri) => Some(iri),
+          Some(_) | None => return Err(Error::InvalidLinkedData("invalid type mapping")),
+        },
+      };
+    }
+
+    match value.get("@id") {
+      Some(Value::Null) => {}
+      Some(Value::String(iri)) if iri != term => {
+        definition.iri = match self.expand_iri(iri, true)? {
+          Some(iri) if is_keyword(&iri) || is_absolute_iri(&iri) || iri.starts_with("_:") => Some(iri),
+          Some(_) => return Err(Error::InvalidLinkedData("invalid IRI mapping")),
+          None => None,
+        };
+
+        definition.prefix = simple
+          && !term.contains(':')
+          && !term.contains('/')
+          && definition
+            .iri
+            .as_deref()
+            .and_then(|iri| iri.chars().last())
+            .map_or(false, |last| GEN_DELIMS.contains(&last));
+      }
+      Some(Value::String(_)) | None => {
+        if let Some((prefix, suffix)) = split_compact_iri(term) {
+          if self.local.contains_key(prefix) {
+            self.define(prefix)?;
+          }
+
+          definition.iri = match self.active.terms.get(prefix).and_then(|term| term.iri.as_deref()) {
+            Some(iri) => Some(format!("{}{}", iri, suffix)),
+            None if is_absolute_iri(term) => Some(term.to_string()),
+            None => return Err(Error::InvalidLinkedData("invalid IRI mapping")),
+          };
+        } else {
+          definition.iri = match self.active.vocab.as_deref() {
+            Some(vocab) => Some(format!("{}{}", vocab, term)),
+            None => return Err(Error::InvalidLinkedData("invalid IRI mapping")),
+          };
+        }
+      }
+      Some(_) => return Err(Error::InvalidLinkedData("invalid IRI mapping")),
+    }
+
+    if let Some(container) = value.get("@container") {
+      let container: Vec<&Value> = match container {
+        Value::Array(container) => container.iter().collect(),
+        container => vec![container],
+      };
+
+      for item in container {
+        match item.as_str() {
+          Some(item @ "@graph") | Some(item @ "@set") => definition.container.push(item.to_string()),
+          Some(_) | None => return Err(Error::InvalidLinkedData("unsupported container mapping")),
+        }
+      }
+    }
+
+    if let Some(prefix) = value.get("@prefix") {
+      definition.prefix = prefix
+        .as_bool()
+        .ok_or(Error::InvalidLinkedData("invalid @prefix value"))?;
+    }
+
+    if let Some(previous) = previous {
+      if previous.protected && !self.override_protected {
+        // Protected terms can only be redefined with the same definition
+        definition.protected = previous.protected;
+
+        if definition != previous {
+          return Err(Error::InvalidLinkedData("protected term redefinition"));
+        }
+      }
+    }
+
+    self.active.terms.insert(term.to_string(), definition);
+    self.defined.insert(term.to_string(), true);
+
+    Ok(())
+  }
+}
+
+fn expand_iri(active: &ActiveContext, value: &str, vocab: bool) -> Option<String> {
+  if is_keyword(value) {
+    return Some(value.to_string());
+  }
+
+  if is_keyword_like(value) {
+    return None;
+  }
+
+  if vocab {
+    if let Some(term) = active.terms.get(value) {
+      return term.iri.clone();
+    }
+  }
+
+  if let Some((prefix, suffix)) = split_compact_iri(value) {
+    if prefix == "_" || suffix.starts_with("//") {
+      return Some(value.to_string());
+    }
+
+    if let Some(iri) = active
+      .terms
+      .get(prefix)
+      .filter(|term| term.prefix)
+      .and_then(|term| term.iri.as_deref())
+    {
+      return Some(format!("{}{}", iri, suffix));
+    }
+
+    if is_absolute_iri(value) {
+      return Some(value.to_string());
+    }
+  }
+
+  if vocab {
+    if let Some(vocab) = active.vocab.as_deref() {
+      return Some(format!("{}{}", vocab, value));
+    }
+  }
+
+  Some(value.to_string())
+}
+
+/// Splits a compact IRI into its prefix and suffix.
+fn split_compact_iri(value: &str) -> Option<(&str, &str)> {
+  value
+    .find(':')
+    .filter(|index| *index > 0)
+    .map(|index| (&value[..index], &value[index + 1..]))
+}
+
+/// Returns `true` if `value` is an IRI with a scheme.
+pub(crate) fn is_absolute_iri(value: &str) -> bool {
+  let scheme: &str = match value.find(':') {
+    Some(index) => &value[..index],
+    None => return false,
+  };
+
+  let mut chars = scheme.chars();
+
+  chars.next().map_or(false, |char| char.is_ascii_alphabetic())
+    && chars.all(|char| char.is_ascii_alphanumeric() || char == '+' || char == '-' || char == '.')
+}
+
+fn load(url: &str) -> Result<Value> {
+  let context: &str = CONTEXTS
+    .iter()
+    .find(|(location, _)| *location == url)
+    .map(|(_, context)| *context)
+    .ok_or_else(|| Error::UnknownContext(url.to_string()))?;
+
+  serde_json::from_str(context).map_err(Error::DecodeJSON)
+}
@@ -0,0 +1,318 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde_json::Map;
+
+use crate::common::Value;
+use crate::convert::ld::context::is_absolute_iri;
+use crate::convert::ld::context::ActiveContext;
+use crate::convert::ld::context::Term;
+use crate::convert::ld::keyword::is_keyword;
+use crate::error::Error;
+use crate::error::Result;
+
+/// Expands a JSON-LD `document` as described in the JSON-LD 1.1 Expansion
+/// Algorithm.
+///
+/// Keys which do not expand to an absolute IRI are not dropped, as the
+/// algorithm would do, but rejected with [`Error::UndefinedTerm`]: data
+/// silently excluded from the expanded document would not be covered by
+/// signatures over it.
+pub(crate) fn expand(document: &Value) -> Result<Vec<Value>> {
+  let expanded: Value = match expand_element(&ActiveContext::new(), None, document, false)? {
+    Some(Value::Object(mut object)) if object.len() == 1 && object.contains_key("@graph") => {
+      object.remove("@graph").unwrap_or_else(|| Value::Array(Vec::new()))
+    }
+    Some(expanded) => expanded,
+    None => Value::Array(Vec::new()),
+  };
+
+  Ok(into_array(expanded))
+}
+
+fn expand_element(
+  active: &ActiveContext,
+  property: Option<&str>,
+  element: &Value,
+  from_map: bool,
+) -> Result<Option<Value>> {
+  let scoped: Option<Value> = property
+    .and_then(|property| active.term(property))
+    .and_then(|term| term.context.clone());
+
+  match element {
+    Value::Null => Ok(None),
+    Value::Array(items) => {
+      let mut result: Vec<Value> = Vec::with_capacity(items.len());
+
+      for item in items {
+        match expand_element(active, property, item, from_map)? {
+          Some(Value::Array(expanded)) => result.extend(expanded),
+          Some(expanded) => result.push(expanded),
+          None => {}
+        }
+      }
+
+      Ok(Some(Value::Array(result)))
+    }
+    Value::Object(element) => expand_object(active, property, scoped.as_ref(), element, from_map),
+    _ => {
+      // Free-floating values are dropped
+      if property.is_none() || property == Some("@graph") {
+        return Ok(None);
+      }
+
+      match scoped {
+        Some(scoped) => expand_value(&active.process(&scoped, true, true)?, property, element).map(Some),
+        None => expand_value(active, property, element).map(Some),
+      }
+    }
+  }
+}
+
+fn expand_object(
+  active: &ActiveContext,
+  property: Option<&str>,
+  scoped: Option<&Value>,
+  element: &Map<String, Value>,
+  from_map: bool,
+) -> Result<Option<Value>> {
+  let mut active: ActiveContext = active.clone();
+
+  // Type-scoped contexts do not apply to nested node objects
+  if let Some(previous) = active.previous() {
+    let value: bool = element
+      .keys()
+      .any(|key| active.expand_iri(key, true).as_deref() == Some("@value"));
+
+    let reference: bool = element.len() == 1
+      && element
+        .keys()
+        .all(|key| active.expand_iri(key, true).as_deref() == Some("@id"));
+
+    if !from_map && !value && !reference {
+      active = previous.clone();
+    }
+  }
+
+  if let Some(scoped) = scoped {
+    active = active.process(scoped, true, true)?;
+  }
+
+  if let Some(context) = element.get("@context") {
+    active = active.process(context, false, true)?;
+  }
+
+  let type_scoped: ActiveContext = active.clone();
+
+  let mut keys: Vec<&String> = element.keys().collect();
+
+  keys.sort_unstable();
+
+  for key in keys.iter().copied() {
+    if type_scoped.expand_iri(key, true).as_deref() != Some("@type") {
+      continue;
+    }
+
+    let mut types: Vec<&str> = strings(&element[key.as_str()])?;
+
+    types.sort_unstable();
+
+    for type_ in types {
+      if let Some(context) = type_scoped.term(type_).and_then(|term| term.context.as_ref()) {
+        active = active.process(context, false, false)?;
+      }
+    }
+  }
+
+  let mut result: Map<String, Value> = Map::new();
+
+  for key in keys {
+    let value: &Value = &element[key.as_str()];
+
+    if key == "@context" {
+      continue;
+    }
+
+    let expanded_property: String = match active.expand_iri(key, true) {
+      Some(expanded) if is_keyword(&expanded) || is_absolute_iri(&expanded) || expanded.starts_with("_:") => expanded,
+      Some(_) | None => return Err(Error::UndefinedTerm(key.clone())),
+    };
+
+    if is_keyword(&expanded_property) {
+      let expanded_value: Value = match expanded_property.as_str() {
+        "@id" => {
+          let id: &str = value.as_str().ok_or(Error::InvalidLinkedData("invalid @id value"))?;
+
+          Value::String(expand_document_iri(&active, id)?)
+        }
+        "@type" => {
+          let mut types: Vec<Value> = match result.remove("@type") {
+            Some(Value::Array(types)) => types,
+            Some(_) | None => Vec::new(),
+          };
+
+          for type_ in strings(value)? {
+            match type_scoped.expand_iri(type_, true) {
+              Some(iri) if is_absolute_iri(&iri) || iri.starts_with("_:") => types.push(Value::String(iri)),
+              Some(_) | None => return Err(Error::UndefinedTerm(type_.to_string())),
+            }
+          }
+
+          Value::Array(types)
+        }
+        "@graph" => match expand_element(&active, Some("@graph"), value, false)? {
+          Some(expanded) => Value::Array(into_array(expanded)),
+          None => Value::Array(Vec::new()),
+        },
+        "@value" => match value {
+          Value::Array(_) | Value::Object(_) => return Err(Error::InvalidLinkedData("invalid value object value")),
+          value => value.clone(),
+        },
+        "@set" => match expand_element(&active, property, value, false)? {
+          Some(expanded) => expanded,
+          None => Value::Array(Vec::new()),
+        },
+        _ => return Err(Error::InvalidLinkedData("unsupported keyword")),
+      };
+
+      if result.contains_key(&expanded_property) {
+        return Err(Error::InvalidLinkedData("colliding keywords"));
+      }
+
+      result.insert(expanded_property, expanded_value);
+
+      continue;
+    }
+
+    let term: Option<&Term> = active.term(key);
+
+    if term.and_then(|term| term.type_.as_deref()) == Some("@json") {
+      return Err(Error::InvalidLinkedData("unsupported type mapping"));
+    }
+
+    let expanded_value: Value = match expand_element(&active, Some(key), value, false)? {
+      Some(expanded) => expanded,
+      None => continue,
+    };
+
+    let expanded_value: Vec<Value> = if term.map_or(false, |term| term.has_container("@graph")) {
+      into_array(expanded_value)
+        .into_iter()
+        .map(|value| {
+          let mut graph: Map<String, Value> = Map::new();
+          graph.insert("@graph".into(), Value::Array(into_array(value)));
+          Value::Object(graph)
+        })
+        .collect()
+    } else {
+      into_array(expanded_value)
+    };
+
+    match result
+      .entry(expanded_property)
+      .or_insert_with(|| Value::Array(Vec::new()))
+    {
+      Value::Array(values) => values.extend(expanded_value),
+      _ => return Err(Error::InvalidLinkedData("colliding keywords")),
+    }
+  }
+
+  if result.contains_key("@value") {
+    return expand_value_object(result);
+  }
+
+  if let Some(set) = result.remove("@set") {
+    if !result.is_empty() {
+      return Err(Error::InvalidLinkedData("invalid set object"));
+    }
+
+    return Ok(Some(set));
+  }
+
+  // Free-floating node references are dropped
+  if (property.is_none() || property == Some("@graph"))
+    && (result.is_empty() || (result.len() == 1 && result.contains_key("@id")))
+  {
+    return Ok(None);
+  }
+
+  Ok(Some(Value::Object(result)))
+}
+
+fn expand_value_object(mut result: Map<String, Value>) -> Result<Option<Value>> {
+  if result.keys().any(|key| key != "@value" && key != "@type") {
+    return Err(Error::InvalidLinkedData("unsupported value object"));
+  }
+
+  if result["@value"].is_null() {
+    return Ok(None);
+  }
+
+  if let Some(type_) = result.remove("@type") {
+    let type_: Value = match type_ {
+      Value::Array(mut types) if types.len() == 1 => types.remove(0),
+      _ => return Err(Error::InvalidLinkedData("invalid typed value")),
+    };
+
+    result.insert("@type".into(), type_);
+  }
+
+  Ok(Some(Value::Object(result)))
+}
+
+fn expand_value(active: &ActiveContext, property: Option<&str>, value: &Value) -> Result<Value> {
+  let type_: Option<&str> = property
+    .and_then(|property| active.term(property))
+    .and_then(|term| term.type_.as_deref());
+
+  let mut result: Map<String, Value> = Map::new();
+
+  match (type_, value) {
+    (Some("@id"), Value::String(value)) => {
+      result.insert("@id".into(), Value::String(expand_document_iri(active, value)?));
+    }
+    (Some("@vocab"), Value::String(value)) => match active.expand_iri(value, true) {
+      Some(iri) if is_absolute_iri(&iri) || iri.starts_with("_:") => {
+        result.insert("@id".into(), Value::String(iri));
+      }
+      Some(_) | None => return Err(Error::UndefinedTerm(value.clone())),
+    },
+    (Some(type_), _) if type_ != "@id" && type_ != "@vocab" && type_ != "@none" => {
+      result.insert("@value".into(), value.clone());
+      result.insert("@type".into(), Value::String(type_.to_string()));
+    }
+    (_, _) => {
+      result.insert("@value".into(), value.clone());
+    }
+  }
+
+  Ok(Value::Object(result))
+}
+
+/// Expands an IRI used as a node identifier; relative IRIs are rejected since
+/// documents have no base IRI to resolve them against.
+fn expand_document_iri(active: &ActiveContext, value: &str) -> Result<String> {
+  match active.expand_iri(value, false) {
+    Some(iri) if is_absolute_iri(&iri) || iri.starts_with("_:") => Ok(iri),
+    Some(_) | None => Err(Error::InvalidLinkedData("relative IRI")),
+  }
+}
+
+fn strings(value: &Value) -> Result<Vec<&str>> {
+  match value {
+    Value::String(value) => Ok(vec![value.as_str()]),
+    Value::Array(values) => values
+      .iter()
+      .map(|value| value.as_str().ok_or(Error::InvalidLinkedData("invalid type value")))
+      .collect(),
+    _ => Err(Error::InvalidLinkedData("invalid type value")),
+  }
+}
+
+pub(crate) fn into_array(value: Value) -> Vec<Value> {
+  match value {
+    Value::Array(values) => values,
+    value => vec![value],
+  }
+}
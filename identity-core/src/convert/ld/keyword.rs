@@ -0,0 +1,39 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+const KEYWORDS: &[&str] = &[
+  "@base",
+  "@container",
+  "@context",
+  "@direction",
+  "@graph",
+  "@id",
+  "@import",
+  "@included",
+  "@index",
+  "@json",
+  "@language",
+  "@list",
+  "@nest",
+  "@none",
+  "@prefix",
+  "@propagate",
+  "@protected",
+  "@reverse",
+  "@set",
+  "@type",
+  "@value",
+  "@version",
+  "@vocab",
+];
+
+/// Returns `true` if `value` is a JSON-LD keyword.
+pub(crate) fn is_keyword(value: &str) -> bool {
+  KEYWORDS.contains(&value)
+}
+
+/// Returns `true` if `value` has the form of a keyword (`@` followed by
+/// letters); such values are reserved and ignored.
+pub(crate) fn is_keyword_like(value: &str) -> bool {
+  value.len() > 1 && value.starts_with('@') && value[1..].chars().all(|char| char.is_ascii_alphabetic())
+}
@@ -0,0 +1,26 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Canonicalization of JSON-LD documents.
+//!
+//! Documents are expanded and converted to RDF following JSON-LD 1.1, then
+//! canonicalized with URDNA2015. Only the subset of JSON-LD needed by the
+//! DID and Verifiable Credential vocabularies is supported, and contexts are
+//! resolved from a set of embedded contexts instead of being fetched.
+
+mod context;
+mod expansion;
+mod keyword;
+mod rdf;
+mod urdna2015;
+
+use crate::common::Value;
+use crate::error::Result;
+
+/// Returns the canonical N-Quads representation of the JSON-LD `document`.
+pub(crate) fn canonicalize(document: &Value) -> Result<String> {
+  let expanded: Vec<Value> = self::expansion::expand(document)?;
+  let quads: Vec<self::rdf::Quad> = self::rdf::to_rdf(&expanded)?;
+
+  self::urdna2015::canonicalize(&quads)
+}
@@ -0,0 +1,357 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use serde_json::Map;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use crate::common::Value;
+use crate::convert::ld::keyword::is_keyword;
+use crate::error::Error;
+use crate::error::Result;
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+
+const DEFAULT_GRAPH: &str = "@default";
+
+/// An RDF term.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Term {
+  Iri(String),
+  Blank(String),
+  Literal { value: String, datatype: String },
+}
+
+impl Term {
+  fn node(id: &str) -> Self {
+    if id.starts_with("_:") {
+      Self::Blank(id.to_string())
+    } else {
+      Self::Iri(id.to_string())
+    }
+  }
+
+  /// Returns the identifier of the blank node, if the term is one.
+  pub(crate) fn as_blank(&self) -> Option<&str> {
+    match self {
+      Self::Blank(id) => Some(id),
+      Self::Iri(_) | Self::Literal { .. } => None,
+    }
+  }
+}
+
+impl Display for Term {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    match self {
+      Self::Iri(iri) => write!(f, "<{}>", iri),
+      Self::Blank(id) => f.write_str(id),
+      Self::Literal { value, datatype } => {
+        f.write_str("\"")?;
+
+        for char in value.chars() {
+          match char {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            char => write!(f, "{}", char)?,
+          }
+        }
+
+        f.write_str("\"")?;
+
+        if datatype != XSD_STRING {
+          write!(f, "^^<{}>", datatype)?;
+        }
+
+        Ok(())
+      }
+    }
+  }
+}
+
+/// An RDF quad; `graph` is `None` for quads of the default graph.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Quad {
+  pub(crate) subject: Term,
+  pub(crate) predicate: Term,
+  pub(crate) object: Term,
+  pub(crate) graph: Option<Term>,
+}
+
+impl Quad {
+  /// Returns the terms of the quad which may be blank nodes.
+  pub(crate) fn terms(&self) -> impl Iterator<Item = &Term> {
+    core::iter::once(&self.subject)
+      .chain(core::iter::once(&self.object))
+      .chain(self.graph.iter())
+  }
+
+  /// Serializes the quad as a line of N-Quads, replacing blank node
+  /// identifiers with the result of `label`.
+  pub(crate) fn to_nquad<F>(&self, label: F) -> String
+  where
+    F: Fn(&str) -> String,
+  {
+    let term = |term: &Term| match term {
+      Term::Blank(id) => label(id),
+      term => term.to_string(),
+    };
+
+    match self.graph.as_ref() {
+      Some(graph) => format!(
+        "{} {} {} {} .\n",
+        term(&self.subject),
+        term(&self.predicate),
+        term(&self.object),
+        term(graph)
+      ),
+      None => format!(
+        "{} {} {} .\n",
+        term(&self.subject),
+        term(&self.predicate),
+        term(&self.object)
+      ),
+    }
+  }
+}
+
+type Node = BTreeMap<String, Vec<Value>>;
+type Graph = BTreeMap<String, Node>;
+
+/// Converts an expanded JSON-LD document into an RDF dataset as described in
+/// the JSON-LD 1.1 Deserialize JSON-LD to RDF Algorithm.
+pub(crate) fn to_rdf(expanded: &[Value]) -> Result<Vec<Quad>> {
+  let mut generator: NodeMapGenerator = NodeMapGenerator::default();
+
+  generator.graphs.insert(DEFAULT_GRAPH.to_string(), Graph::new());
+
+  for element in expanded {
+    generator.generate(element, DEFAULT_GRAPH, None, None)?;
+  }
+
+  let mut quads: Vec<Quad> = Vec::new();
+
+  for (name, graph) in generator.graphs.iter() {
+    let graph_name: Option<Term> = if name == DEFAULT_GRAPH {
+      None
+    } else {
+      Some(Term::node(name))
+    };
+
+    for (subject, node) in graph.iter() {
+      for (property, values) in node.iter() {
+        if property == "@type" {
+          for type_ in values {
+            let type_: &str = type_.as_str().ok_or(Error::InvalidLinkedData("invalid type value"))?;
+
+            quads.push(Quad {
+              subject: Term::node(subject),
+              predicate: Term::Iri(RDF_TYPE.to_string()),
+              object: Term::node(type_),
+              graph: graph_name.clone(),
+            });
+          }
+        } else if !is_keyword(property) {
+          // Blank node predicates are not valid RDF
+          if property.starts_with("_:") {
+            return Err(Error::InvalidLinkedData("blank node predicate"));
+          }
+
+          for value in values {
+            quads.push(Quad {
+              subject: Term::node(subject),
+              predicate: Term::Iri(property.clone()),
+              object: to_object(value)?,
+              graph: graph_name.clone(),
+            });
+          }
+        }
+      }
+    }
+  }
+
+  Ok(quads)
+}
+
+fn to_object(value: &Value) -> Result<Term> {
+  if let Some(id) = value.get("@id").and_then(Value::as_str) {
+    return Ok(Term::node(id));
+  }
+
+  let datatype: Option<&str> = value.get("@type").and_then(Value::as_str);
+
+  let (value, default): (String, &str) = match value.get("@value") {
+    Some(Value::Bool(value)) => (value.to_string(), XSD_BOOLEAN),
+    Some(Value::Number(number)) => {
+      let double: bool = datatype == Some(XSD_DOUBLE);
+
+      match (number.as_i64(), number.as_u64(), number.as_f64()) {
+        (Some(value), _, _) if !double => (value.to_string(), XSD_INTEGER),
+        (_, Some(value), _) if !double => (value.to_string(), XSD_INTEGER),
+        (_, _, Some(value)) if !double && value.fract() == 0.0 && value.abs() < 1e21 => {
+          (format!("{:.0}", value), XSD_INTEGER)
+        }
+        (_, _, Some(value)) => (canonical_double(value), XSD_DOUBLE),
+        (_, _, None) => return Err(Error::InvalidLinkedData("invalid number")),
+      }
+    }
+    Some(Value::String(value)) => (value.clone(), XSD_STRING),
+    Some(_) | None => return Err(Error::InvalidLinkedData("invalid value object")),
+  };
+
+  Ok(Term::Literal {
+    value,
+    datatype: datatype.unwrap_or(default).to_string(),
+  })
+}
+
+/// Returns the canonical lexical form of an `xsd:double`, e.g. `1.1E0`.
+fn canonical_double(value: f64) -> String {
+  let formatted: String = format!("{:.15E}", value);
+  let (mantissa, exponent): (&str, &str) = formatted.split_at(formatted.find('E').unwrap_or(formatted.len()));
+  let mantissa: &str = mantissa.trim_end_matches('0');
+  let mantissa: String = if mantissa.ends_with('.') {
+    format!("{}0", mantissa)
+  } else {
+    mantissa.to_string()
+  };
+
+  format!("{}{}", mantissa, exponent)
+}
+
+#[derive(Default)]
+struct NodeMapGenerator {
+  graphs: BTreeMap<String, Graph>,
+  blank_nodes: HashMap<String, String>,
+  counter: usize,
+}
+
+impl NodeMapGenerator {
+  /// Returns a new blank node identifier, or the identifier previously issued
+  /// for the blank node `id`.
+  fn blank_node(&mut self, id: Option<&str>) -> String {
+    if let Some(issued) = id.and_then(|id| self.blank_nodes.get(id)) {
+      return issued.clone();
+    }
+
+    let issued: String = format!("_:b{}", self.counter);
+
+    self.counter += 1;
+
+    if let Some(id) = id {
+      self.blank_nodes.insert(id.to_string(), issued.clone());
+    }
+
+    issued
+  }
+
+  fn node_id(&mut self, id: &str) -> String {
+    if id.starts_with("_:") {
+      self.blank_node(Some(id))
+    } else {
+      id.to_string()
+    }
+  }
+
+  fn generate(&mut self, element: &Value, graph: &str, subject: Option<&str>, property: Option<&str>) -> Result<()> {
+    let element: &Map<String, Value> = match element {
+      Value::Array(items) => {
+        for item in items {
+          self.generate(item, graph, subject, property)?;
+        }
+
+        return Ok(());
+      }
+      Value::Object(element) => element,
+      _ => return Err(Error::InvalidLinkedData("invalid expanded element")),
+    };
+
+    if element.contains_key("@value") {
+      if let (Some(subject), Some(property)) = (subject, property) {
+        self.append(graph, subject, property, Value::Object(element.clone()));
+      }
+
+      return Ok(());
+    }
+
+    let id: String = match element.get("@id").and_then(Value::as_str) {
+      Some(id) => self.node_id(id),
+      None => self.blank_node(None),
+    };
+
+    self
+      .graphs
+      .entry(graph.to_string())
+      .or_default()
+      .entry(id.clone())
+      .or_default();
+
+    if let (Some(subject), Some(property)) = (subject, property) {
+      let mut reference: Map<String, Value> = Map::new();
+
+      reference.insert("@id".into(), Value::String(id.clone()));
+
+      self.append(graph, subject, property, Value::Object(reference));
+    }
+
+    for (key, value) in element.iter() {
+      match key.as_str() {
+        "@id" => {}
+        "@type" => {
+          for type_ in value.as_array().into_iter().flatten() {
+            let type_: &str = type_.as_str().ok_or(Error::InvalidLinkedData("invalid type value"))?;
+            let type_: String = self.node_id(type_);
+
+            self.append(graph, &id, "@type", Value::String(type_));
+          }
+        }
+        "@graph" => {
+          self.graphs.entry(id.clone()).or_default();
+          self.generate(value, &id, None, None)?;
+        }
+        key if is_keyword(key) => return Err(Error::InvalidLinkedData("unsupported keyword")),
+        key => {
+          let key: String = self.node_id(key);
+
+          self
+            .graphs
+            .entry(graph.to_string())
+            .or_default()
+            .entry(id.clone())
+            .or_default()
+            .entry(key.clone())
+            .or_default();
+
+          self.generate(value, graph, Some(&id), Some(&key))?;
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Appends `value` to the values of `property` of the node `subject`,
+  /// unless it is already present.
+  fn append(&mut self, graph: &str, subject: &str, property: &str, value: Value) {
+    let values: &mut Vec<Value> = self
+      .graphs
+      .entry(graph.to_string())
+      .or_default()
+      .entry(subject.to_string())
+      .or_default()
+      .entry(property.to_string())
+      .or_default();
+
+    if !values.contains(&value) {
+      values.push(value);
+    }
+  }
+}
@@ -0,0 +1,299 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::hashes::sha::Sha256;
+use crypto::hashes::Digest;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use crate::convert::ld::rdf::Quad;
+use crate::convert::ld::rdf::Term;
+use crate::error::Error;
+use crate::error::Result;
+use crate::utils::encode_b16;
+
+/// The maximum number of N-degree hashes computed for a dataset, which bounds
+/// the work spent on datasets crafted to exhaust the algorithm.
+const MAX_DEEP_ITERATIONS: usize = 4096;
+
+/// The maximum number of related blank nodes with the same hash, whose
+/// permutations are compared to choose a canonical labeling.
+const MAX_PERMUTATION_LENGTH: usize = 6;
+
+/// Returns the canonical N-Quads serialization of `quads` as described in the
+/// [URDNA2015][SPEC] RDF Dataset Canonicalization Algorithm.
+///
+/// [SPEC]: https://json-ld.github.io/rdf-dataset-canonicalization/spec/
+pub(crate) fn canonicalize(quads: &[Quad]) -> Result<String> {
+  let mut state: State<'_> = State {
+    blank_nodes: HashMap::new(),
+    canonical: Issuer::new("_:c14n"),
+    iterations: 0,
+  };
+
+  for quad in quads {
+    for term in quad.terms() {
+      if let Some(id) = term.as_blank() {
+        let quads: &mut Vec<&Quad> = state.blank_nodes.entry(id).or_default();
+
+        if !quads.contains(&quad) {
+          quads.push(quad);
+        }
+      }
+    }
+  }
+
+  let mut hashes: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+
+  for id in state.blank_nodes.keys() {
+    hashes.entry(state.hash_first_degree(id)).or_default().push(id);
+  }
+
+  // Blank nodes with a unique first degree hash are labeled first
+  let mut shared: Vec<Vec<&str>> = Vec::new();
+
+  for (_, ids) in hashes {
+    if ids.len() == 1 {
+      state.canonical.issue(ids[0]);
+    } else {
+      shared.push(ids);
+    }
+  }
+
+  for ids in shared {
+    let mut paths: Vec<(String, Issuer)> = Vec::new();
+
+    for id in ids {
+      if state.canonical.get(id).is_some() {
+        continue;
+      }
+
+      let mut issuer: Issuer = Issuer::new("_:b");
+
+      issuer.issue(id);
+      paths.push(state.hash_n_degree(id, issuer)?);
+    }
+
+    paths.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (_, issuer) in paths {
+      for id in issuer.order.iter() {
+        state.canonical.issue(id);
+      }
+    }
+  }
+
+  let mut lines: Vec<String> = quads
+    .iter()
+    .map(|quad| quad.to_nquad(|id| state.canonical.get(id).unwrap_or(id).to_string()))
+    .collect();
+
+  lines.sort_unstable();
+  lines.dedup();
+
+  Ok(lines.concat())
+}
+
+struct State<'a> {
+  blank_nodes: HashMap<&'a str, Vec<&'a Quad>>,
+  canonical: Issuer,
+  iterations: usize,
+}
+
+impl<'a> State<'a> {
+  fn hash_first_degree(&self, id: &str) -> String {
+    let mut nquads: Vec<String> = self.blank_nodes[id]
+      .iter()
+      .map(|quad| quad.to_nquad(|other| if other == id { "_:a" } else { "_:z" }.to_string()))
+      .collect();
+
+    nquads.sort_unstable();
+
+    hash(&nquads.concat())
+  }
+
+  fn hash_related(&self, related: &str, quad: &Quad, issuer: &Issuer, position: char) -> String {
+    let id: String = match self.canonical.get(related).or_else(|| issuer.get(related)) {
+      Some(id) => id.to_string(),
+      None => self.hash_first_degree(related),
+    };
+
+    let mut input: String = position.to_string();
+
+    if position != 'g' {
+      input.push_str(&quad.predicate.to_string());
+    }
+
+    input.push_str(&id);
+
+    hash(&input)
+  }
+
+  fn hash_n_degree(&mut self, id: &'a str, mut issuer: Issuer) -> Result<(String, Issuer)> {
+    self.iterations += 1;
+
+    if self.iterations > MAX_DEEP_ITERATIONS {
+      return Err(Error::InvalidLinkedData("canonicalization limit exceeded"));
+    }
+
+    let mut related: BTreeMap<String, Vec<&'a str>> = BTreeMap::new();
+
+    for quad in self.blank_nodes[id].iter().copied() {
+      let positions: [(char, Option<&'a Term>); 3] = [
+        ('s', Some(&quad.subject)),
+        ('o', Some(&quad.object)),
+        ('g', quad.graph.as_ref()),
+      ];
+
+      for (position, term) in positions.iter().copied() {
+        if let Some(other) = term.and_then(Term::as_blank).filter(|other| *other != id) {
+          related
+            .entry(self.hash_related(other, quad, &issuer, position))
+            .or_default()
+            .push(other);
+        }
+      }
+    }
+
+    let mut data: String = String::new();
+
+    for (hash, ids) in related {
+      data.push_str(&hash);
+
+      if ids.len() > MAX_PERMUTATION_LENGTH {
+        return Err(Error::InvalidLinkedData("canonicalization limit exceeded"));
+      }
+
+      let mut chosen: Option<(String, Issuer)> = None;
+
+      for permutation in permutations(&ids) {
+        let mut copy: Issuer = issuer.clone();
+        let mut path: String = String::new();
+        let mut recursion: Vec<&'a str> = Vec::new();
+        let mut skip: bool = false;
+
+        for other in permutation.iter().copied() {
+          match self.canonical.get(other) {
+            Some(canonical) => path.push_str(canonical),
+            None => {
+              if copy.get(other).is_none() {
+                recursion.push(other);
+              }
+
+              path.push_str(copy.issue(other));
+            }
+          }
+
+          if is_longer(&path, chosen.as_ref()) {
+            skip = true;
+            break;
+          }
+        }
+
+        if skip {
+          continue;
+        }
+
+        for other in recursion {
+          let (result, issuer): (String, Issuer) = self.hash_n_degree(other, copy.clone())?;
+
+          copy = issuer;
+
+          path.push_str(copy.issue(other));
+          path.push('<');
+          path.push_str(&result);
+          path.push('>');
+
+          if is_longer(&path, chosen.as_ref()) {
+            skip = true;
+            break;
+          }
+        }
+
+        if skip {
+          continue;
+        }
+
+        if chosen.as_ref().map_or(true, |(chosen, _)| path < *chosen) {
+          chosen = Some((path, copy));
+        }
+      }
+
+      if let Some((path, chosen)) = chosen {
+        data.push_str(&path);
+        issuer = chosen;
+      }
+    }
+
+    Ok((hash(&data), issuer))
+  }
+}
+
+/// Returns `true` if `path` can not result in a lexicographically smaller
+/// path than the `chosen` path.
+fn is_longer(path: &str, chosen: Option<&(String, Issuer)>) -> bool {
+  match chosen {
+    Some((chosen, _)) => path.len() >= chosen.len() && path > chosen.as_str(),
+    None => false,
+  }
+}
+
+/// An issuer of blank node identifiers, which remembers the order in which
+/// identifiers were issued.
+#[derive(Clone, Debug)]
+struct Issuer {
+  prefix: &'static str,
+  issued: HashMap<String, String>,
+  order: Vec<String>,
+}
+
+impl Issuer {
+  fn new(prefix: &'static str) -> Self {
+    Self {
+      prefix,
+      issued: HashMap::new(),
+      order: Vec::new(),
+    }
+  }
+
+  fn get(&self, id: &str) -> Option<&str> {
+    self.issued.get(id).map(String::as_str)
+  }
+
+  fn issue(&mut self, id: &str) -> &str {
+    if !self.issued.contains_key(id) {
+      let issued: String = format!("{}{}", self.prefix, self.order.len());
+
+      self.issued.insert(id.to_string(), issued);
+      self.order.push(id.to_string());
+    }
+
+    &self.issued[id]
+  }
+}
+
+fn hash(data: &str) -> String {
+  encode_b16(&Sha256::digest(data.as_bytes()))
+}
+
+/// Returns all permutations of `items`.
+fn permutations<'a>(items: &[&'a str]) -> Vec<Vec<&'a str>> {
+  if items.len() <= 1 {
+    return vec![items.to_vec()];
+  }
+
+  let mut result: Vec<Vec<&'a str>> = Vec::new();
+
+  for (index, item) in items.iter().enumerate() {
+    let mut rest: Vec<&'a str> = items.to_vec();
+
+    rest.remove(index);
+
+    for mut permutation in permutations(&rest) {
+      permutation.insert(0, item);
+      result.push(permutation);
+    }
+  }
+
+  result
+}
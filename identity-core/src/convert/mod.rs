@@ -3,9 +3,13 @@
 
 //! Traits for conversions between types.
 
+mod encoding;
 mod json;
+pub(crate) mod ld;
 mod serde_into;
 
+pub use self::encoding::Encoding;
+pub use self::json::json_eq;
 pub use self::json::AsJson;
 pub use self::json::FromJson;
 pub use self::json::ToJson;
@@ -26,6 +26,10 @@ pub(crate) fn ed25519_verify(message: &[u8], signature: &[u8], public: &[u8]) ->
   }
 }
 
+pub(crate) fn ed25519_public(secret: &[u8]) -> Result<[u8; PUBLIC_KEY_LEN]> {
+  parse_secret(secret).map(|secret| secret.public_key().to_compressed_bytes())
+}
+
 fn parse_public(slice: &[u8]) -> Result<ed25519::PublicKey> {
   let bytes: [u8; PUBLIC_KEY_LEN] = slice
     .get(..PUBLIC_KEY_LEN)
@@ -46,9 +50,8 @@ fn parse_secret(slice: &[u8]) -> Result<ed25519::SecretKey> {
 
 fn parse_signature(slice: &[u8]) -> Result<ed25519::Signature> {
   let bytes: [u8; SIGNATURE_LEN] = slice
-    .get(..SIGNATURE_LEN)
-    .and_then(|bytes| bytes.try_into().ok())
-    .ok_or_else(|| Error::InvalidSigLength(slice.len(), SIGNATURE_LEN))?;
+    .try_into()
+    .map_err(|_| Error::InvalidSigLength(slice.len(), SIGNATURE_LEN))?;
 
   Ok(ed25519::Signature::from_bytes(bytes))
 }
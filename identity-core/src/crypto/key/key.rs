@@ -5,6 +5,8 @@ use core::fmt::Debug;
 use core::fmt::Display;
 use core::fmt::Formatter;
 use core::fmt::Result;
+use subtle::Choice;
+use subtle::ConstantTimeEq;
 use zeroize::Zeroize;
 
 macro_rules! impl_key {
@@ -25,6 +27,21 @@ macro_rules! impl_key {
       }
     }
 
+    impl ConstantTimeEq for $ident {
+      fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+      }
+    }
+
+    // Keys are compared in constant time to avoid leaking key material
+    impl PartialEq for $ident {
+      fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+      }
+    }
+
+    impl Eq for $ident {}
+
     impl Drop for $ident {
       fn drop(&mut self) {
         self.0.zeroize();
@@ -13,5 +13,6 @@ pub use self::collection::KeyCollection;
 pub use self::key::PublicKey;
 pub use self::key::SecretKey;
 pub use self::pair::KeyPair;
+pub use self::pair::PublicKeyPair;
 pub use self::reference::KeyRef;
 pub use self::type_::KeyType;
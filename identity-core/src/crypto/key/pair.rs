@@ -1,13 +1,21 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use subtle::Choice;
+use subtle::ConstantTimeEq;
 use zeroize::Zeroize;
 
+use crate::common::Object;
+use crate::common::Value;
+use crate::crypto::ed25519_public;
 use crate::crypto::KeyRef;
 use crate::crypto::KeyType;
 use crate::crypto::PublicKey;
 use crate::crypto::SecretKey;
+use crate::error::Error;
 use crate::error::Result;
+use crate::utils::decode_b64;
+use crate::utils::encode_b64;
 use crate::utils::generate_ed25519;
 
 /// A convenient type for representing a pair of cryptographic keys.
@@ -33,6 +41,81 @@ impl KeyPair {
     Ok(Self { type_, public, secret })
   }
 
+  /// Creates a new [`Ed25519`][`KeyType::Ed25519`] [`KeyPair`] deterministically
+  /// derived from the given `seed`.
+  ///
+  /// Note: This is intended for reproducible tests - use [`KeyPair::new_ed25519`]
+  /// to generate keys for production use.
+  pub fn from_seed_ed25519(seed: [u8; 32]) -> Result<Self> {
+    Self::from_seed(KeyType::Ed25519, &seed)
+  }
+
+  /// Creates a new [`KeyPair`] with the given [`key type`][`KeyType`]
+  /// deterministically derived from the given `seed`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the `seed` is not a valid secret key of the given type.
+  pub fn from_seed(type_: KeyType, seed: &[u8]) -> Result<Self> {
+    let public: PublicKey = match type_ {
+      KeyType::Ed25519 if seed.len() == 32 => ed25519_public(seed)?.to_vec().into(),
+      KeyType::Ed25519 => return Err(Error::InvalidKeyLength(seed.len(), 32)),
+    };
+
+    Ok(Self {
+      type_,
+      public,
+      secret: seed.to_vec().into(),
+    })
+  }
+
+  /// Creates a new [`KeyPair`] from an OKP JSON Web Key (JWK) with both the
+  /// public (`x`) and private (`d`) members.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the `kty`/`crv` members are unsupported, either key component is
+  /// missing, or the public key does not belong to the private key.
+  pub fn from_jwk(jwk: &Object) -> Result<Self> {
+    let kty: Option<&str> = jwk.get("kty").and_then(Value::as_str);
+    let crv: Option<&str> = jwk.get("crv").and_then(Value::as_str);
+
+    let type_: KeyType = match (kty, crv) {
+      (Some("OKP"), Some("Ed25519")) => KeyType::Ed25519,
+      (_, _) => return Err(Error::InvalidKeyFormat),
+    };
+
+    let public: Vec<u8> = decode_jwk_member(jwk, "x")?;
+    let secret: SecretKey = decode_jwk_member(jwk, "d")?.into();
+
+    let derived: [u8; 32] = match type_ {
+      KeyType::Ed25519 => ed25519_public(secret.as_ref())?,
+    };
+
+    if !bool::from(derived[..].ct_eq(&public[..])) {
+      return Err(Error::InvalidKeyFormat);
+    }
+
+    Ok(Self {
+      type_,
+      public: public.into(),
+      secret,
+    })
+  }
+
+  /// Returns the `KeyPair` as an OKP JSON Web Key (JWK).
+  ///
+  /// The private key (`d`) is only included if `include_private` is `true`.
+  pub fn to_jwk(&self, include_private: bool) -> Object {
+    let mut jwk: Object = encode_jwk(self.type_, &self.public);
+
+    if include_private {
+      jwk.insert("d".into(), encode_jwk_member(self.secret.as_ref()).into());
+    }
+
+    jwk
+  }
+
   /// Returns the [`type`][`KeyType`] of the `KeyPair` object.
   pub const fn type_(&self) -> KeyType {
     self.type_
@@ -48,6 +131,17 @@ impl KeyPair {
     KeyRef::new(self.type_, self.public.as_ref())
   }
 
+  /// Returns a [`PublicKeyPair`] with a copy of the public key only.
+  ///
+  /// The secret key is not copied; use this to hand out verification
+  /// material without exposing the secret through a cloned `KeyPair`.
+  pub fn to_public(&self) -> PublicKeyPair {
+    PublicKeyPair {
+      type_: self.type_,
+      public: self.public.clone(),
+    }
+  }
+
   /// Returns a reference to the [`SecretKey`] object.
   pub const fn secret(&self) -> &SecretKey {
     &self.secret
@@ -57,8 +151,31 @@ impl KeyPair {
   pub fn secret_ref(&self) -> KeyRef<'_> {
     KeyRef::new(self.type_, self.secret.as_ref())
   }
+
+  /// Returns `true` if both `KeyPair`s have the same type and key material.
+  ///
+  /// The keys are compared in constant time; this is equivalent to `==`.
+  pub fn ct_eq(&self, other: &Self) -> bool {
+    ConstantTimeEq::ct_eq(self, other).into()
+  }
 }
 
+impl ConstantTimeEq for KeyPair {
+  fn ct_eq(&self, other: &Self) -> Choice {
+    let type_: Choice = Choice::from((self.type_ == other.type_) as u8);
+
+    type_ & self.public.ct_eq(&other.public) & self.secret.ct_eq(&other.secret)
+  }
+}
+
+impl PartialEq for KeyPair {
+  fn eq(&self, other: &Self) -> bool {
+    ConstantTimeEq::ct_eq(self, other).into()
+  }
+}
+
+impl Eq for KeyPair {}
+
 impl Drop for KeyPair {
   fn drop(&mut self) {
     self.public.zeroize();
@@ -83,6 +200,83 @@ impl From<(KeyType, PublicKey, SecretKey)> for KeyPair {
   }
 }
 
+// =============================================================================
+// =============================================================================
+
+/// The public half of a [`KeyPair`].
+///
+/// A `PublicKeyPair` carries no secret key material and can therefore not be
+/// used to create signatures; APIs that sign data require a [`KeyPair`].
+#[derive(Clone, Debug)]
+pub struct PublicKeyPair {
+  type_: KeyType,
+  public: PublicKey,
+}
+
+impl PublicKeyPair {
+  /// Returns the [`type`][`KeyType`] of the `PublicKeyPair` object.
+  pub const fn type_(&self) -> KeyType {
+    self.type_
+  }
+
+  /// Returns a reference to the [`PublicKey`] object.
+  pub const fn public(&self) -> &PublicKey {
+    &self.public
+  }
+
+  /// Returns the public key as a [`KeyRef`] object.
+  pub fn public_ref(&self) -> KeyRef<'_> {
+    KeyRef::new(self.type_, self.public.as_ref())
+  }
+
+  /// Returns the `PublicKeyPair` as an OKP JSON Web Key (JWK).
+  pub fn to_jwk(&self) -> Object {
+    encode_jwk(self.type_, &self.public)
+  }
+}
+
+impl From<&KeyPair> for PublicKeyPair {
+  fn from(other: &KeyPair) -> Self {
+    other.to_public()
+  }
+}
+
+impl From<(KeyType, PublicKey)> for PublicKeyPair {
+  fn from(other: (KeyType, PublicKey)) -> Self {
+    Self {
+      type_: other.0,
+      public: other.1,
+    }
+  }
+}
+
+fn encode_jwk(type_: KeyType, public: &PublicKey) -> Object {
+  let crv: &str = match type_ {
+    KeyType::Ed25519 => "Ed25519",
+  };
+
+  let mut jwk: Object = Object::new();
+
+  jwk.insert("kty".into(), "OKP".into());
+  jwk.insert("crv".into(), crv.into());
+  jwk.insert("x".into(), encode_jwk_member(public.as_ref()).into());
+
+  jwk
+}
+
+fn decode_jwk_member(jwk: &Object, name: &str) -> Result<Vec<u8>> {
+  jwk
+    .get(name)
+    .and_then(Value::as_str)
+    .ok_or(Error::InvalidKeyFormat)
+    .and_then(decode_b64)
+}
+
+fn encode_jwk_member(data: &[u8]) -> String {
+  // JWK members are base64url-encoded without padding
+  encode_b64(data).trim_end_matches('=').to_string()
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -94,4 +288,90 @@ mod tests {
     assert_eq!(keypair.public().as_ref().len(), 32);
     assert_eq!(keypair.secret().as_ref().len(), 32);
   }
+
+  #[test]
+  fn test_from_seed_ed25519() {
+    // RFC 8032 - Test 1
+    let seed: [u8; 32] = [
+      0x9d, 0x61, 0xb1, 0x9d, 0xef, 0xfd, 0x5a, 0x60, 0xba, 0x84, 0x4a, 0xf4, 0x92, 0xec, 0x2c, 0xc4, 0x44, 0x49, 0xc5,
+      0x69, 0x7b, 0x32, 0x69, 0x19, 0x70, 0x3b, 0xac, 0x03, 0x1c, 0xae, 0x7f, 0x60,
+    ];
+
+    let public: [u8; 32] = [
+      0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9, 0x64, 0x07, 0x3a, 0x0e, 0xe1, 0x72,
+      0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02, 0x1a, 0x68, 0xf7, 0x07, 0x51, 0x1a,
+    ];
+
+    let keypair: KeyPair = KeyPair::from_seed_ed25519(seed).unwrap();
+
+    assert_eq!(keypair.type_(), KeyType::Ed25519);
+    assert_eq!(keypair.public().as_ref(), &public[..]);
+    assert_eq!(keypair.secret().as_ref(), &seed[..]);
+
+    assert!(KeyPair::from_seed(KeyType::Ed25519, &seed[..31]).is_err());
+    assert!(KeyPair::from_seed(KeyType::Ed25519, &[0; 33]).is_err());
+  }
+
+  #[test]
+  fn test_ct_eq() {
+    let keypair: KeyPair = KeyPair::from_seed_ed25519([1; 32]).unwrap();
+    let other: KeyPair = KeyPair::from_seed_ed25519([2; 32]).unwrap();
+
+    assert!(keypair.ct_eq(&KeyPair::from_seed_ed25519([1; 32]).unwrap()));
+    assert!(!keypair.ct_eq(&other));
+
+    assert_eq!(keypair.secret(), &SecretKey::from(vec![1; 32]));
+    assert_ne!(keypair.secret(), other.secret());
+    assert_ne!(keypair.secret(), &SecretKey::from(vec![1; 31]));
+  }
+
+  #[test]
+  fn test_jwk_roundtrip() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let jwk: Object = keypair.to_jwk(true);
+
+    assert_eq!(jwk["kty"], "OKP");
+    assert_eq!(jwk["crv"], "Ed25519");
+
+    let other: KeyPair = KeyPair::from_jwk(&jwk).unwrap();
+
+    assert_eq!(other.type_(), keypair.type_());
+    assert_eq!(other.public().as_ref(), keypair.public().as_ref());
+    assert_eq!(other.secret().as_ref(), keypair.secret().as_ref());
+  }
+
+  #[test]
+  fn test_jwk_public_only() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let jwk: Object = keypair.to_jwk(false);
+
+    assert!(!jwk.contains_key("d"));
+    assert!(KeyPair::from_jwk(&jwk).is_err());
+  }
+
+  #[test]
+  fn test_to_public() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let public: PublicKeyPair = keypair.to_public();
+
+    assert_eq!(public.type_(), keypair.type_());
+    assert_eq!(public.public().as_ref(), keypair.public().as_ref());
+    assert_eq!(public.to_jwk(), keypair.to_jwk(false));
+  }
+
+  #[test]
+  fn test_jwk_invalid() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut jwk: Object = keypair.to_jwk(true);
+
+    jwk.insert("crv".into(), "X25519".into());
+    assert!(KeyPair::from_jwk(&jwk).is_err());
+
+    // The public key does not match the private key
+    let mut jwk: Object = keypair.to_jwk(true);
+    let other: Object = KeyPair::new_ed25519().unwrap().to_jwk(false);
+
+    jwk.insert("x".into(), other["x"].clone());
+    assert!(KeyPair::from_jwk(&jwk).is_err());
+  }
 }
@@ -26,6 +26,13 @@ impl KeyType {
     }
   }
 
+  /// Returns the length in bytes of a signature created with this key type.
+  pub const fn signature_length(&self) -> usize {
+    match self {
+      Self::Ed25519 => 64,
+    }
+  }
+
   /// Creates a DID Document public key value for the given Merkle tree `root`.
   pub fn encode_key<D>(&self, root: &Hash<D>) -> Vec<u8>
   where
@@ -16,16 +16,23 @@ pub use self::key::KeyPair;
 pub use self::key::KeyRef;
 pub use self::key::KeyType;
 pub use self::key::PublicKey;
+pub use self::key::PublicKeyPair;
 pub use self::key::SecretKey;
+pub use self::proof::Ed25519Signature2020;
 pub use self::proof::JcsEd25519Signature2020;
 pub use self::signature::SetSignature;
 pub use self::signature::Signature;
 pub use self::signature::SignatureName;
+pub use self::signature::SignatureOptions;
 pub use self::signature::SignatureSign;
 pub use self::signature::SignatureValue;
 pub use self::signature::SignatureVerify;
 pub use self::signature::TrySignature;
 pub use self::signature::TrySignatureMut;
 
+pub(crate) use self::ed25519::ed25519_public;
 pub(crate) use self::ed25519::ed25519_sign;
 pub(crate) use self::ed25519::ed25519_verify;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,205 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::hashes::sha::Sha256;
+use crypto::hashes::Digest;
+use erased_serde::Serialize;
+use serde_json::Map;
+
+use crate::common::Value;
+use crate::convert::ld::canonicalize;
+use crate::convert::ToJson;
+use crate::crypto::ed25519_sign;
+use crate::crypto::ed25519_verify;
+use crate::crypto::SignatureName;
+use crate::crypto::SignatureSign;
+use crate::crypto::SignatureValue;
+use crate::crypto::SignatureVerify;
+use crate::error::Error;
+use crate::error::Result;
+use crate::utils::decode_b58;
+use crate::utils::encode_b58;
+
+const SIGNATURE_NAME: &str = "Ed25519Signature2020";
+
+// The multibase prefix of base58-btc encoded data
+const MULTIBASE_B58: char = 'z';
+
+/// An implementation of the [Ed25519 Signature 2020][SPEC] signature suite.
+///
+/// The signature is created over the SHA-256 hashes of the proof options and
+/// of the document, both canonicalized with URDNA2015, and stored as a
+/// multibase-encoded (base58-btc) `proofValue`.
+///
+/// Note: JSON-LD contexts are never fetched. Documents may only reference the
+/// DID v1, Verifiable Credentials v1, Ed25519 2020 and X25519 2019 contexts,
+/// and must not contain terms left undefined by them.
+///
+/// Users should use the [`SignatureSign`]/[`SignatureVerify`] traits to access
+/// this implementation.
+///
+/// [SPEC]: https://w3c-ccg.github.io/lds-ed25519-2020/
+#[derive(Clone, Copy, Debug)]
+pub struct Ed25519Signature2020;
+
+impl Ed25519Signature2020 {
+  /// Returns the data signed by the suite: the SHA-256 hash of the
+  /// canonicalized proof options followed by the SHA-256 hash of the
+  /// canonicalized document.
+  fn signing_input(data: &dyn Serialize) -> Result<Vec<u8>> {
+    let mut document: Value = data.to_json_value()?;
+
+    let mut options: Map<String, Value> = match document.as_object_mut().and_then(|document| document.remove("proof")) {
+      Some(Value::Object(options)) => options,
+      Some(_) => return Err(Error::InvalidProofFormat),
+      None => return Err(Error::MissingSignature),
+    };
+
+    let context: Value = document
+      .get("@context")
+      .cloned()
+      .ok_or(Error::InvalidLinkedData("missing @context"))?;
+
+    // The proof options are canonicalized with the context of the document
+    options.remove("proofValue");
+    options.insert("@context".into(), context);
+
+    let mut input: Vec<u8> = Vec::new();
+
+    input.extend_from_slice(&Sha256::digest(canonicalize(&Value::Object(options))?.as_bytes()));
+    input.extend_from_slice(&Sha256::digest(canonicalize(&document)?.as_bytes()));
+
+    Ok(input)
+  }
+}
+
+impl SignatureName for Ed25519Signature2020 {
+  fn name(&self) -> String {
+    SIGNATURE_NAME.to_string()
+  }
+}
+
+impl SignatureSign for Ed25519Signature2020 {
+  fn sign(&self, data: &dyn Serialize, secret: &[u8]) -> Result<SignatureValue> {
+    let signature: _ = ed25519_sign(&Self::signing_input(data)?, secret)?;
+    let signature: String = format!("{}{}", MULTIBASE_B58, encode_b58(&signature));
+
+    Ok(SignatureValue::Proof(signature))
+  }
+}
+
+impl SignatureVerify for Ed25519Signature2020 {
+  fn verify(&self, data: &dyn Serialize, signature: &SignatureValue, public: &[u8]) -> Result<()> {
+    let signature: &str = signature
+      .as_proof()
+      .and_then(|proof| proof.strip_prefix(MULTIBASE_B58))
+      .ok_or(Error::InvalidProofValue)?;
+
+    let signature: Vec<u8> = decode_b58(signature)?;
+
+    ed25519_verify(&Self::signing_input(data)?, &signature, public)?;
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::common::Value;
+  use crate::crypto::Ed25519Signature2020 as Ed25519;
+  use crate::crypto::KeyPair;
+  use crate::crypto::SignatureSign;
+  use crate::crypto::SignatureValue;
+  use crate::crypto::SignatureVerify;
+  use crate::error::Error;
+  use crate::json;
+
+  fn credential(subject: &str) -> Value {
+    json!({
+      "@context": [
+        "https://www.w3.org/2018/credentials/v1",
+        "https://w3id.org/security/suites/ed25519-2020/v1",
+      ],
+      "id": "http://example.edu/credentials/1872",
+      "type": ["VerifiableCredential"],
+      "issuer": "did:example:1234",
+      "issuanceDate": "2021-01-01T00:00:00Z",
+      "credentialSubject": {
+        "id": subject,
+      },
+      "proof": {
+        "type": "Ed25519Signature2020",
+        "verificationMethod": "did:example:1234#key-1",
+        "created": "2021-01-01T00:00:00Z",
+        "proofPurpose": "assertionMethod",
+      },
+    })
+  }
+
+  #[test]
+  fn test_sign_verify() {
+    let key1: KeyPair = KeyPair::new_ed25519().unwrap();
+    let key2: KeyPair = KeyPair::new_ed25519().unwrap();
+
+    let public1: &[u8] = key1.public().as_ref();
+    let secret1: &[u8] = key1.secret().as_ref();
+    let public2: &[u8] = key2.public().as_ref();
+
+    let data1: Value = credential("did:example:abcd");
+    let data2: Value = credential("did:example:efgh");
+
+    let signature: SignatureValue = Ed25519.sign(&data1, secret1).unwrap();
+
+    // The signature should be a multibase-encoded `proofValue`
+    assert!(signature.as_proof().unwrap().starts_with('z'));
+
+    // The signature should be valid
+    assert!(Ed25519.verify(&data1, &signature, public1).is_ok());
+
+    // Modified data should be invalid
+    assert!(Ed25519.verify(&data2, &signature, public1).is_err());
+
+    // A modified key should be invalid
+    assert!(Ed25519.verify(&data1, &signature, public2).is_err());
+
+    // A signature without the multibase prefix should be invalid
+    let signature: _ = SignatureValue::Proof(signature.as_str()[1..].to_string());
+    assert!(Ed25519.verify(&data1, &signature, public1).is_err());
+  }
+
+  #[test]
+  fn test_sign_modified_proof() {
+    let key: KeyPair = KeyPair::new_ed25519().unwrap();
+
+    let data1: Value = credential("did:example:abcd");
+    let mut data2: Value = data1.clone();
+
+    data2["proof"]["created"] = json!("2021-01-02T00:00:00Z");
+
+    let signature: SignatureValue = Ed25519.sign(&data1, key.secret().as_ref()).unwrap();
+
+    // The proof options are covered by the signature
+    assert!(Ed25519.verify(&data2, &signature, key.public().as_ref()).is_err());
+  }
+
+  #[test]
+  fn test_sign_invalid_linked_data() {
+    let key: KeyPair = KeyPair::new_ed25519().unwrap();
+
+    let mut data1: Value = credential("did:example:abcd");
+    let mut data2: Value = credential("did:example:abcd");
+
+    data1["@context"] = json!(["https://example.com/context/v1"]);
+    data2["credentialSubject"]["degree"] = json!("Bachelor of Science");
+
+    assert!(matches!(
+      Ed25519.sign(&data1, key.secret().as_ref()),
+      Err(Error::UnknownContext(_))
+    ));
+
+    assert!(matches!(
+      Ed25519.sign(&data2, key.secret().as_ref()),
+      Err(Error::UndefinedTerm(_))
+    ));
+  }
+}
@@ -4,6 +4,8 @@
 //! Types and traits for helping ensure the authenticity and integrity of
 //! DID Documents and Verifiable Credentials.
 
+mod ed25519;
 mod jcs_ed25519;
 
+pub use self::ed25519::Ed25519Signature2020;
 pub use self::jcs_ed25519::JcsEd25519Signature2020;
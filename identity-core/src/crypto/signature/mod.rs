@@ -4,10 +4,12 @@
 #![allow(clippy::module_inception)]
 
 mod signature;
+mod signature_options;
 mod signature_value;
 mod traits;
 
 pub use self::signature::Signature;
+pub use self::signature_options::SignatureOptions;
 pub use self::signature_value::SignatureValue;
 pub use self::traits::SetSignature;
 pub use self::traits::SignatureName;
@@ -10,6 +10,7 @@ use serde::ser::SerializeMap;
 use serde::ser::Serializer;
 use serde::Serialize;
 
+use crate::common::Timestamp;
 use crate::crypto::SignatureValue;
 use crate::error::Result;
 
@@ -22,6 +23,14 @@ pub struct Signature {
   value: SignatureValue,
   #[serde(rename = "verificationMethod")]
   method: String,
+  #[serde(default)]
+  created: Option<Timestamp>,
+  #[serde(default)]
+  domain: Option<String>,
+  #[serde(default)]
+  challenge: Option<String>,
+  #[serde(default, rename = "proofPurpose")]
+  purpose: Option<String>,
   #[serde(default, skip_deserializing)]
   hidden: Cell<bool>,
 }
@@ -33,6 +42,10 @@ impl Signature {
       type_: type_.into(),
       value: SignatureValue::None,
       method: method.into(),
+      created: None,
+      domain: None,
+      challenge: None,
+      purpose: None,
       hidden: Cell::new(false),
     }
   }
@@ -47,6 +60,46 @@ impl Signature {
     &*self.method
   }
 
+  /// Returns the timestamp of when the signature was created, if any.
+  pub const fn created(&self) -> Option<Timestamp> {
+    self.created
+  }
+
+  /// Sets the timestamp of when the signature was created.
+  pub fn set_created(&mut self, value: Timestamp) {
+    self.created = Some(value);
+  }
+
+  /// Returns the domain the signature is intended for, if any.
+  pub fn domain(&self) -> Option<&str> {
+    self.domain.as_deref()
+  }
+
+  /// Sets the domain the signature is intended for.
+  pub fn set_domain(&mut self, value: impl Into<String>) {
+    self.domain = Some(value.into());
+  }
+
+  /// Returns the challenge the signature was created for, if any.
+  pub fn challenge(&self) -> Option<&str> {
+    self.challenge.as_deref()
+  }
+
+  /// Sets the challenge the signature is created for.
+  pub fn set_challenge(&mut self, value: impl Into<String>) {
+    self.challenge = Some(value.into());
+  }
+
+  /// Returns the purpose of the signature (e.g. `authentication`), if any.
+  pub fn purpose(&self) -> Option<&str> {
+    self.purpose.as_deref()
+  }
+
+  /// Sets the purpose of the signature.
+  pub fn set_purpose(&mut self, value: impl Into<String>) {
+    self.purpose = Some(value.into());
+  }
+
   /// Returns a reference to the signature `value`.
   pub const fn value(&self) -> &SignatureValue {
     &self.value
@@ -88,6 +141,10 @@ impl Debug for Signature {
       .field("type_", &self.type_)
       .field("value", &self.value)
       .field("method", &self.method)
+      .field("created", &self.created)
+      .field("domain", &self.domain)
+      .field("challenge", &self.challenge)
+      .field("purpose", &self.purpose)
       .finish()
   }
 }
@@ -100,14 +157,30 @@ impl Serialize for Signature {
     let hide: bool = self.__hide();
 
     let mut state: S::SerializeMap = if hide {
-      serializer.serialize_map(Some(1 + 5))?
+      serializer.serialize_map(Some(1 + 8))?
     } else {
-      serializer.serialize_map(Some(2 + 5))?
+      serializer.serialize_map(Some(2 + 8))?
     };
 
     state.serialize_entry("type", &self.type_)?;
     state.serialize_entry("verificationMethod", &self.method)?;
 
+    if let Some(created) = self.created {
+      state.serialize_entry("created", &created)?;
+    }
+
+    if let Some(domain) = self.domain.as_deref() {
+      state.serialize_entry("domain", domain)?;
+    }
+
+    if let Some(challenge) = self.challenge.as_deref() {
+      state.serialize_entry("challenge", challenge)?;
+    }
+
+    if let Some(purpose) = self.purpose.as_deref() {
+      state.serialize_entry("proofPurpose", purpose)?;
+    }
+
     if !hide {
       Serialize::serialize(&self.value, FlatMapSerializer(&mut state))?;
     }
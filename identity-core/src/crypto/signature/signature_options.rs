@@ -0,0 +1,34 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::Timestamp;
+
+/// Optional properties written into a [`Signature`][crate::crypto::Signature]
+/// when it is created.
+///
+/// All properties are covered by the signature; `domain` and `challenge` can
+/// be used to bind a proof to a particular authentication request.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SignatureOptions {
+  /// The domain the proof is intended for.
+  pub domain: Option<String>,
+  /// A challenge provided by the verifier to prevent replay attacks.
+  pub challenge: Option<String>,
+  /// The purpose of the proof, e.g. `authentication` or `assertionMethod`.
+  pub purpose: Option<String>,
+  /// The creation time of the proof; omitted unless set so that signing
+  /// stays deterministic.
+  pub created: Option<Timestamp>,
+}
+
+impl SignatureOptions {
+  /// Creates a new `SignatureOptions` without any properties.
+  pub const fn new() -> Self {
+    Self {
+      domain: None,
+      challenge: None,
+      purpose: None,
+      created: None,
+    }
+  }
+}
@@ -4,7 +4,9 @@
 use erased_serde::serialize_trait_object;
 use erased_serde::Serialize;
 
+use crate::convert::ToJson;
 use crate::crypto::Signature;
+use crate::crypto::SignatureOptions;
 use crate::crypto::SignatureValue;
 use crate::error::Error;
 use crate::error::Result;
@@ -34,7 +36,34 @@ pub trait SignatureSign: SignatureName {
 
   #[doc(hidden)]
   fn __sign(&self, data: &mut dyn __TargetSign, method: String, secret: &[u8]) -> Result<()> {
-    let signature: Signature = Signature::new(self.name(), method);
+    self.__sign_with(data, method, secret, &SignatureOptions::new())
+  }
+
+  #[doc(hidden)]
+  fn __sign_with(
+    &self,
+    data: &mut dyn __TargetSign,
+    method: String,
+    secret: &[u8],
+    options: &SignatureOptions,
+  ) -> Result<()> {
+    let mut signature: Signature = Signature::new(self.name(), method);
+
+    if let Some(created) = options.created {
+      signature.set_created(created);
+    }
+
+    if let Some(domain) = options.domain.as_deref() {
+      signature.set_domain(domain);
+    }
+
+    if let Some(challenge) = options.challenge.as_deref() {
+      signature.set_challenge(challenge);
+    }
+
+    if let Some(purpose) = options.purpose.as_deref() {
+      signature.set_purpose(purpose);
+    }
 
     data.set_signature(signature);
 
@@ -135,6 +164,27 @@ pub trait TrySignature {
   fn try_signature(&self) -> Result<&Signature> {
     self.signature().ok_or(Error::MissingSignature)
   }
+
+  /// Returns the exact bytes covered by the [`Signature`] - `self` serialized
+  /// using JSON Canonicalization Scheme (JCS) with the signature value omitted.
+  ///
+  /// Errors
+  ///
+  /// Fails if the signature is not found.
+  fn signing_input(&self) -> Result<Vec<u8>>
+  where
+    Self: serde::Serialize + Sized,
+  {
+    let signature: &Signature = self.try_signature()?;
+
+    signature.hide_value();
+
+    let input: Result<Vec<u8>> = self.to_jcs();
+
+    signature.show_value();
+
+    input
+  }
 }
 
 impl<'a, T> TrySignature for &'a T
@@ -0,0 +1,75 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::hashes::sha::Sha256;
+
+use crate::convert::FromJson;
+use crate::crypto::ed25519_sign;
+use crate::crypto::ed25519_verify;
+use crate::crypto::merkle_tree::Hash;
+use crate::crypto::merkle_tree::Proof;
+use crate::utils::decode_b16;
+
+const ED25519_RFC8032: &str = include_str!("../../tests/fixtures/ed25519_rfc8032.json");
+const MERKLE_SHA256: &str = include_str!("../../tests/fixtures/merkle_sha256.json");
+
+/// An Ed25519 test vector from RFC 8032, section 7.1.
+#[derive(Deserialize)]
+struct Ed25519Vector {
+  secret: String,
+  public: String,
+  message: String,
+  signature: String,
+}
+
+/// A Merkle tree inclusion proof test vector using SHA-256.
+#[derive(Deserialize)]
+struct MerkleVector {
+  leaf: String,
+  index: usize,
+  root: String,
+  proof: String,
+}
+
+fn load<T>(json: &str) -> Vec<T>
+where
+  T: FromJson,
+{
+  Vec::<T>::from_json(json).unwrap()
+}
+
+#[test]
+fn test_ed25519_rfc8032() {
+  for tv in load::<Ed25519Vector>(ED25519_RFC8032) {
+    let secret: Vec<u8> = decode_b16(&tv.secret).unwrap();
+    let public: Vec<u8> = decode_b16(&tv.public).unwrap();
+    let message: Vec<u8> = decode_b16(&tv.message).unwrap();
+    let signature: Vec<u8> = decode_b16(&tv.signature).unwrap();
+
+    assert_eq!(&ed25519_sign(&message, &secret).unwrap()[..], &signature[..]);
+    assert!(ed25519_verify(&message, &signature, &public).is_ok());
+
+    // Fails when the message is mutated
+    let mutated: Vec<u8> = [&message[..], b"IOTA"].concat();
+    assert!(ed25519_verify(&mutated, &signature, &public).is_err());
+  }
+}
+
+#[test]
+fn test_merkle_sha256() {
+  for tv in load::<MerkleVector>(MERKLE_SHA256) {
+    let leaf: Vec<u8> = decode_b16(&tv.leaf).unwrap();
+    let root: Vec<u8> = decode_b16(&tv.root).unwrap();
+    let data: Vec<u8> = decode_b16(&tv.proof).unwrap();
+
+    let root: Hash<Sha256> = Hash::from_slice(&root).unwrap();
+    let proof: Proof<Sha256> = Proof::decode(&data).unwrap();
+
+    assert_eq!(proof.index(), tv.index);
+    assert_eq!(proof.encode(), data);
+    assert!(proof.verify(&root, &leaf));
+
+    // Fails when the leaf is mutated
+    assert!(!proof.verify(&root, b"IOTA"));
+  }
+}
@@ -6,6 +6,11 @@
 /// Alias for a `Result` with the error type [`Error`].
 pub type Result<T, E = Error> = ::core::result::Result<T, E>;
 
+use serde::ser::SerializeStruct;
+use serde::Serialize;
+use serde::Serializer;
+
+use crate::common::Object;
 use crate::crypto::merkle_key::MerkleTag;
 
 /// This type represents all possible errors that can occur in the library.
@@ -20,6 +25,18 @@ pub enum Error {
   /// Caused by a failure to decode Rust types from JSON.
   #[error("Failed to decode JSON: {0}")]
   DecodeJSON(serde_json::Error),
+  /// Caused by a failure to decode Rust types from JSON text.
+  #[error("Failed to decode JSON: {error} near `{snippet}`")]
+  DecodeJSONText {
+    /// The underlying decoding error, including the line and column.
+    error: serde_json::Error,
+    /// The text surrounding the position of the error, with the contents of
+    /// string literals redacted.
+    snippet: String,
+  },
+  /// Caused by a failure to decode Rust types from CBOR.
+  #[error("Failed to decode CBOR: {0}")]
+  DecodeCBOR(serde_cbor::Error),
   /// Caused by a failure to decode base16-encoded data.
   #[error("Failed to decode base16 data: {0}")]
   DecodeBase16(#[from] hex::FromHexError),
@@ -35,6 +52,12 @@ pub enum Error {
   /// Cause by a failure to decode a Roaring Bitmap.
   #[error("Failed to decode roaring bitmap: {0}")]
   DecodeBitmap(std::io::Error),
+  /// Caused by a failure to read a length-prefixed payload.
+  #[error("Failed to decode framed data: {0}")]
+  DecodeFrame(std::io::Error),
+  /// Caused by a payload too large to frame or a truncated frame.
+  #[error("Invalid Frame Length: {0}")]
+  InvalidFrameLength(usize),
   /// Caused by attempting to perform an invalid `Diff` operation.
   #[error("Invalid Document Diff: {0}")]
   InvalidDiff(#[from] identity_diff::Error),
@@ -68,6 +91,21 @@ pub enum Error {
   /// Caused by attempting to create a KeyCollection of invalid size.
   #[error("Invalid Key Collection Size: {0}")]
   InvalidKeyCollectionSize(usize),
+  /// Caused by attempting to decode data of an unknown encoding.
+  #[error("Unknown Encoding")]
+  UnknownEncoding,
+  /// Caused by attempting to canonicalize an invalid or unsupported JSON-LD
+  /// document.
+  #[error("Invalid Linked Data: {0}")]
+  InvalidLinkedData(&'static str),
+  /// Caused by a JSON-LD document referencing a context which is not
+  /// available.
+  #[error("Unknown JSON-LD Context: {0}")]
+  UnknownContext(String),
+  /// Caused by a JSON-LD document containing a term not defined by its
+  /// context.
+  #[error("Undefined JSON-LD Term: {0}")]
+  UndefinedTerm(String),
 }
 
 impl From<crypto::Error> for Error {
@@ -75,3 +113,110 @@ impl From<crypto::Error> for Error {
     Self::Crypto(other)
   }
 }
+
+impl Error {
+  /// Returns the name of the error variant.
+  pub const fn kind(&self) -> &'static str {
+    match self {
+      Self::Crypto(_) => "Crypto",
+      Self::EncodeJSON(_) => "EncodeJSON",
+      Self::DecodeJSON(_) => "DecodeJSON",
+      Self::DecodeJSONText { .. } => "DecodeJSON",
+      Self::DecodeCBOR(_) => "DecodeCBOR",
+      Self::DecodeBase16(_) => "DecodeBase16",
+      Self::DecodeBase58(_) => "DecodeBase58",
+      Self::DecodeBase64(_) => "DecodeBase64",
+      Self::EncodeBitmap(_) => "EncodeBitmap",
+      Self::DecodeBitmap(_) => "DecodeBitmap",
+      Self::DecodeFrame(_) => "DecodeFrame",
+      Self::InvalidFrameLength(_) => "InvalidFrameLength",
+      Self::InvalidDiff(_) => "InvalidDiff",
+      Self::InvalidUrl(_) => "InvalidUrl",
+      Self::InvalidTimestamp(_) => "InvalidTimestamp",
+      Self::InvalidProofValue => "InvalidProofValue",
+      Self::InvalidProofFormat => "InvalidProofFormat",
+      Self::InvalidKeyFormat => "InvalidKeyFormat",
+      Self::InvalidKeyLength(_, _) => "InvalidKeyLength",
+      Self::InvalidSigLength(_, _) => "InvalidSigLength",
+      Self::InvalidMerkleKeyTag(_) => "InvalidMerkleKeyTag",
+      Self::MissingSignature => "MissingSignature",
+      Self::InvalidKeyCollectionSize(_) => "InvalidKeyCollectionSize",
+      Self::UnknownEncoding => "UnknownEncoding",
+      Self::InvalidLinkedData(_) => "InvalidLinkedData",
+      Self::UnknownContext(_) => "UnknownContext",
+      Self::UndefinedTerm(_) => "UndefinedTerm",
+    }
+  }
+
+  /// Returns structured details of the error, if any.
+  ///
+  /// Details never contain key material or other secret data.
+  pub fn details(&self) -> Option<Object> {
+    let mut details: Object = Object::new();
+
+    match self {
+      Self::InvalidKeyLength(received, expected) | Self::InvalidSigLength(received, expected) => {
+        details.insert("received".into(), (*received).into());
+        details.insert("expected".into(), (*expected).into());
+      }
+      Self::InvalidKeyCollectionSize(size) => {
+        details.insert("size".into(), (*size).into());
+      }
+      Self::InvalidFrameLength(length) => {
+        details.insert("length".into(), (*length).into());
+      }
+      Self::DecodeJSONText { error, snippet } => {
+        details.insert("line".into(), error.line().into());
+        details.insert("column".into(), error.column().into());
+        details.insert("snippet".into(), snippet.as_str().into());
+      }
+      Self::UnknownContext(url) => {
+        details.insert("url".into(), url.as_str().into());
+      }
+      Self::UndefinedTerm(term) => {
+        details.insert("term".into(), term.as_str().into());
+      }
+      _ => return None,
+    }
+
+    Some(details)
+  }
+}
+
+/// Serializes the error as an object of the form `{ kind, message, details }`.
+impl Serialize for Error {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut state: S::SerializeStruct = serializer.serialize_struct("Error", 3)?;
+
+    state.serialize_field("kind", self.kind())?;
+    state.serialize_field("message", &self.to_string())?;
+    state.serialize_field("details", &self.details())?;
+    state.end()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::convert::ToJson;
+
+  #[test]
+  fn test_serialize() {
+    let error: Error = Error::InvalidKeyLength(31, 32);
+
+    assert_eq!(
+      error.to_json().unwrap(),
+      r#"{"kind":"InvalidKeyLength","message":"Invalid Key Length. Received 31, Expected 32","details":{"expected":32,"received":31}}"#
+    );
+
+    let error: Error = Error::MissingSignature;
+
+    assert_eq!(
+      error.to_json().unwrap(),
+      r#"{"kind":"MissingSignature","message":"Signature Not Found","details":null}"#
+    );
+  }
+}
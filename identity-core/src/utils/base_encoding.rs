@@ -54,3 +54,48 @@ where
 {
   base64::encode_config(data.as_ref(), base64::URL_SAFE)
 }
+
+/// Encodes the given `data` as unpadded, lowercase base32 (RFC 4648).
+pub fn encode_b32<T>(data: &T) -> String
+where
+  T: AsRef<[u8]> + ?Sized,
+{
+  const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+  let data: &[u8] = data.as_ref();
+  let mut output: String = String::with_capacity((data.len() * 8 + 4) / 5);
+  let mut buffer: u32 = 0;
+  let mut bits: u32 = 0;
+
+  for byte in data {
+    buffer = (buffer << 8) | u32::from(*byte);
+    bits += 8;
+
+    while bits >= 5 {
+      bits -= 5;
+      output.push(ALPHABET[((buffer >> bits) & 0x1F) as usize] as char);
+    }
+  }
+
+  if bits > 0 {
+    output.push(ALPHABET[((buffer << (5 - bits)) & 0x1F) as usize] as char);
+  }
+
+  output
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_encode_b32() {
+    assert_eq!(encode_b32(b""), "");
+    assert_eq!(encode_b32(b"f"), "my");
+    assert_eq!(encode_b32(b"fo"), "mzxq");
+    assert_eq!(encode_b32(b"foo"), "mzxw6");
+    assert_eq!(encode_b32(b"foob"), "mzxw6yq");
+    assert_eq!(encode_b32(b"fooba"), "mzxw6ytb");
+    assert_eq!(encode_b32(b"foobar"), "mzxw6ytboi");
+  }
+}
@@ -0,0 +1,559 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_core::common::Value;
+use identity_core::convert::ToJson;
+use identity_core::utils::decode_b16;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use tiny_keccak::Hasher;
+use tiny_keccak::Keccak;
+
+use crate::credential::Credential;
+use crate::error::Error;
+use crate::error::Result;
+
+const PRIMARY_TYPE: &str = "VerifiableCredential";
+const SUBJECT_TYPE: &str = "CredentialSubject";
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+type Types = BTreeMap<String, Vec<Member>>;
+
+/// The EIP-712 domain used to compute the digest of a [`Credential`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Eip712Domain {
+  name: String,
+  version: String,
+  chain_id: Option<u64>,
+  verifying_contract: Option<String>,
+}
+
+impl Eip712Domain {
+  /// Creates a new `Eip712Domain` with the given `name` and `version`.
+  pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+    Self {
+      name: name.into(),
+      version: version.into(),
+      chain_id: None,
+      verifying_contract: None,
+    }
+  }
+
+  /// Sets the EIP-155 chain id of the domain.
+  #[must_use]
+  pub fn chain_id(mut self, value: u64) -> Self {
+    self.chain_id = Some(value);
+    self
+  }
+
+  /// Sets the hex-encoded address of the contract verifying the signature.
+  #[must_use]
+  pub fn verifying_contract(mut self, value: impl Into<String>) -> Self {
+    self.verifying_contract = Some(value.into());
+    self
+  }
+
+  fn members(&self) -> Vec<Member> {
+    let mut members: Vec<Member> = vec![Member::new("name", "string"), Member::new("version", "string")];
+
+    if self.chain_id.is_some() {
+      members.push(Member::new("chainId", "uint256"));
+    }
+
+    if self.verifying_contract.is_some() {
+      members.push(Member::new("verifyingContract", "address"));
+    }
+
+    members
+  }
+
+  fn to_value(&self) -> Value {
+    let mut object: Object = Object::new();
+
+    object.insert("name".into(), self.name.clone().into());
+    object.insert("version".into(), self.version.clone().into());
+
+    if let Some(chain_id) = self.chain_id {
+      object.insert("chainId".into(), chain_id.into());
+    }
+
+    if let Some(ref verifying_contract) = self.verifying_contract {
+      object.insert("verifyingContract".into(), verifying_contract.clone().into());
+    }
+
+    Value::Object(object.into_iter().collect())
+  }
+
+  fn separator(&self) -> Result<[u8; 32]> {
+    let mut types: Types = Types::new();
+
+    types.insert(DOMAIN_TYPE.into(), self.members());
+
+    hash_struct(DOMAIN_TYPE, &self.to_value(), &types)
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct Member {
+  name: String,
+  #[serde(rename = "type")]
+  type_: String,
+}
+
+impl Member {
+  fn new(name: impl Into<String>, type_: impl Into<String>) -> Self {
+    Self {
+      name: name.into(),
+      type_: type_.into(),
+    }
+  }
+}
+
+impl<T> Credential<T>
+where
+  T: Serialize,
+{
+  /// Returns the `Credential` as EIP-712 typed data with the given `domain`.
+  ///
+  /// Credentials are mapped to a fixed schema:
+  ///
+  /// ```text
+  /// VerifiableCredential(string[] context,string id,string[] type,string issuer,string issuanceDate,
+  ///   string expirationDate,CredentialSubject[] credentialSubject,string properties)
+  /// CredentialSubject(string id,string claims)
+  /// ```
+  ///
+  /// Absent optional values are encoded as empty strings. Structured values
+  /// without a dedicated member - `@context` entries and issuers given as
+  /// objects, the claims of each subject, and all remaining properties of
+  /// the `Credential` - are encoded as JCS-canonicalized JSON strings. Any
+  /// `proof` property is excluded.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the `Credential` cannot be serialized.
+  pub fn to_eip712_typed_data(&self, domain: &Eip712Domain) -> Result<Value> {
+    let message: Value = self.eip712_message()?;
+
+    let mut data: Object = Object::new();
+    let mut all: Object = Object::new();
+
+    all.insert(DOMAIN_TYPE.into(), domain.members().to_json_value()?);
+
+    for (name, members) in credential_types() {
+      all.insert(name, members.to_json_value()?);
+    }
+
+    data.insert("types".into(), Value::Object(all.into_iter().collect()));
+    data.insert("primaryType".into(), PRIMARY_TYPE.into());
+    data.insert("domain".into(), domain.to_value());
+    data.insert("message".into(), message);
+
+    Ok(Value::Object(data.into_iter().collect()))
+  }
+
+  /// Computes the EIP-712 digest of the `Credential` with the given `domain`.
+  ///
+  /// This is the data signed by an `EthereumEip712Signature2021` proof, see
+  /// [`Credential::to_eip712_typed_data`] for the schema.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the `Credential` cannot be represented as EIP-712 typed data.
+  pub fn to_eip712_hash(&self, domain: &Eip712Domain) -> Result<[u8; 32]> {
+    let message: Value = self.eip712_message()?;
+    let types: Types = credential_types();
+
+    let mut data: Vec<u8> = Vec::with_capacity(66);
+
+    data.extend_from_slice(b"\x19\x01");
+    data.extend_from_slice(&domain.separator()?);
+    data.extend_from_slice(&hash_struct(PRIMARY_TYPE, &message, &types)?);
+
+    Ok(keccak256(&data))
+  }
+
+  fn eip712_message(&self) -> Result<Value> {
+    let mut object: Object = match self.to_json_value()? {
+      Value::Object(object) => object.into_iter().collect(),
+      _ => return Err(Error::InvalidEip712Data("Expected Object")),
+    };
+
+    object.remove("proof");
+
+    let subjects: Vec<Value> = match object.remove("credentialSubject") {
+      Some(Value::Array(subjects)) => subjects,
+      Some(subject) => vec![subject],
+      None => Vec::new(),
+    };
+
+    let mut message: Object = Object::new();
+
+    message.insert("context".into(), encode_strings(object.remove("@context"))?);
+    message.insert("id".into(), encode_string(object.remove("id"))?);
+    message.insert("type".into(), encode_strings(object.remove("type"))?);
+    message.insert("issuer".into(), encode_string(object.remove("issuer"))?);
+    message.insert("issuanceDate".into(), encode_string(object.remove("issuanceDate"))?);
+    message.insert("expirationDate".into(), encode_string(object.remove("expirationDate"))?);
+    message.insert(
+      "credentialSubject".into(),
+      Value::Array(subjects.into_iter().map(encode_subject).collect::<Result<_>>()?),
+    );
+    message.insert("properties".into(), encode_json(object)?);
+
+    Ok(Value::Object(message.into_iter().collect()))
+  }
+}
+
+fn credential_types() -> Types {
+  let mut types: Types = Types::new();
+
+  types.insert(
+    PRIMARY_TYPE.into(),
+    vec![
+      Member::new("context", "string[]"),
+      Member::new("id", "string"),
+      Member::new("type", "string[]"),
+      Member::new("issuer", "string"),
+      Member::new("issuanceDate", "string"),
+      Member::new("expirationDate", "string"),
+      Member::new("credentialSubject", format!("{}[]", SUBJECT_TYPE)),
+      Member::new("properties", "string"),
+    ],
+  );
+
+  types.insert(
+    SUBJECT_TYPE.into(),
+    vec![Member::new("id", "string"), Member::new("claims", "string")],
+  );
+
+  types
+}
+
+fn encode_subject(subject: Value) -> Result<Value> {
+  let mut subject: Object = match subject {
+    Value::Object(object) => object.into_iter().collect(),
+    _ => return Err(Error::InvalidEip712Data("Expected Object")),
+  };
+
+  let mut object: Object = Object::new();
+
+  object.insert("id".into(), encode_string(subject.remove("id"))?);
+  object.insert("claims".into(), encode_json(subject)?);
+
+  Ok(Value::Object(object.into_iter().collect()))
+}
+
+// Encodes a value as `string[]`; non-string entries are JCS-encoded.
+fn encode_strings(value: Option<Value>) -> Result<Value> {
+  let values: Vec<Value> = match value {
+    Some(Value::Array(values)) => values,
+    Some(value) => vec![value],
+    None => Vec::new(),
+  };
+
+  values
+    .into_iter()
+    .map(|value| encode_string(Some(value)))
+    .collect::<Result<_>>()
+    .map(Value::Array)
+}
+
+// Encodes a value as `string`; non-string values are JCS-encoded.
+fn encode_string(value: Option<Value>) -> Result<Value> {
+  match value {
+    Some(Value::String(string)) => Ok(Value::String(string)),
+    Some(value) => encode_json(value),
+    None => Ok(Value::String(String::new())),
+  }
+}
+
+fn encode_json(value: impl ToJson) -> Result<Value> {
+  String::from_utf8(value.to_jcs()?)
+    .map(Value::String)
+    .map_err(|_| Error::InvalidEip712Data("Invalid JCS"))
+}
+
+fn encode_type(name: &str, types: &Types) -> Result<String> {
+  let mut dependencies: BTreeSet<&str> = BTreeSet::new();
+
+  collect_dependencies(name, types, &mut dependencies)?;
+  dependencies.remove(name);
+
+  let mut output: String = String::new();
+
+  for type_ in Some(name).into_iter().chain(dependencies) {
+    let members: Vec<String> = types[type_]
+      .iter()
+      .map(|member| format!("{} {}", member.type_, member.name))
+      .collect();
+
+    output.push_str(type_);
+    output.push('(');
+    output.push_str(&members.join(","));
+    output.push(')');
+  }
+
+  Ok(output)
+}
+
+fn collect_dependencies<'a>(name: &'a str, types: &'a Types, output: &mut BTreeSet<&'a str>) -> Result<()> {
+  if !output.insert(name) {
+    return Ok(());
+  }
+
+  let members: &Vec<Member> = types.get(name).ok_or(Error::InvalidEip712Data("Unknown Type"))?;
+
+  for member in members {
+    let type_: &str = member.type_.trim_end_matches("[]");
+
+    if types.contains_key(type_) {
+      collect_dependencies(type_, types, output)?;
+    }
+  }
+
+  Ok(())
+}
+
+fn hash_struct(name: &str, value: &Value, types: &Types) -> Result<[u8; 32]> {
+  let object = value.as_object().ok_or(Error::InvalidEip712Data("Expected Object"))?;
+  let members: &Vec<Member> = types.get(name).ok_or(Error::InvalidEip712Data("Unknown Type"))?;
+
+  let mut data: Vec<u8> = Vec::with_capacity(32 * (members.len() + 1));
+
+  data.extend_from_slice(&keccak256(encode_type(name, types)?.as_bytes()));
+
+  for member in members {
+    let value: &Value = object
+      .get(&member.name)
+      .ok_or(Error::InvalidEip712Data("Missing Value"))?;
+
+    data.extend_from_slice(&encode_value(&member.type_, value, types)?);
+  }
+
+  Ok(keccak256(&data))
+}
+
+fn encode_value(type_: &str, value: &Value, types: &Types) -> Result<[u8; 32]> {
+  if let Some(inner) = type_.strip_suffix("[]") {
+    let array: &Vec<Value> = value.as_array().ok_or(Error::InvalidEip712Data("Expected Array"))?;
+    let mut data: Vec<u8> = Vec::with_capacity(32 * array.len());
+
+    for value in array {
+      data.extend_from_slice(&encode_value(inner, value, types)?);
+    }
+
+    return Ok(keccak256(&data));
+  }
+
+  match (type_, value) {
+    ("string", Value::String(string)) => Ok(keccak256(string.as_bytes())),
+    ("address", Value::String(string)) => encode_address(string),
+    ("bool", Value::Bool(value)) => Ok(encode_word(*value as u64, false)),
+    ("uint256", Value::Number(number)) => number
+      .as_u64()
+      .map(|number| encode_word(number, false))
+      .ok_or(Error::InvalidEip712Data("Expected Unsigned Integer")),
+    ("int256", Value::Number(number)) => number
+      .as_i64()
+      .map(|number| encode_word(number as u64, number < 0))
+      .ok_or(Error::InvalidEip712Data("Expected Integer")),
+    (_, Value::Object(_)) => hash_struct(type_, value, types),
+    (_, _) => Err(Error::InvalidEip712Data("Type Mismatch")),
+  }
+}
+
+fn encode_address(address: &str) -> Result<[u8; 32]> {
+  let bytes: Vec<u8> = decode_b16(address.strip_prefix("0x").unwrap_or(address))
+    .map_err(|_| Error::InvalidEip712Data("Invalid Address"))?;
+
+  if bytes.len() != 20 {
+    return Err(Error::InvalidEip712Data("Invalid Address"));
+  }
+
+  let mut word: [u8; 32] = [0; 32];
+
+  word[12..].copy_from_slice(&bytes);
+
+  Ok(word)
+}
+
+fn encode_word(value: u64, negative: bool) -> [u8; 32] {
+  // Negative integers are sign-extended two's complement
+  let mut word: [u8; 32] = if negative { [0xff; 32] } else { [0; 32] };
+
+  word[24..].copy_from_slice(&value.to_be_bytes());
+  word
+}
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+  let mut hasher: Keccak = Keccak::v256();
+  let mut output: [u8; 32] = [0; 32];
+
+  hasher.update(data);
+  hasher.finalize(&mut output);
+
+  output
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Url;
+  use identity_core::convert::FromJson;
+  use identity_core::json;
+  use identity_core::utils::encode_b16;
+
+  use super::*;
+
+  const JSON: &str = include_str!("../../tests/fixtures/credential-1.json");
+
+  fn domain() -> Eip712Domain {
+    Eip712Domain::new("Verifiable Credential", "1").chain_id(1)
+  }
+
+  #[test]
+  fn test_keccak256() {
+    assert_eq!(
+      encode_b16(&keccak256(b"")),
+      "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+  }
+
+  #[test]
+  fn test_eip712_mail() {
+    // The `Mail` example of the EIP-712 specification
+    let domain: Eip712Domain = Eip712Domain::new("Ether Mail", "1")
+      .chain_id(1)
+      .verifying_contract("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC");
+
+    let mut types: Types = Types::new();
+
+    types.insert(
+      "Person".into(),
+      vec![Member::new("name", "string"), Member::new("wallet", "address")],
+    );
+
+    types.insert(
+      "Mail".into(),
+      vec![
+        Member::new("from", "Person"),
+        Member::new("to", "Person"),
+        Member::new("contents", "string"),
+      ],
+    );
+
+    let message: Value = json!({
+      "from": {
+        "name": "Cow",
+        "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826",
+      },
+      "to": {
+        "name": "Bob",
+        "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB",
+      },
+      "contents": "Hello, Bob!",
+    });
+
+    assert_eq!(
+      encode_type("Mail", &types).unwrap(),
+      "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+    );
+    assert_eq!(
+      encode_b16(&keccak256(encode_type("Mail", &types).unwrap().as_bytes())),
+      "a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2"
+    );
+    assert_eq!(
+      encode_b16(&hash_struct("Mail", &message, &types).unwrap()),
+      "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+    );
+    assert_eq!(
+      encode_b16(&domain.separator().unwrap()),
+      "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+    );
+
+    let mut data: Vec<u8> = Vec::new();
+
+    data.extend_from_slice(b"\x19\x01");
+    data.extend_from_slice(&domain.separator().unwrap());
+    data.extend_from_slice(&hash_struct("Mail", &message, &types).unwrap());
+
+    assert_eq!(
+      encode_b16(&keccak256(&data)),
+      "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+    );
+  }
+
+  #[test]
+  fn test_encode_type() {
+    assert_eq!(
+      encode_type(PRIMARY_TYPE, &credential_types()).unwrap(),
+      "VerifiableCredential(string[] context,string id,string[] type,string issuer,string issuanceDate,string \
+       expirationDate,CredentialSubject[] credentialSubject,string properties)CredentialSubject(string id,string \
+       claims)"
+    );
+  }
+
+  #[test]
+  fn test_eip712_message() {
+    let credential: Credential = Credential::from_json(JSON).unwrap();
+    let typed: Value = credential.to_eip712_typed_data(&domain()).unwrap();
+
+    assert_eq!(
+      typed["message"],
+      json!({
+        "context": [
+          "https://www.w3.org/2018/credentials/v1",
+          "https://www.w3.org/2018/credentials/examples/v1",
+        ],
+        "id": "http://example.edu/credentials/58473",
+        "type": ["VerifiableCredential", "AlumniCredential"],
+        "issuer": "https://example.edu/issuers/14",
+        "issuanceDate": "2010-01-01T19:23:24Z",
+        "expirationDate": "",
+        "credentialSubject": [
+          {
+            "id": "did:example:ebfeb1f712ebc6f1c276e12ec21",
+            "claims": "{\"alumniOf\":\"Example University\"}",
+          },
+        ],
+        "properties": "{}",
+      })
+    );
+  }
+
+  #[test]
+  fn test_eip712_hash() {
+    let credential: Credential = Credential::from_json(JSON).unwrap();
+    let digest: [u8; 32] = credential.to_eip712_hash(&domain()).unwrap();
+
+    assert_eq!(credential.to_eip712_hash(&domain()).unwrap(), digest);
+    assert_ne!(credential.to_eip712_hash(&domain().chain_id(2)).unwrap(), digest);
+
+    let mut other: Credential = credential.clone();
+
+    other.issuer = Url::parse("https://example.edu/issuers/15").unwrap().into();
+
+    assert_ne!(other.to_eip712_hash(&domain()).unwrap(), digest);
+  }
+
+  #[test]
+  fn test_eip712_properties() {
+    let credential: Credential = Credential::from_json(JSON).unwrap();
+    let digest: [u8; 32] = credential.to_eip712_hash(&domain()).unwrap();
+
+    // Properties without a dedicated member are covered by the digest
+    let mut other: Credential = credential.clone();
+
+    other.properties.insert("score".into(), 1.5.into());
+
+    assert_ne!(other.to_eip712_hash(&domain()).unwrap(), digest);
+
+    let mut other: Credential = credential.clone();
+
+    other.non_transferable = Some(true);
+
+    assert_ne!(other.to_eip712_hash(&domain()).unwrap(), digest);
+  }
+}
@@ -7,10 +7,13 @@
 
 mod builder;
 mod credential;
+#[cfg(feature = "eip712")]
+mod eip712;
 mod evidence;
 mod issuer;
 mod policy;
 mod refresh;
+mod revocation;
 mod schema;
 mod status;
 mod subject;
@@ -18,10 +21,13 @@ mod verifiable;
 
 pub use self::builder::CredentialBuilder;
 pub use self::credential::Credential;
+#[cfg(feature = "eip712")]
+pub use self::eip712::Eip712Domain;
 pub use self::evidence::Evidence;
 pub use self::issuer::Issuer;
 pub use self::policy::Policy;
 pub use self::refresh::Refresh;
+pub use self::revocation::RevocationList;
 pub use self::schema::Schema;
 pub use self::status::Status;
 pub use self::subject::Subject;
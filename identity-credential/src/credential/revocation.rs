@@ -0,0 +1,370 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use identity_core::common::Url;
+use identity_core::common::Value;
+use identity_core::crypto::SecretKey;
+use identity_core::utils::decode_b64;
+use identity_core::utils::encode_b64;
+use identity_did::document::Document;
+use identity_did::verification::MethodQuery;
+use serde::Serialize;
+use std::io::Read;
+use std::io::Write;
+
+use crate::credential::Credential;
+use crate::credential::Status;
+use crate::credential::Subject;
+use crate::credential::VerifiableCredential;
+use crate::error::Error;
+use crate::error::Result;
+
+/// A bitstring of the revocation status of `Credential`s, as published in a
+/// `RevocationList2020Credential`.
+///
+/// [More Info](https://w3c-ccg.github.io/vc-status-rl-2020/)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevocationList(Vec<u8>);
+
+impl RevocationList {
+  /// The `type` of a `credentialStatus` referencing a revocation list.
+  pub const STATUS_TYPE: &'static str = "RevocationList2020Status";
+
+  /// The `type` of a `Credential` publishing a revocation list.
+  pub const CREDENTIAL_TYPE: &'static str = "RevocationList2020Credential";
+
+  /// The `type` of the subject of a revocation list `Credential`.
+  pub const SUBJECT_TYPE: &'static str = "RevocationList2020";
+
+  /// The minimum number of entries of a revocation list (16KB), which provides
+  /// group privacy for the holders of the listed `Credential`s.
+  pub const MIN_LENGTH: usize = 131_072;
+
+  /// The maximum size (in bytes) of a decompressed revocation list (1MB),
+  /// which bounds the memory used to decode untrusted lists.
+  pub const MAX_LIST_BYTES: usize = 1_048_576;
+
+  /// Creates a new `RevocationList` of [`MIN_LENGTH`][Self::MIN_LENGTH] entries
+  /// without any revoked `Credential`s.
+  pub fn new() -> Self {
+    Self(vec![0; Self::MIN_LENGTH / 8])
+  }
+
+  /// Decodes a `RevocationList` from a GZIP-compressed, base64url-encoded
+  /// bitstring.
+  ///
+  /// Lists larger than [`MAX_LIST_BYTES`][Self::MAX_LIST_BYTES] once
+  /// decompressed are rejected.
+  pub fn decode(encoded: &str) -> Result<Self> {
+    let data: Vec<u8> = decode_b64(encoded)?;
+    let mut bits: Vec<u8> = Vec::new();
+
+    // Read one byte past the limit to detect oversized lists without
+    // decompressing them entirely
+    GzDecoder::new(&data[..])
+      .take(Self::MAX_LIST_BYTES as u64 + 1)
+      .read_to_end(&mut bits)
+      .map_err(|_| Error::InvalidRevocationList("Invalid Encoded List"))?;
+
+    if bits.len() > Self::MAX_LIST_BYTES {
+      return Err(Error::InvalidRevocationList("Encoded List Too Long"));
+    }
+
+    if bits.len() * 8 < Self::MIN_LENGTH {
+      return Err(Error::InvalidRevocationList("Encoded List Too Short"));
+    }
+
+    Ok(Self(bits))
+  }
+
+  /// Encodes the `RevocationList` as a GZIP-compressed, base64url-encoded
+  /// bitstring.
+  pub fn encode(&self) -> Result<String> {
+    let mut encoder: GzEncoder<Vec<u8>> = GzEncoder::new(Vec::new(), Compression::best());
+
+    encoder
+      .write_all(&self.0)
+      .and_then(|_| encoder.finish())
+      .map(|data| encode_b64(&data))
+      .map_err(|_| Error::InvalidRevocationList("Compression Failed"))
+  }
+
+  /// Returns the number of entries of the `RevocationList`.
+  pub fn len(&self) -> usize {
+    self.0.len() * 8
+  }
+
+  /// Returns `true` if the `RevocationList` has no entries.
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// Returns `true` if the entry at `index` is revoked.
+  pub fn is_revoked(&self, index: usize) -> Result<bool> {
+    self
+      .0
+      .get(index / 8)
+      .map(|byte| byte & Self::mask(index) != 0)
+      .ok_or(Error::InvalidRevocationStatus("Index Out Of Bounds"))
+  }
+
+  /// Revokes the entry at `index`; returns `false` if it was already revoked.
+  pub fn revoke(&mut self, index: usize) -> Result<bool> {
+    let byte: &mut u8 = self
+      .0
+      .get_mut(index / 8)
+      .ok_or(Error::InvalidRevocationStatus("Index Out Of Bounds"))?;
+
+    let revoked: bool = *byte & Self::mask(index) == 0;
+
+    *byte |= Self::mask(index);
+
+    Ok(revoked)
+  }
+
+  /// Decodes the `RevocationList` published by `credential`.
+  pub fn from_credential<T>(credential: &Credential<T>) -> Result<Self> {
+    Self::subject(credential)?
+      .properties
+      .get("encodedList")
+      .and_then(Value::as_str)
+      .ok_or(Error::InvalidRevocationList("Missing Encoded List"))
+      .and_then(Self::decode)
+  }
+
+  /// Writes the encoded `RevocationList` into the subject of `credential`.
+  pub fn update<T>(&self, credential: &mut Credential<T>) -> Result<()> {
+    let encoded: String = self.encode()?;
+
+    Self::subject_mut(credential)?
+      .properties
+      .insert("encodedList".into(), encoded.into());
+
+    Ok(())
+  }
+
+  // Entries are indexed from the most significant bit of each byte
+  fn mask(index: usize) -> u8 {
+    0x80 >> (index % 8)
+  }
+
+  fn subject<T>(credential: &Credential<T>) -> Result<&Subject> {
+    if !credential.types.iter().any(|type_| type_ == Self::CREDENTIAL_TYPE) {
+      return Err(Error::InvalidRevocationList("Missing Credential Type"));
+    }
+
+    credential
+      .credential_subject
+      .get(0)
+      .filter(|subject| subject.properties.get("type").and_then(Value::as_str) == Some(Self::SUBJECT_TYPE))
+      .ok_or(Error::InvalidRevocationList("Missing Subject Type"))
+  }
+
+  fn subject_mut<T>(credential: &mut Credential<T>) -> Result<&mut Subject> {
+    Self::subject(credential)?;
+
+    credential
+      .credential_subject
+      .get_mut(0)
+      .ok_or(Error::InvalidRevocationList("Missing Subject Type"))
+  }
+}
+
+impl Default for RevocationList {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> Credential<T> {
+  /// Returns the `revocationListCredential` and `revocationListIndex` of the
+  /// `RevocationList2020Status` of the `Credential`.
+  pub fn revocation_status(&self) -> Result<(Url, usize)> {
+    let status: &Status = self
+      .credential_status
+      .iter()
+      .find(|status| status.types.iter().any(|type_| type_ == RevocationList::STATUS_TYPE))
+      .ok_or(Error::InvalidRevocationStatus("Missing Status"))?;
+
+    // The index is a string in the specification; numbers are accepted as well
+    let index: usize = match status.properties.get("revocationListIndex") {
+      Some(Value::String(index)) => index.parse().ok(),
+      Some(Value::Number(index)) => index.as_u64().map(|index| index as usize),
+      _ => None,
+    }
+    .ok_or(Error::InvalidRevocationStatus("Invalid Index"))?;
+
+    let list: Url = status
+      .properties
+      .get("revocationListCredential")
+      .and_then(Value::as_str)
+      .and_then(|list| Url::parse(list).ok())
+      .ok_or(Error::InvalidRevocationStatus("Invalid List Credential"))?;
+
+    Ok((list, index))
+  }
+
+  /// Returns `true` if the `Credential` is revoked in the revocation `list`.
+  ///
+  /// The signature of `list` is not verified.
+  pub fn is_revoked<U>(&self, list: &Credential<U>) -> Result<bool> {
+    let index: usize = self.revocation_list_index(list)?;
+
+    RevocationList::from_credential(list)?.is_revoked(index)
+  }
+
+  /// Revokes the `Credential` in the revocation `list`; returns the updated
+  /// `list` signed with the verification method of `document` matching `query`.
+  ///
+  /// Any proof of `list` is replaced by the new proof.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `document` is not the issuer of both the `Credential` and `list`.
+  pub fn revoke<U, D1, D2, D3>(
+    &self,
+    list: &Credential<U>,
+    document: &Document<D1, D2, D3>,
+    query: MethodQuery<'_>,
+    secret: &SecretKey,
+  ) -> Result<VerifiableCredential<U>>
+  where
+    U: Clone + Serialize,
+  {
+    let index: usize = self.revocation_list_index(list)?;
+
+    if self.issuer.url().as_str() != document.id().as_str() || list.issuer.url().as_str() != document.id().as_str() {
+      return Err(Error::InvalidRevocationList("Issuer Mismatch"));
+    }
+
+    let mut bits: RevocationList = RevocationList::from_credential(list)?;
+    let mut list: Credential<U> = list.clone();
+
+    bits.revoke(index)?;
+    bits.update(&mut list)?;
+
+    list.sign(document, query, secret)
+  }
+
+  fn revocation_list_index<U>(&self, list: &Credential<U>) -> Result<usize> {
+    let (url, index): (Url, usize) = self.revocation_status()?;
+
+    if list.id.as_ref().map(|id| id.as_str()) != Some(url.as_str()) {
+      return Err(Error::InvalidRevocationStatus("List Credential Mismatch"));
+    }
+
+    Ok(index)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Url;
+  use identity_core::convert::FromJson;
+  use identity_core::crypto::KeyPair;
+  use identity_core::utils::encode_b58;
+  use identity_did::did::DID;
+  use identity_did::document::Document;
+  use identity_did::document::DocumentBuilder;
+  use identity_did::verification::Method;
+  use identity_did::verification::MethodBuilder;
+  use identity_did::verification::MethodData;
+  use identity_did::verification::MethodType;
+
+  use crate::credential::Credential;
+  use crate::credential::RevocationList;
+  use crate::credential::VerifiableCredential;
+  use crate::error::Error;
+
+  const CREDENTIAL: &str = include_str!("../../tests/fixtures/revocation-1.json");
+  const LIST: &str = include_str!("../../tests/fixtures/revocation-list-1.json");
+
+  #[test]
+  fn test_encode_decode() {
+    let mut list: RevocationList = RevocationList::new();
+
+    assert_eq!(list.len(), RevocationList::MIN_LENGTH);
+    assert!(list.revoke(0).unwrap());
+    assert!(list.revoke(94567).unwrap());
+    assert!(!list.revoke(94567).unwrap());
+    assert!(list.revoke(RevocationList::MIN_LENGTH).is_err());
+
+    let decoded: RevocationList = RevocationList::decode(&list.encode().unwrap()).unwrap();
+
+    assert_eq!(decoded, list);
+    assert!(decoded.is_revoked(0).unwrap());
+    assert!(!decoded.is_revoked(1).unwrap());
+    assert!(decoded.is_revoked(94567).unwrap());
+  }
+
+  #[test]
+  fn test_decode_too_long() {
+    let list: RevocationList = RevocationList(vec![0; RevocationList::MAX_LIST_BYTES]);
+
+    assert!(RevocationList::decode(&list.encode().unwrap()).is_ok());
+
+    // A small encoded list decompressing beyond the limit
+    let list: RevocationList = RevocationList(vec![0; RevocationList::MAX_LIST_BYTES + 1]);
+
+    assert!(matches!(
+      RevocationList::decode(&list.encode().unwrap()),
+      Err(Error::InvalidRevocationList("Encoded List Too Long"))
+    ));
+  }
+
+  #[test]
+  fn test_revoke() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let controller: DID = "did:example:1234".parse().unwrap();
+
+    let method: Method = MethodBuilder::default()
+      .id(controller.join("#key-1").unwrap())
+      .controller(controller.clone())
+      .key_type(MethodType::Ed25519VerificationKey2018)
+      .key_data(MethodData::PublicKeyBase58(encode_b58(keypair.public())))
+      .build()
+      .unwrap();
+
+    let document: Document = DocumentBuilder::default()
+      .id(controller)
+      .verification_method(method)
+      .build()
+      .unwrap();
+
+    let credential: Credential = Credential::from_json(CREDENTIAL).unwrap();
+    let list: Credential = Credential::from_json(LIST).unwrap();
+
+    assert_eq!(credential.revocation_status().unwrap().1, 94567);
+    assert!(!credential.is_revoked(&list).unwrap());
+
+    let revoked: VerifiableCredential = credential
+      .revoke(&list, &document, "#key-1".into(), keypair.secret())
+      .unwrap();
+
+    assert!(credential.is_revoked(&*revoked).unwrap());
+    assert!(document.verifier().verify(&revoked).is_ok());
+
+    // The list can only be revoked by its issuer
+    let mut foreign: Credential = list.clone();
+
+    foreign.issuer = Url::parse("did:example:5678").unwrap().into();
+
+    assert!(matches!(
+      credential.revoke(&foreign, &document, "#key-1".into(), keypair.secret()),
+      Err(Error::InvalidRevocationList("Issuer Mismatch"))
+    ));
+
+    // The credential is listed in a different revocation list
+    let mut other: Credential = list.clone();
+
+    other.id = Some(Url::parse("https://example.com/credentials/status/4").unwrap());
+
+    assert!(matches!(
+      credential.is_revoked(&other),
+      Err(Error::InvalidRevocationStatus(_))
+    ));
+  }
+}
@@ -9,6 +9,9 @@ pub type Result<T, E = Error> = ::core::result::Result<T, E>;
 /// This type represents all possible errors that can occur in the library.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
+  /// Caused by errors from the `identity_core` crate.
+  #[error("{0}")]
+  CoreError(#[from] identity_core::Error),
   /// Caused by errors from the `identity_did` crate.
   #[error("{0}")]
   DIDError(#[from] identity_did::Error),
@@ -27,4 +30,13 @@ pub enum Error {
   /// Caused when validating a Credential with a malformed subject.
   #[error("Invalid Credential Subject")]
   InvalidSubject,
+  /// Caused when a Credential cannot be represented as EIP-712 typed data.
+  #[error("Invalid EIP-712 Data: {0}")]
+  InvalidEip712Data(&'static str),
+  /// Caused when a revocation list is malformed.
+  #[error("Invalid Revocation List: {0}")]
+  InvalidRevocationList(&'static str),
+  /// Caused when a Credential has no valid revocation status for a list.
+  #[error("Invalid Revocation Status: {0}")]
+  InvalidRevocationStatus(&'static str),
 }
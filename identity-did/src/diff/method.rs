@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use identity_core::common::Object;
+use identity_core::common::Timestamp;
 use identity_core::diff::Diff;
+use identity_core::diff::DiffOption;
 use identity_core::diff::DiffString;
 use identity_core::diff::Error;
 use identity_core::diff::Result;
@@ -29,6 +31,8 @@ where
   #[serde(skip_serializing_if = "Option::is_none")]
   key_data: Option<DiffMethodData>,
   #[serde(skip_serializing_if = "Option::is_none")]
+  expires: Option<DiffOption<Timestamp>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
   properties: Option<<T as Diff>::Type>,
 }
 
@@ -60,6 +64,11 @@ where
       } else {
         Some(self.key_data().diff(other.key_data())?)
       },
+      expires: if self.expires() == other.expires() {
+        None
+      } else {
+        Some(self.expires().diff(&other.expires())?)
+      },
       properties: if self.properties() == other.properties() {
         None
       } else {
@@ -93,6 +102,12 @@ where
       .transpose()?
       .unwrap_or_else(|| self.key_type());
 
+    let expires: Option<Timestamp> = diff
+      .expires
+      .map(|value| self.expires().merge(value))
+      .transpose()?
+      .unwrap_or_else(|| self.expires());
+
     let properties: T = diff
       .properties
       .map(|value| self.properties().merge(value))
@@ -104,6 +119,7 @@ where
       controller,
       key_type,
       key_data,
+      expires,
       properties,
     })
   }
@@ -133,6 +149,8 @@ where
       .transpose()?
       .ok_or_else(|| Error::convert("Missing field `key_data`"))?;
 
+    let expires: Option<Timestamp> = diff.expires.map(<Option<Timestamp>>::from_diff).transpose()?.flatten();
+
     let properties: T = diff
       .properties
       .map(T::from_diff)
@@ -144,6 +162,7 @@ where
       controller,
       key_type,
       key_data,
+      expires,
       properties,
     })
   }
@@ -154,6 +173,7 @@ where
       controller: Some(self.controller().to_string().into_diff()?),
       key_type: Some(self.key_type().into_diff()?),
       key_data: Some(self.key_data().clone().into_diff()?),
+      expires: Some(self.expires().into_diff()?),
       properties: Some(self.properties().clone().into_diff()?),
     })
   }
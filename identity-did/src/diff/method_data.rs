@@ -13,6 +13,7 @@ pub enum DiffMethodData {
   PublicKeyBase58(#[serde(skip_serializing_if = "Option::is_none")] Option<DiffString>),
   PublicKeyHex(#[serde(skip_serializing_if = "Option::is_none")] Option<DiffString>),
   PublicKeyJwk(#[serde(skip_serializing_if = "Option::is_none")] Option<DiffObject>),
+  PublicKeyMultibase(#[serde(skip_serializing_if = "Option::is_none")] Option<DiffString>),
 }
 
 impl Diff for MethodData {
@@ -26,6 +27,12 @@ impl Diff for MethodData {
       (Self::PublicKeyHex(a), Self::PublicKeyHex(b)) => a.diff(b).map(Some).map(DiffMethodData::PublicKeyHex),
       (Self::PublicKeyJwk(a), Self::PublicKeyJwk(b)) if a == b => Ok(DiffMethodData::PublicKeyJwk(None)),
       (Self::PublicKeyJwk(a), Self::PublicKeyJwk(b)) => a.diff(b).map(Some).map(DiffMethodData::PublicKeyJwk),
+      (Self::PublicKeyMultibase(a), Self::PublicKeyMultibase(b)) if a == b => {
+        Ok(DiffMethodData::PublicKeyMultibase(None))
+      }
+      (Self::PublicKeyMultibase(a), Self::PublicKeyMultibase(b)) => {
+        a.diff(b).map(Some).map(DiffMethodData::PublicKeyMultibase)
+      }
       (_, _) => other.clone().into_diff(),
     }
   }
@@ -40,6 +47,12 @@ impl Diff for MethodData {
       (Self::PublicKeyHex(a), DiffMethodData::PublicKeyHex(None)) => Ok(Self::PublicKeyHex(a.clone())),
       (Self::PublicKeyJwk(a), DiffMethodData::PublicKeyJwk(Some(ref b))) => a.merge(b.clone()).map(Self::PublicKeyJwk),
       (Self::PublicKeyJwk(a), DiffMethodData::PublicKeyJwk(None)) => Ok(Self::PublicKeyJwk(a.clone())),
+      (Self::PublicKeyMultibase(a), DiffMethodData::PublicKeyMultibase(Some(ref b))) => {
+        a.merge(b.clone()).map(Self::PublicKeyMultibase)
+      }
+      (Self::PublicKeyMultibase(a), DiffMethodData::PublicKeyMultibase(None)) => {
+        Ok(Self::PublicKeyMultibase(a.clone()))
+      }
       (_, diff) => Self::from_diff(diff),
     }
   }
@@ -52,6 +65,8 @@ impl Diff for MethodData {
       DiffMethodData::PublicKeyHex(None) => Ok(Self::PublicKeyHex(Default::default())),
       DiffMethodData::PublicKeyJwk(Some(value)) => Diff::from_diff(value).map(Self::PublicKeyJwk),
       DiffMethodData::PublicKeyJwk(None) => Ok(Self::PublicKeyJwk(Default::default())),
+      DiffMethodData::PublicKeyMultibase(Some(value)) => Diff::from_diff(value).map(Self::PublicKeyMultibase),
+      DiffMethodData::PublicKeyMultibase(None) => Ok(Self::PublicKeyMultibase(Default::default())),
     }
   }
 
@@ -60,6 +75,7 @@ impl Diff for MethodData {
       Self::PublicKeyBase58(value) => value.into_diff().map(Some).map(DiffMethodData::PublicKeyBase58),
       Self::PublicKeyHex(value) => value.into_diff().map(Some).map(DiffMethodData::PublicKeyHex),
       Self::PublicKeyJwk(value) => value.into_diff().map(Some).map(DiffMethodData::PublicKeyJwk),
+      Self::PublicKeyMultibase(value) => value.into_diff().map(Some).map(DiffMethodData::PublicKeyMultibase),
     }
   }
 }
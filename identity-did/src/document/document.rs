@@ -10,6 +10,7 @@ use identity_core::common::Object;
 use identity_core::common::Url;
 use identity_core::convert::ToJson;
 use serde::Serialize;
+use std::collections::HashSet;
 
 use crate::did::DID;
 use crate::document::DocumentBuilder;
@@ -51,6 +52,11 @@ pub struct Document<T = Object, U = Object, V = Object> {
 }
 
 impl<T, U, V> Document<T, U, V> {
+  /// Returns the base JSON-LD context for DID `Document`s.
+  pub const fn base_context() -> &'static str {
+    "https://www.w3.org/ns/did/v1"
+  }
+
   /// Creates a `DocumentBuilder` to configure a new `Document`.
   ///
   /// This is the same as `DocumentBuilder::new()`.
@@ -252,7 +258,88 @@ impl<T, U, V> Document<T, U, V> {
     self.verification_method.remove(did);
   }
 
+  /// Returns the ids of verification methods with the same key type and key
+  /// material as a preceding method, each paired with the id of that method.
+  ///
+  /// The default authentication method always precedes all other methods.
+  pub fn duplicate_keys(&self) -> Vec<(DID, DID)> {
+    let head: Option<&Method<U>> = self.authentication.head().and_then(|method| self.resolve_ref(method));
+    let rest: _ = self.methods().filter(|method| head.map_or(true, |head| head.id() != method.id()));
+
+    let mut seen: Vec<(&Method<U>, Vec<u8>)> = Vec::new();
+    let mut duplicates: Vec<(DID, DID)> = Vec::new();
+
+    for method in head.into_iter().chain(rest) {
+      let data: Vec<u8> = match method.key_data().try_decode() {
+        Ok(data) => data,
+        Err(_) => continue,
+      };
+
+      let original: Option<&Method<U>> = seen
+        .iter()
+        .find(|(other, other_data)| other.key_type() == method.key_type() && *other_data == data)
+        .map(|(other, _)| *other);
+
+      match original {
+        Some(original) => duplicates.push((method.id().clone(), original.id().clone())),
+        None => seen.push((method, data)),
+      }
+    }
+
+    duplicates
+  }
+
+  /// Removes verification methods with duplicate key material; relationships
+  /// of a removed method are transferred to the method it duplicates.
+  ///
+  /// Returns the ids of the removed methods.
+  pub fn dedupe_keys(&mut self) -> Vec<DID>
+  where
+    U: Clone,
+  {
+    let duplicates: Vec<(DID, DID)> = self.duplicate_keys();
+
+    for (duplicate, original) in duplicates.iter() {
+      // References can only target methods in `verificationMethod`
+      let method: MethodRef<U> = if self.verification_method.query(original.as_str()).is_some() {
+        MethodRef::Refer(original.clone())
+      } else {
+        match self.resolve(original.as_str()) {
+          Some(method) => MethodRef::Embed(method.clone()),
+          None => continue,
+        }
+      };
+
+      let mut relationships: [&mut OrderedSet<DIDKey<MethodRef<U>>>; 5] = [
+        &mut self.authentication,
+        &mut self.assertion_method,
+        &mut self.key_agreement,
+        &mut self.capability_delegation,
+        &mut self.capability_invocation,
+      ];
+
+      for relationship in relationships.iter_mut() {
+        if !relationship.iter().any(|item| item.as_did() == duplicate) {
+          continue;
+        }
+
+        relationship.remove(duplicate);
+
+        if !relationship.iter().any(|item| item.as_did() == original) {
+          relationship.append(method.clone().into());
+        }
+      }
+
+      self.verification_method.remove(duplicate);
+    }
+
+    duplicates.into_iter().map(|(duplicate, _)| duplicate).collect()
+  }
+
   /// Returns an iterator over all verification methods in the DID Document.
+  ///
+  /// This includes methods embedded in verification relationships; methods
+  /// with the same `id` are only returned once.
   pub fn methods(&self) -> impl Iterator<Item = &Method<U>> {
     fn __filter_ref<T>(method: &DIDKey<MethodRef<T>>) -> Option<&Method<T>> {
       match &**method {
@@ -261,6 +348,8 @@ impl<T, U, V> Document<T, U, V> {
       }
     }
 
+    let mut seen: HashSet<&str> = HashSet::new();
+
     self
       .verification_method
       .iter()
@@ -270,10 +359,14 @@ impl<T, U, V> Document<T, U, V> {
       .chain(self.key_agreement.iter().filter_map(__filter_ref))
       .chain(self.capability_delegation.iter().filter_map(__filter_ref))
       .chain(self.capability_invocation.iter().filter_map(__filter_ref))
+      .filter(move |method| seen.insert(method.id().as_str()))
   }
 
   /// Returns the first verification [`method`][`Method`] with an `id` property
   /// matching the provided `query`.
+  ///
+  /// The verification relationships are searched in order, starting with the
+  /// default authentication method, followed by the `verificationMethod` set.
   pub fn resolve<'query, Q>(&self, query: Q) -> Option<&Method<U>>
   where
     Q: Into<MethodQuery<'query>>,
@@ -316,6 +409,53 @@ impl<T, U, V> Document<T, U, V> {
     self.resolve_mut(query).ok_or(Error::QueryMethodNotFound)
   }
 
+  /// Returns `true` if the verification [`method`][`Method`] matching the
+  /// provided `query` is authorized under `capabilityInvocation`.
+  ///
+  /// References are only authorized if they resolve to a method in the
+  /// `verificationMethod` set.
+  pub fn is_capability_invoker<'query, Q>(&self, query: Q) -> bool
+  where
+    Q: Into<MethodQuery<'query>>,
+  {
+    self
+      .capability_invocation
+      .query(query)
+      .and_then(|method| self.resolve_ref(method))
+      .is_some()
+  }
+
+  /// Returns the verification relationships of the verification
+  /// [`method`][`Method`] matching the provided `query`.
+  ///
+  /// References are only included if they resolve to a method in the
+  /// `verificationMethod` set.
+  pub fn relationships<'query, Q>(&self, query: Q) -> Vec<MethodScope>
+  where
+    Q: Into<MethodQuery<'query>>,
+  {
+    let query: MethodQuery<'_> = query.into();
+
+    let relationships: [(MethodScope, &OrderedSet<DIDKey<MethodRef<U>>>); 5] = [
+      (MethodScope::Authentication, &self.authentication),
+      (MethodScope::AssertionMethod, &self.assertion_method),
+      (MethodScope::KeyAgreement, &self.key_agreement),
+      (MethodScope::CapabilityDelegation, &self.capability_delegation),
+      (MethodScope::CapabilityInvocation, &self.capability_invocation),
+    ];
+
+    relationships
+      .iter()
+      .filter(|(_, methods)| {
+        methods
+          .query(query)
+          .and_then(|method| self.resolve_ref(method))
+          .is_some()
+      })
+      .map(|(scope, _)| *scope)
+      .collect()
+  }
+
   #[doc(hidden)]
   pub fn resolve_ref<'a>(&'a self, method: &'a MethodRef<U>) -> Option<&'a Method<U>> {
     match method {
@@ -408,7 +548,9 @@ mod tests {
   use crate::verification::Method;
   use crate::verification::MethodBuilder;
   use crate::verification::MethodData;
+  use crate::verification::MethodScope;
   use crate::verification::MethodType;
+  use crate::Error;
 
   fn controller() -> DID {
     "did:example:1234".parse().unwrap()
@@ -465,6 +607,25 @@ mod tests {
     assert_eq!(document.methods().nth(2).unwrap().id(), "did:example:1234#key-3");
   }
 
+  #[test]
+  fn test_methods_unique() {
+    let controller: DID = controller();
+    let shared: Method = method(&controller, "#shared");
+
+    let document: Document = DocumentBuilder::default()
+      .id(controller.clone())
+      .verification_method(method(&controller, "#key-1"))
+      .authentication(shared.clone())
+      .assertion_method(shared)
+      .key_agreement(controller.join("#key-1").unwrap())
+      .build()
+      .unwrap();
+
+    let methods: Vec<&str> = document.methods().map(|method| method.id().as_str()).collect();
+
+    assert_eq!(methods, ["did:example:1234#key-1", "did:example:1234#shared"]);
+  }
+
   #[test]
   #[rustfmt::skip]
   fn test_resolve_reference_missing() {
@@ -473,4 +634,92 @@ mod tests {
     // Resolving an existing reference to a missing method returns None
     assert_eq!(document.resolve("#key-4"), None);
   }
+
+  #[test]
+  fn test_is_capability_invoker() {
+    let controller: DID = controller();
+
+    let document: Document = DocumentBuilder::default()
+      .id(controller.clone())
+      .verification_method(method(&controller, "#key-1"))
+      .authentication(method(&controller, "#auth-key"))
+      .capability_invocation(method(&controller, "#invoke-key"))
+      .capability_invocation(controller.join("#key-1").unwrap())
+      .capability_invocation(controller.join("#key-2").unwrap())
+      .build()
+      .unwrap();
+
+    assert!(document.is_capability_invoker("#invoke-key"));
+    assert!(document.is_capability_invoker("#key-1"));
+
+    // Methods outside of `capabilityInvocation` are not authorized
+    assert!(!document.is_capability_invoker("#auth-key"));
+
+    // References to missing methods are not authorized
+    assert!(!document.is_capability_invoker("#key-2"));
+  }
+
+  #[test]
+  fn test_relationships() {
+    let controller: DID = controller();
+    let shared: Method = method(&controller, "#shared");
+
+    let document: Document = DocumentBuilder::default()
+      .id(controller.clone())
+      .verification_method(method(&controller, "#key-1"))
+      .authentication(shared.clone())
+      .assertion_method(shared)
+      .assertion_method(controller.join("#key-1").unwrap())
+      .capability_invocation(controller.join("#key-1").unwrap())
+      .capability_invocation(controller.join("#key-2").unwrap())
+      .build()
+      .unwrap();
+
+    assert_eq!(
+      document.relationships("#shared"),
+      [MethodScope::Authentication, MethodScope::AssertionMethod]
+    );
+    assert_eq!(
+      document.relationships("#key-1"),
+      [MethodScope::AssertionMethod, MethodScope::CapabilityInvocation]
+    );
+
+    // References to missing methods are not included
+    assert!(document.relationships("#key-2").is_empty());
+  }
+
+  #[test]
+  fn test_dedupe_keys() {
+    let controller: DID = controller();
+    let mut duplicate: Method = method(&controller, "#key-1");
+
+    *duplicate.id_mut() = controller.join("#key-2").unwrap();
+
+    let mut document: Document = DocumentBuilder::default()
+      .id(controller.clone())
+      .verification_method(method(&controller, "#key-1"))
+      .verification_method(duplicate)
+      .authentication(method(&controller, "#auth-key"))
+      .assertion_method(controller.join("#key-2").unwrap())
+      .build()
+      .unwrap();
+
+    assert_eq!(
+      document.duplicate_keys(),
+      [(controller.join("#key-2").unwrap(), controller.join("#key-1").unwrap())]
+    );
+
+    assert_eq!(document.dedupe_keys(), [controller.join("#key-2").unwrap()]);
+    assert!(document.duplicate_keys().is_empty());
+    assert_eq!(document.resolve("#key-2"), None);
+
+    // The relationships of the removed method are transferred
+    let assertion: Vec<&str> = document
+      .assertion_method()
+      .iter()
+      .map(|method| method.as_did().as_str())
+      .collect();
+
+    assert_eq!(assertion, ["did:example:1234#key-1"]);
+  }
 }
@@ -46,6 +46,12 @@ pub enum Error {
   InvalidMethodType,
   #[error("Invalid Verification Method - Duplicate")]
   InvalidMethodDuplicate,
+  #[error("Invalid Verification Method - Expired")]
+  InvalidMethodExpired,
+  #[error("Invalid Verification Method - Unsupported Type Migration")]
+  InvalidMethodMigration,
+  #[error("Invalid Verification Method - Merkle Key Collection revocation flags unavailable")]
+  InvalidMethodRevocation,
 
   #[error("Unknown Method Scope")]
   UnknownMethodScope,
@@ -53,6 +59,16 @@ pub enum Error {
   UnknownMethodType,
   #[error("Unknown Signature Type")]
   UnknownSignatureType,
+  #[error("Invalid Signature - Unexpected Verification Method")]
+  InvalidSignatureMethod,
+  #[error("Invalid Signature - Merkle Key Collection methods require a `MerkleKeySignature2021` proof")]
+  InvalidSignatureMerkleKey,
+  #[error("Invalid Proof - Unexpected Domain")]
+  InvalidProofDomain,
+  #[error("Invalid Proof - Unexpected Challenge")]
+  InvalidProofChallenge,
+  #[error("Invalid Proof - Unexpected Purpose")]
+  InvalidProofPurpose,
 
   #[error("Invalid Key Data")]
   InvalidKeyData,
@@ -60,6 +76,8 @@ pub enum Error {
   InvalidKeyDataBase16,
   #[error("Invalid Base58 Key Data")]
   InvalidKeyDataBase58,
+  #[error("Invalid Multibase Key Data")]
+  InvalidKeyDataMultibase,
 
   #[error("Missing Resolution DID")]
   MissingResolutionDID,
@@ -17,6 +17,11 @@ pub struct DocumentMetadata {
   /// [More Info](https://www.w3.org/TR/did-spec-registries/#updated)
   #[serde(skip_serializing_if = "Option::is_none")]
   pub updated: Option<Timestamp>,
+  /// Whether the DID has been deactivated.
+  ///
+  /// [More Info](https://www.w3.org/TR/did-spec-registries/#deactivated)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub deactivated: Option<bool>,
   /// Additional document metadata properties.
   #[serde(flatten)]
   pub properties: Object,
@@ -28,6 +33,7 @@ impl DocumentMetadata {
     Self {
       created: None,
       updated: None,
+      deactivated: None,
       properties: Object::new(),
     }
   }
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use core::any::Any;
+use identity_core::common::Timestamp;
+use identity_core::crypto::merkle_key::Blake2b256;
 use identity_core::crypto::merkle_key::MerkleDigest;
 use identity_core::crypto::merkle_key::MerkleKey;
 use identity_core::crypto::merkle_key::MerkleTag;
@@ -9,11 +11,14 @@ use identity_core::crypto::merkle_key::Sha256;
 use identity_core::crypto::merkle_key::Signer;
 use identity_core::crypto::merkle_key::Verifier;
 use identity_core::crypto::merkle_tree::Proof;
+use identity_core::crypto::Ed25519Signature2020;
 use identity_core::crypto::JcsEd25519Signature2020 as Ed25519;
+use identity_core::crypto::KeyType;
 use identity_core::crypto::PublicKey;
 use identity_core::crypto::SecretKey;
 use identity_core::crypto::SetSignature;
 use identity_core::crypto::Signature;
+use identity_core::crypto::SignatureOptions;
 use identity_core::crypto::SignatureSign;
 use identity_core::crypto::SignatureVerify;
 use identity_core::crypto::TrySignature;
@@ -26,6 +31,8 @@ use crate::error::Error;
 use crate::error::Result;
 use crate::verifiable::Properties;
 use crate::verifiable::Revocation;
+use crate::verifiable::SignatureSuiteRegistry;
+use crate::verifiable::SignatureType;
 use crate::verification::Method;
 use crate::verification::MethodQuery;
 use crate::verification::MethodType;
@@ -94,29 +101,48 @@ where
     let fragment: String = method.try_into_fragment()?;
 
     match method.key_type() {
-      MethodType::Ed25519VerificationKey2018 => {
+      MethodType::Ed25519VerificationKey2018 | MethodType::Ed25519VerificationKey2020 => {
         Ed25519.__sign(self, fragment, secret)?;
       }
       MethodType::MerkleKeyCollection2021 => {
         // Documents can't be signed with Merkle Key Collections
         return Err(Error::InvalidMethodType);
       }
+      MethodType::X25519KeyAgreementKey2019 => {
+        // Key agreement keys can't be used for signatures
+        return Err(Error::InvalidMethodType);
+      }
     }
 
     Ok(())
   }
 
+  /// Verifies the `proof` of the document with the verification method it
+  /// references.
+  ///
+  /// Merkle Key Collection methods can't be verified without their revocation
+  /// flags; use [`Document::verify_this_with_revocation`] for documents
+  /// signed with them.
   pub fn verify_this(&self) -> Result<()> {
     let signature: &Signature = self.try_signature()?;
     let method: &Method<U> = self.try_resolve(signature)?;
-    let public: Vec<u8> = method.key_data().try_decode()?;
+
+    // Proofs without a `created` timestamp are checked against the current time
+    if method.is_expired_at(signature.created().unwrap_or_else(Timestamp::now)) {
+      return Err(Error::InvalidMethodExpired);
+    }
 
     match method.key_type() {
-      MethodType::Ed25519VerificationKey2018 => {
-        Ed25519.__verify(self, &public)?;
+      MethodType::Ed25519VerificationKey2018 | MethodType::Ed25519VerificationKey2020 => {
+        let public: Vec<u8> = method.key_data().try_decode()?;
+
+        SignatureSuiteRegistry::default().verify(self, &public)?;
       }
       MethodType::MerkleKeyCollection2021 => {
-        // Documents can't be signed with Merkle Key Collections
+        return Err(Error::InvalidMethodRevocation);
+      }
+      MethodType::X25519KeyAgreementKey2019 => {
+        // Key agreement keys can't be used for signatures
         return Err(Error::InvalidMethodType);
       }
     }
@@ -125,6 +151,36 @@ where
   }
 }
 
+impl<T, U, V> Document<Properties<T>, U, V>
+where
+  T: Serialize,
+  U: Serialize + Revocation,
+  V: Serialize,
+{
+  /// Verifies the `proof` of the document with the verification method it
+  /// references, honoring the revocation flags of the method.
+  ///
+  /// Merkle Key Collection methods are verified with the public key and
+  /// proof of membership embedded in a `MerkleKeySignature2021` proof; other
+  /// methods are verified as with [`Document::verify_this`].
+  pub fn verify_this_with_revocation(&self) -> Result<()> {
+    let signature: &Signature = self.try_signature()?;
+    let method: &Method<U> = self.try_resolve(signature)?;
+
+    if method.key_type() != MethodType::MerkleKeyCollection2021 {
+      return self.verify_this();
+    }
+
+    // The method only holds the Merkle root - the signing key and proof must
+    // be provided by the signature
+    if signature.type_() != MerkleKey::TYPE_SIG {
+      return Err(Error::InvalidSignatureMerkleKey);
+    }
+
+    self.verifier().verify(self)
+  }
+}
+
 impl<T, U, V> Document<T, U, V> {
   /// Creates a new [`DocumentSigner`] that can be used to create digital
   /// signatures from verification methods in this DID Document.
@@ -148,6 +204,8 @@ pub struct DocumentSigner<'base, 'query, 'proof, T, U, V> {
   secret: &'base SecretKey,
   method: Option<MethodQuery<'query>>,
   merkle_key: Option<(&'proof PublicKey, &'proof dyn Any)>,
+  suite: SignatureType,
+  options: SignatureOptions,
 }
 
 impl<'base, T, U, V> DocumentSigner<'base, '_, '_, T, U, V> {
@@ -157,6 +215,8 @@ impl<'base, T, U, V> DocumentSigner<'base, '_, '_, T, U, V> {
       secret,
       method: None,
       merkle_key: None,
+      suite: SignatureType::default(),
+      options: SignatureOptions::new(),
     }
   }
 }
@@ -171,6 +231,38 @@ impl<'base, 'query, T, U, V> DocumentSigner<'base, 'query, '_, T, U, V> {
   }
 }
 
+impl<T, U, V> DocumentSigner<'_, '_, '_, T, U, V> {
+  /// Sets the signature suite used for `Ed25519VerificationKey2018` methods.
+  pub fn suite(mut self, value: SignatureType) -> Self {
+    self.suite = value;
+    self
+  }
+
+  /// Sets the `domain` written into the created proof.
+  pub fn domain(mut self, value: impl Into<String>) -> Self {
+    self.options.domain = Some(value.into());
+    self
+  }
+
+  /// Sets the `challenge` written into the created proof.
+  pub fn challenge(mut self, value: impl Into<String>) -> Self {
+    self.options.challenge = Some(value.into());
+    self
+  }
+
+  /// Sets the `proofPurpose` written into the created proof.
+  pub fn purpose(mut self, value: impl Into<String>) -> Self {
+    self.options.purpose = Some(value.into());
+    self
+  }
+
+  /// Sets the `created` timestamp written into the created proof.
+  pub fn created(mut self, value: Timestamp) -> Self {
+    self.options.created = Some(value);
+    self
+  }
+}
+
 impl<'proof, T, U, V> DocumentSigner<'_, '_, 'proof, T, U, V> {
   pub fn merkle_key<D>(mut self, proof: (&'proof PublicKey, &'proof Proof<D>)) -> Self
   where
@@ -199,9 +291,19 @@ impl<T, U, V> DocumentSigner<'_, '_, '_, T, U, V> {
     let fragment: String = method.try_into_fragment()?;
 
     match method.key_type() {
-      MethodType::Ed25519VerificationKey2018 => {
-        Ed25519.__sign(that, fragment, secret)?;
-      }
+      MethodType::Ed25519VerificationKey2018 | MethodType::Ed25519VerificationKey2020 => match self.suite {
+        SignatureType::JcsEd25519Signature2020 => Ed25519.__sign_with(that, fragment, secret, &self.options)?,
+        SignatureType::Ed25519Signature2020 => {
+          // Linked Data proofs reference the method by its absolute DID URL
+          // and require the `created` and `proofPurpose` properties
+          let mut options: SignatureOptions = self.options.clone();
+
+          options.created.get_or_insert_with(Timestamp::now);
+          options.purpose.get_or_insert_with(|| "assertionMethod".into());
+
+          Ed25519Signature2020.__sign_with(that, method.id().to_string(), secret, &options)?
+        }
+      },
       MethodType::MerkleKeyCollection2021 => {
         let data: Vec<u8> = method.key_data().try_decode()?;
 
@@ -220,13 +322,32 @@ impl<T, U, V> DocumentSigner<'_, '_, '_, T, U, V> {
               }
             };
 
-            signer.__sign(that, fragment, secret)?;
+            signer.__sign_with(that, fragment, secret, &self.options)?;
+          }
+          (MerkleTag::ED25519, MerkleTag::BLAKE2B_256) => {
+            let signer: _ = match self.merkle_key {
+              Some((public, proof)) => {
+                let proof: &Proof<Blake2b256> = proof
+                  .downcast_ref()
+                  .ok_or(Error::CoreError(CoreError::InvalidKeyFormat))?;
+
+                Signer::from_borrowed(Ed25519, public, proof)
+              }
+              None => {
+                return Err(Error::CoreError(CoreError::InvalidKeyFormat));
+              }
+            };
+
+            signer.__sign_with(that, fragment, secret, &self.options)?;
           }
           (_, _) => {
             return Err(Error::InvalidMethodType);
           }
         }
       }
+      MethodType::X25519KeyAgreementKey2019 => {
+        return Err(Error::InvalidMethodType);
+      }
     }
 
     Ok(())
@@ -239,11 +360,47 @@ impl<T, U, V> DocumentSigner<'_, '_, '_, T, U, V> {
 
 pub struct DocumentVerifier<'base, T, U, V> {
   document: &'base Document<T, U, V>,
+  registry: Option<&'base SignatureSuiteRegistry>,
+  domain: Option<String>,
+  challenge: Option<String>,
+  purpose: Option<String>,
 }
 
 impl<'base, T, U, V> DocumentVerifier<'base, T, U, V> {
   pub fn new(document: &'base Document<T, U, V>) -> Self {
-    Self { document }
+    Self {
+      document,
+      registry: None,
+      domain: None,
+      challenge: None,
+      purpose: None,
+    }
+  }
+
+  /// Sets the signature suites used to verify signatures created with
+  /// `Ed25519` methods; the [default][`SignatureSuiteRegistry::default`]
+  /// suites are used otherwise.
+  pub fn registry(mut self, value: &'base SignatureSuiteRegistry) -> Self {
+    self.registry = Some(value);
+    self
+  }
+
+  /// Sets the `domain` the proof is expected to contain.
+  pub fn domain(mut self, value: impl Into<String>) -> Self {
+    self.domain = Some(value.into());
+    self
+  }
+
+  /// Sets the `challenge` the proof is expected to contain.
+  pub fn challenge(mut self, value: impl Into<String>) -> Self {
+    self.challenge = Some(value.into());
+    self
+  }
+
+  /// Sets the `proofPurpose` the proof is expected to contain.
+  pub fn purpose(mut self, value: impl Into<String>) -> Self {
+    self.purpose = Some(value.into());
+    self
   }
 }
 
@@ -255,7 +412,8 @@ where
   ///
   /// # Errors
   ///
-  /// Fails if an unsupported verification method is used, document
+  /// Fails if an unsupported verification method is used, the proof does
+  /// not contain the expected `domain`, `challenge` or `proofPurpose`, document
   /// serialization fails, or the verification operation fails.
   pub fn verify<X>(&self, that: &X) -> Result<()>
   where
@@ -264,9 +422,43 @@ where
     let signature: &Signature = that.try_signature()?;
     let method: &Method<U> = self.document.try_resolve(signature)?;
 
+    // Proofs without a `created` timestamp are checked against the current time
+    if method.is_expired_at(signature.created().unwrap_or_else(Timestamp::now)) {
+      return Err(Error::InvalidMethodExpired);
+    }
+
+    if self.domain.is_some() && signature.domain() != self.domain.as_deref() {
+      return Err(Error::InvalidProofDomain);
+    }
+
+    if self.challenge.is_some() && signature.challenge() != self.challenge.as_deref() {
+      return Err(Error::InvalidProofChallenge);
+    }
+
+    if self.purpose.is_some() && signature.purpose() != self.purpose.as_deref() {
+      return Err(Error::InvalidProofPurpose);
+    }
+
     match method.key_type() {
-      MethodType::Ed25519VerificationKey2018 => {
-        Ed25519.__verify(that, &method.key_data().try_decode()?)?;
+      MethodType::Ed25519VerificationKey2018 | MethodType::Ed25519VerificationKey2020 => {
+        let public: Vec<u8> = method.key_data().try_decode()?;
+
+        // Reject malformed signatures of the built-in suites before
+        // attempting verification
+        if let Ok(suite) = signature.type_().parse::<SignatureType>() {
+          let length: usize = suite.decode_value(signature.value())?.len();
+          let expected: usize = KeyType::Ed25519.signature_length();
+
+          if length != expected {
+            return Err(Error::CoreError(CoreError::InvalidSigLength(length, expected)));
+          }
+        }
+
+        // Dispatch on the proof `type`
+        match self.registry {
+          Some(registry) => registry.verify(that, &public)?,
+          None => SignatureSuiteRegistry::default().verify(that, &public)?,
+        }
       }
       MethodType::MerkleKeyCollection2021 => {
         let data: Vec<u8> = method.key_data().try_decode()?;
@@ -281,13 +473,48 @@ where
 
             verifier.__verify(that, &[])?;
           }
+          (MerkleTag::ED25519, MerkleTag::BLAKE2B_256) => {
+            let mut verifier: Verifier<'_, _, Blake2b256> = Verifier::from_borrowed(&data, Ed25519);
+
+            if let Some(revocation) = method.revocation()? {
+              verifier.set_revocation(revocation);
+            }
+
+            verifier.__verify(that, &[])?;
+          }
           (_, _) => {
             return Err(Error::InvalidMethodType);
           }
         }
       }
+      MethodType::X25519KeyAgreementKey2019 => {
+        return Err(Error::InvalidMethodType);
+      }
     }
 
     Ok(())
   }
+
+  /// Verifies the signature of the provided data was created by the
+  /// verification method matching `query`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the signature references a different verification method or
+  /// [`verify`][`DocumentVerifier::verify`] fails.
+  pub fn verify_with<'query, X, Q>(&self, that: &X, query: Q) -> Result<()>
+  where
+    X: Serialize + TrySignature,
+    Q: Into<MethodQuery<'query>>,
+  {
+    let expected: &Method<U> = self.document.try_resolve(query)?;
+    let signature: &Signature = that.try_signature()?;
+    let method: &Method<U> = self.document.try_resolve(signature)?;
+
+    if method.id() != expected.id() {
+      return Err(Error::InvalidSignatureMethod);
+    }
+
+    self.verify(that)
+  }
 }
@@ -3,11 +3,15 @@
 
 mod document;
 mod properties;
+mod signature_type;
+mod suite_registry;
 mod traits;
 
 pub use self::document::DocumentSigner;
 pub use self::document::DocumentVerifier;
 pub use self::properties::Properties;
+pub use self::signature_type::SignatureType;
+pub use self::suite_registry::SignatureSuiteRegistry;
 pub use self::traits::Revocation;
 
 #[cfg(test)]
@@ -0,0 +1,58 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::str::FromStr;
+use identity_core::crypto::SignatureValue;
+use identity_core::error::Error as CoreError;
+use identity_core::utils::decode_b58;
+
+use crate::error::Error;
+use crate::error::Result;
+
+/// Supported signature suites for `Ed25519VerificationKey2018` methods.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[non_exhaustive]
+pub enum SignatureType {
+  JcsEd25519Signature2020,
+  Ed25519Signature2020,
+}
+
+impl SignatureType {
+  pub const fn as_str(self) -> &'static str {
+    match self {
+      Self::JcsEd25519Signature2020 => "JcsEd25519Signature2020",
+      Self::Ed25519Signature2020 => "Ed25519Signature2020",
+    }
+  }
+
+  /// Decodes the raw signature bytes from a signature `value` created with
+  /// this suite.
+  pub fn decode_value(self, value: &SignatureValue) -> Result<Vec<u8>> {
+    let encoded: Option<&str> = match self {
+      Self::JcsEd25519Signature2020 => value.as_signature(),
+      Self::Ed25519Signature2020 => value.as_proof().and_then(|proof| proof.strip_prefix('z')),
+    };
+
+    let encoded: &str = encoded.ok_or(Error::CoreError(CoreError::InvalidProofValue))?;
+
+    decode_b58(encoded).map_err(Into::into)
+  }
+}
+
+impl Default for SignatureType {
+  fn default() -> Self {
+    Self::JcsEd25519Signature2020
+  }
+}
+
+impl FromStr for SignatureType {
+  type Err = Error;
+
+  fn from_str(string: &str) -> Result<Self, Self::Err> {
+    match string {
+      "JcsEd25519Signature2020" => Ok(Self::JcsEd25519Signature2020),
+      "Ed25519Signature2020" => Ok(Self::Ed25519Signature2020),
+      _ => Err(Error::UnknownSignatureType),
+    }
+  }
+}
@@ -0,0 +1,92 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use identity_core::crypto::Ed25519Signature2020;
+use identity_core::crypto::JcsEd25519Signature2020;
+use identity_core::crypto::Signature;
+use identity_core::crypto::SignatureVerify;
+use identity_core::crypto::TrySignature;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::error::Error;
+use crate::error::Result;
+
+type Suite = Box<dyn SignatureVerify + Send + Sync>;
+
+/// A collection of signature suites used to verify signatures created with
+/// `Ed25519` verification methods, keyed by the proof `type`.
+///
+/// The default registry contains the `JcsEd25519Signature2020` and
+/// `Ed25519Signature2020` suites.
+pub struct SignatureSuiteRegistry {
+  suites: BTreeMap<String, Suite>,
+}
+
+impl SignatureSuiteRegistry {
+  /// Creates a new `SignatureSuiteRegistry` without any registered suites.
+  pub fn new() -> Self {
+    Self {
+      suites: BTreeMap::new(),
+    }
+  }
+
+  /// Registers a signature `suite` under its name; returns `true` if a suite
+  /// with the same name was replaced.
+  pub fn register<S>(&mut self, suite: S) -> bool
+  where
+    S: SignatureVerify + Send + Sync + 'static,
+  {
+    self.suites.insert(suite.name(), Box::new(suite)).is_some()
+  }
+
+  /// Returns `true` if a suite is registered for the given proof `type`.
+  pub fn contains(&self, type_: &str) -> bool {
+    self.suites.contains_key(type_)
+  }
+
+  /// Returns an iterator over the proof types of all registered suites.
+  pub fn types(&self) -> impl Iterator<Item = &str> {
+    self.suites.keys().map(String::as_str)
+  }
+
+  /// Verifies the signature of the provided data with the suite registered
+  /// for the proof `type`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if no suite is registered for the proof `type` or the
+  /// verification operation fails.
+  pub fn verify<X>(&self, that: &X, public: &[u8]) -> Result<()>
+  where
+    X: Serialize + TrySignature,
+  {
+    let signature: &Signature = that.try_signature()?;
+
+    self
+      .suites
+      .get(signature.type_())
+      .ok_or(Error::UnknownSignatureType)?
+      .__verify(that, public)
+      .map_err(Into::into)
+  }
+}
+
+impl Default for SignatureSuiteRegistry {
+  fn default() -> Self {
+    let mut this: Self = Self::new();
+
+    this.register(JcsEd25519Signature2020);
+    this.register(Ed25519Signature2020);
+    this
+  }
+}
+
+impl Debug for SignatureSuiteRegistry {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_set().entries(self.types()).finish()
+  }
+}
@@ -1,22 +1,33 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use identity_core::common::Timestamp;
+use identity_core::convert::FromJson;
+use identity_core::crypto::merkle_key::Blake2b256;
+use identity_core::crypto::merkle_key::MerkleDigest;
 use identity_core::crypto::merkle_key::MerkleKey;
 use identity_core::crypto::merkle_key::Sha256;
 use identity_core::crypto::merkle_tree::Hash;
 use identity_core::crypto::merkle_tree::Proof;
+use identity_core::crypto::JcsEd25519Signature2020;
 use identity_core::crypto::KeyCollection;
 use identity_core::crypto::KeyPair;
 use identity_core::crypto::PublicKey;
 use identity_core::crypto::SecretKey;
 use identity_core::crypto::SetSignature;
 use identity_core::crypto::Signature;
+use identity_core::crypto::SignatureValue;
 use identity_core::crypto::TrySignature;
 use identity_core::crypto::TrySignatureMut;
+use identity_core::error::Error as CoreError;
+use identity_core::utils::encode_b58;
 
 use crate::did::DID;
 use crate::document::Document;
+use crate::error::Error;
 use crate::verifiable::Properties;
+use crate::verifiable::SignatureSuiteRegistry;
+use crate::verifiable::SignatureType;
 use crate::verification::Method;
 use crate::verification::MethodData;
 use crate::verification::MethodType;
@@ -52,27 +63,92 @@ impl SetSignature for That {
   }
 }
 
-// ===========================================================================
-// ===========================================================================
+fn controller() -> DID {
+  "did:example:1234".parse().unwrap()
+}
 
-#[test]
-fn test_sign_verify_this_ed25519() {
-  let key: KeyPair = KeyPair::new_ed25519().unwrap();
-  let controller: DID = "did:example:1234".parse().unwrap();
+fn method(fragment: &str, key_type: MethodType, key_data: MethodData) -> Method {
+  let controller: DID = controller();
 
-  let method: Method = Method::builder(Default::default())
-    .id(controller.join("#key-1").unwrap())
-    .controller(controller.clone())
-    .key_type(MethodType::Ed25519VerificationKey2018)
-    .key_data(MethodData::new_b58(key.public()))
+  Method::builder(Default::default())
+    .id(controller.join(fragment).unwrap())
+    .controller(controller)
+    .key_type(key_type)
+    .key_data(key_data)
     .build()
-    .unwrap();
+    .unwrap()
+}
 
-  let mut document: Document<Properties> = Document::builder(Default::default())
-    .id(controller)
+fn document(method: Method) -> Document<Properties> {
+  Document::builder(Default::default())
+    .id(controller())
     .verification_method(method)
     .build()
-    .unwrap();
+    .unwrap()
+}
+
+// Creates a document with the public key of a new Ed25519 keypair as method
+// `#key-1`.
+fn ed25519_document() -> (KeyPair, Document<Properties>) {
+  let key: KeyPair = KeyPair::new_ed25519().unwrap();
+  let method: Method = method(
+    "#key-1",
+    MethodType::Ed25519VerificationKey2018,
+    MethodData::new_b58(key.public()),
+  );
+
+  (key, document(method))
+}
+
+// Creates a JSON-LD credential which can be signed with `Ed25519Signature2020`.
+fn credential() -> Properties {
+  Properties::from_json(
+    r#"{
+      "@context": [
+        "https://www.w3.org/2018/credentials/v1",
+        "https://w3id.org/security/suites/ed25519-2020/v1"
+      ],
+      "type": "VerifiableCredential",
+      "issuer": "did:example:1234",
+      "issuanceDate": "2021-01-01T00:00:00Z",
+      "credentialSubject": {
+        "id": "did:example:abcd"
+      }
+    }"#,
+  )
+  .unwrap()
+}
+
+// Creates a document with the Merkle root of `keys` as method `#key-collection`.
+fn merkle_document<D>(keys: &KeyCollection) -> Document<Properties>
+where
+  D: MerkleDigest,
+{
+  let root: Hash<D> = keys.merkle_root();
+  let mkey: Vec<u8> = MerkleKey::encode_ed25519_key::<D>(&root);
+
+  document(method(
+    "#key-collection",
+    MethodType::MerkleKeyCollection2021,
+    MethodData::new_b58(mkey),
+  ))
+}
+
+// Signs a new `That` with method `#key-1` of `document`.
+fn sign_that(document: &Document<Properties>, key: &KeyPair) -> That {
+  let mut that: That = That::new(123);
+
+  document.signer(key.secret()).method("#key-1").sign(&mut that).unwrap();
+
+  that
+}
+
+// ===========================================================================
+// ===========================================================================
+
+#[test]
+fn test_sign_verify_this_ed25519() {
+  let (key, mut document): (KeyPair, Document<Properties>) = ed25519_document();
 
   assert!(document.verify_this().is_err());
 
@@ -82,31 +158,282 @@ fn test_sign_verify_this_ed25519() {
 }
 
 #[test]
-fn test_sign_verify_that_merkle_key_ed25519_sha256() {
-  let total: usize = 1 << 11;
-  let index: usize = 1 << 9;
+fn test_verify_this_expired_method() {
+  let (key, mut document): (KeyPair, Document<Properties>) = ed25519_document();
+  let expires: Timestamp = Timestamp::parse("2000-01-01T00:00:00Z").unwrap();
 
-  let keys: KeyCollection = KeyCollection::new_ed25519(total).unwrap();
-  let controller: DID = "did:example:1234".parse().unwrap();
+  document.try_resolve_mut("#key-1").unwrap().set_expires(Some(expires));
+  document.sign_this("#key-1", key.secret().as_ref()).unwrap();
 
-  let root: Hash<Sha256> = keys.merkle_root();
-  let proof: Proof<Sha256> = keys.merkle_proof(index).unwrap();
-  let mkey: Vec<u8> = MerkleKey::encode_ed25519_key::<Sha256>(&root);
+  assert!(matches!(document.verify_this(), Err(Error::InvalidMethodExpired)));
+}
 
-  let method: Method = Method::builder(Default::default())
-    .id(controller.join("#key-collection").unwrap())
-    .controller(controller.clone())
-    .key_type(MethodType::MerkleKeyCollection2021)
-    .key_data(MethodData::new_b58(mkey))
-    .build()
-    .unwrap();
+#[test]
+fn test_sign_verify_that_named_method() {
+  let key1: KeyPair = KeyPair::new_ed25519().unwrap();
+  let key2: KeyPair = KeyPair::new_ed25519().unwrap();
 
   let document: Document<Properties> = Document::builder(Default::default())
-    .id(controller)
-    .verification_method(method)
+    .id(controller())
+    .authentication(method(
+      "#key-1",
+      MethodType::Ed25519VerificationKey2018,
+      MethodData::new_b58(key1.public()),
+    ))
+    .verification_method(method(
+      "#key-2",
+      MethodType::Ed25519VerificationKey2018,
+      MethodData::new_b58(key2.public()),
+    ))
     .build()
     .unwrap();
 
+  // Methods are resolved by the fragment named in the query
+  assert_eq!(document.resolve("#key-1").unwrap().id().fragment(), Some("key-1"));
+  assert_eq!(document.resolve("#key-2").unwrap().id().fragment(), Some("key-2"));
+
+  let mut that1: That = That::new(123);
+  let mut that2: That = That::new(123);
+
+  document
+    .signer(key1.secret())
+    .method("#key-1")
+    .sign(&mut that1)
+    .unwrap();
+  document
+    .signer(key2.secret())
+    .method("#key-2")
+    .sign(&mut that2)
+    .unwrap();
+
+  assert!(document.verifier().verify(&that1).is_ok());
+  assert!(document.verifier().verify(&that2).is_ok());
+
+  // A valid signature from a method other than the expected one is rejected
+  assert!(document.verifier().verify_with(&that1, "#key-1").is_ok());
+  assert!(document.verifier().verify_with(&that2, "#key-2").is_ok());
+  assert!(matches!(
+    document.verifier().verify_with(&that2, "#key-1"),
+    Err(Error::InvalidSignatureMethod)
+  ));
+
+  // The proof names the signer - a signature from another key fails
+  let signature: Signature = that2.proof.take().unwrap();
+  let mut forged: Signature = Signature::new(signature.type_(), "#key-1");
+
+  forged.set_value(signature.value().clone());
+
+  if let Some(created) = signature.created() {
+    forged.set_created(created);
+  }
+
+  that2.set_signature(forged);
+
+  assert!(document.verifier().verify(&that2).is_err());
+}
+
+#[test]
+fn test_sign_verify_that_expired_method() {
+  let (key, mut document): (KeyPair, Document<Properties>) = ed25519_document();
+  let expires: Timestamp = Timestamp::parse("2999-01-01T00:00:00Z").unwrap();
+
+  document.try_resolve_mut("#key-1").unwrap().set_expires(Some(expires));
+
+  let that: That = sign_that(&document, &key);
+
+  assert!(document.verifier().verify(&that).is_ok());
+
+  let expires: Timestamp = Timestamp::parse("2000-01-01T00:00:00Z").unwrap();
+
+  document.try_resolve_mut("#key-1").unwrap().set_expires(Some(expires));
+
+  assert!(matches!(
+    document.verifier().verify(&that),
+    Err(Error::InvalidMethodExpired)
+  ));
+}
+
+#[test]
+fn test_sign_verify_that_ed25519_2020() {
+  let (key, document): (KeyPair, Document<Properties>) = ed25519_document();
+  let mut that: Properties = credential();
+
+  document
+    .signer(key.secret())
+    .method("#key-1")
+    .suite(SignatureType::Ed25519Signature2020)
+    .sign(&mut that)
+    .unwrap();
+
+  let proof: &Signature = that.proof().unwrap();
+
+  assert_eq!(proof.type_(), "Ed25519Signature2020");
+  assert_eq!(proof.verification_method(), "did:example:1234#key-1");
+  assert_eq!(proof.purpose(), Some("assertionMethod"));
+  assert!(proof.created().is_some());
+  assert!(proof.value().as_proof().unwrap().starts_with('z'));
+  assert!(document.verifier().verify(&that).is_ok());
+
+  that.insert("issuanceDate".into(), "2021-01-02T00:00:00Z".into());
+
+  assert!(document.verifier().verify(&that).is_err());
+}
+
+#[test]
+fn test_sign_verify_that_domain_challenge() {
+  let (key, document): (KeyPair, Document<Properties>) = ed25519_document();
+  let mut that: That = That::new(123);
+
+  document
+    .signer(key.secret())
+    .method("#key-1")
+    .domain("example.com")
+    .challenge("1f44d55f")
+    .sign(&mut that)
+    .unwrap();
+
+  let proof: &Signature = that.proof.as_ref().unwrap();
+
+  assert_eq!(proof.domain(), Some("example.com"));
+  assert_eq!(proof.challenge(), Some("1f44d55f"));
+
+  assert!(document.verifier().verify(&that).is_ok());
+  assert!(document
+    .verifier()
+    .domain("example.com")
+    .challenge("1f44d55f")
+    .verify(&that)
+    .is_ok());
+
+  assert!(matches!(
+    document.verifier().domain("example.org").verify(&that),
+    Err(Error::InvalidProofDomain)
+  ));
+  assert!(matches!(
+    document.verifier().challenge("99612b24").verify(&that),
+    Err(Error::InvalidProofChallenge)
+  ));
+
+  // The domain and challenge are covered by the signature
+  that.proof.as_mut().unwrap().set_challenge("99612b24");
+
+  assert!(document.verifier().verify(&that).is_err());
+}
+
+#[test]
+fn test_sign_verify_that_created() {
+  let (key, document): (KeyPair, Document<Properties>) = ed25519_document();
+
+  // Proofs are deterministic unless a creation time is requested
+  let that1: That = sign_that(&document, &key);
+  let that2: That = sign_that(&document, &key);
+
+  let proof1: &Signature = that1.proof.as_ref().unwrap();
+  let proof2: &Signature = that2.proof.as_ref().unwrap();
+
+  assert_eq!(proof1.created(), None);
+  assert_eq!(proof1.value(), proof2.value());
+
+  let created: Timestamp = Timestamp::parse("2021-01-01T00:00:00Z").unwrap();
+  let mut that: That = That::new(123);
+
+  document
+    .signer(key.secret())
+    .method("#key-1")
+    .created(created)
+    .sign(&mut that)
+    .unwrap();
+
+  assert_eq!(that.proof.as_ref().unwrap().created(), Some(created));
+  assert!(document.verifier().verify(&that).is_ok());
+
+  // The creation time is covered by the signature
+  that.proof.as_mut().unwrap().set_created(Timestamp::now());
+
+  assert!(document.verifier().verify(&that).is_err());
+}
+
+#[test]
+fn test_sign_verify_that_purpose() {
+  let (key, document): (KeyPair, Document<Properties>) = ed25519_document();
+  let mut that: That = That::new(123);
+
+  document
+    .signer(key.secret())
+    .method("#key-1")
+    .purpose("assertionMethod")
+    .sign(&mut that)
+    .unwrap();
+
+  assert_eq!(that.proof.as_ref().unwrap().purpose(), Some("assertionMethod"));
+
+  assert!(document.verifier().purpose("assertionMethod").verify(&that).is_ok());
+  assert!(matches!(
+    document.verifier().purpose("authentication").verify(&that),
+    Err(Error::InvalidProofPurpose)
+  ));
+
+  // The purpose is covered by the signature
+  that.proof.as_mut().unwrap().set_purpose("authentication");
+
+  assert!(document.verifier().verify(&that).is_err());
+}
+
+#[test]
+fn test_verify_that_suite_registry() {
+  let (key, document): (KeyPair, Document<Properties>) = ed25519_document();
+  let mut that: Properties = credential();
+
+  document
+    .signer(key.secret())
+    .method("#key-1")
+    .suite(SignatureType::Ed25519Signature2020)
+    .sign(&mut that)
+    .unwrap();
+
+  let mut registry: SignatureSuiteRegistry = SignatureSuiteRegistry::new();
+
+  assert!(!registry.register(JcsEd25519Signature2020));
+  assert!(!registry.contains("Ed25519Signature2020"));
+
+  // Signatures of unregistered suites are rejected
+  assert!(matches!(
+    document.verifier().registry(&registry).verify(&that),
+    Err(Error::UnknownSignatureType)
+  ));
+
+  registry = SignatureSuiteRegistry::default();
+
+  assert!(document.verifier().registry(&registry).verify(&that).is_ok());
+}
+
+#[test]
+fn test_verify_that_invalid_signature_length() {
+  let (key, document): (KeyPair, Document<Properties>) = ed25519_document();
+  let mut that: That = sign_that(&document, &key);
+
+  // Append a trailing byte to an otherwise valid signature
+  let proof: &mut Signature = that.proof.as_mut().unwrap();
+  let mut value: Vec<u8> = SignatureType::default().decode_value(proof.value()).unwrap();
+
+  value.push(0);
+  proof.set_value(SignatureValue::Signature(encode_b58(&value)));
+
+  assert!(matches!(
+    document.verifier().verify(&that).unwrap_err(),
+    Error::CoreError(CoreError::InvalidSigLength(65, 64))
+  ));
+}
+
+#[test]
+fn test_sign_verify_that_merkle_key_ed25519_sha256() {
+  let total: usize = 1 << 11;
+  let index: usize = 1 << 9;
+
+  let keys: KeyCollection = KeyCollection::new_ed25519(total).unwrap();
+  let proof: Proof<Sha256> = keys.merkle_proof(index).unwrap();
+  let document: Document<Properties> = merkle_document::<Sha256>(&keys);
+
   let public: &PublicKey = keys.public(index).unwrap();
   let secret: &SecretKey = keys.secret(index).unwrap();
 
@@ -123,3 +450,63 @@ fn test_sign_verify_that_merkle_key_ed25519_sha256() {
 
   assert!(document.verifier().verify(&that).is_ok());
 }
+
+#[test]
+fn test_sign_verify_that_merkle_key_ed25519_blake2b_256() {
+  let keys: KeyCollection = KeyCollection::new_ed25519(8).unwrap();
+  let proof: Proof<Blake2b256> = keys.merkle_proof(3).unwrap();
+  let document: Document<Properties> = merkle_document::<Blake2b256>(&keys);
+
+  let mut that: That = That::new(123);
+
+  document
+    .signer(keys.secret(3).unwrap())
+    .method("#key-collection")
+    .merkle_key((keys.public(3).unwrap(), &proof))
+    .sign(&mut that)
+    .unwrap();
+
+  assert!(document.verifier().verify(&that).is_ok());
+
+  // A proof of membership of the wrong digest is rejected
+  let proof: Proof<Sha256> = keys.merkle_proof(3).unwrap();
+
+  assert!(document
+    .signer(keys.secret(3).unwrap())
+    .method("#key-collection")
+    .merkle_key((keys.public(3).unwrap(), &proof))
+    .sign(&mut That::new(123))
+    .is_err());
+}
+
+#[test]
+fn test_verify_this_merkle_key() {
+  let keys: KeyCollection = KeyCollection::new_ed25519(8).unwrap();
+  let proof: Proof<Sha256> = keys.merkle_proof(0).unwrap();
+  let document: Document<Properties> = merkle_document::<Sha256>(&keys);
+
+  let mut signed: Document<Properties> = document.clone();
+
+  // A proof without the public key and proof of membership
+  signed.set_proof(Signature::new("JcsEd25519Signature2020", "#key-collection"));
+
+  assert!(matches!(
+    signed.verify_this_with_revocation().unwrap_err(),
+    Error::InvalidSignatureMerkleKey
+  ));
+
+  document
+    .signer(keys.secret(0).unwrap())
+    .method("#key-collection")
+    .merkle_key((keys.public(0).unwrap(), &proof))
+    .sign(&mut signed)
+    .unwrap();
+
+  assert!(signed.verify_this_with_revocation().is_ok());
+
+  // Revocation flags are only available through `verify_this_with_revocation`
+  assert!(matches!(
+    signed.verify_this().unwrap_err(),
+    Error::InvalidMethodRevocation
+  ));
+}
@@ -7,6 +7,7 @@ use crate::verification::Method;
 use crate::verification::MethodData;
 use crate::verification::MethodType;
 use identity_core::common::Object;
+use identity_core::common::Timestamp;
 
 /// A `MethodBuilder` is used to generate a customized `Method`.
 #[derive(Clone, Debug, Default)]
@@ -15,6 +16,7 @@ pub struct MethodBuilder<T = Object> {
   pub(crate) controller: Option<DID>,
   pub(crate) key_type: Option<MethodType>,
   pub(crate) key_data: Option<MethodData>,
+  pub(crate) expires: Option<Timestamp>,
   pub(crate) properties: T,
 }
 
@@ -26,6 +28,7 @@ impl<T> MethodBuilder<T> {
       controller: None,
       key_type: None,
       key_data: None,
+      expires: None,
       properties,
     }
   }
@@ -58,6 +61,13 @@ impl<T> MethodBuilder<T> {
     self
   }
 
+  /// Sets the `expires` value of the generated verification `Method`.
+  #[must_use]
+  pub fn expires(mut self, value: Timestamp) -> Self {
+    self.expires = Some(value);
+    self
+  }
+
   /// Returns a new `Method` based on the `MethodBuilder` configuration.
   pub fn build(self) -> Result<Method<T>> {
     Method::from_builder(self)
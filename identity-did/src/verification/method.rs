@@ -7,6 +7,7 @@ use core::fmt::Formatter;
 use core::fmt::Result as FmtResult;
 use core::iter::once;
 use identity_core::common::Object;
+use identity_core::common::Timestamp;
 use identity_core::convert::ToJson;
 use serde::Serialize;
 
@@ -27,6 +28,8 @@ pub struct Method<T = Object> {
   pub(crate) key_type: MethodType,
   #[serde(flatten)]
   pub(crate) key_data: MethodData,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) expires: Option<Timestamp>,
   #[serde(flatten)]
   pub(crate) properties: T,
 }
@@ -46,6 +49,7 @@ impl<T> Method<T> {
       controller: builder.controller.ok_or(Error::BuilderInvalidMethodController)?,
       key_type: builder.key_type.ok_or(Error::BuilderInvalidMethodType)?,
       key_data: builder.key_data.ok_or(Error::BuilderInvalidMethodData)?,
+      expires: builder.expires,
       properties: builder.properties,
     })
   }
@@ -90,6 +94,23 @@ impl<T> Method<T> {
     &mut self.key_data
   }
 
+  /// Returns the timestamp after which the verification `Method` is expired, if any.
+  pub fn expires(&self) -> Option<Timestamp> {
+    self.expires
+  }
+
+  /// Sets the timestamp after which the verification `Method` is expired.
+  pub fn set_expires(&mut self, value: Option<Timestamp>) {
+    self.expires = value;
+  }
+
+  /// Returns `true` if the verification `Method` is expired at `timestamp`.
+  ///
+  /// Methods without an `expires` timestamp never expire.
+  pub fn is_expired_at(&self, timestamp: Timestamp) -> bool {
+    matches!(self.expires, Some(expires) if timestamp >= expires)
+  }
+
   /// Returns a reference to the custom verification `Method` properties.
   pub fn properties(&self) -> &T {
     &self.properties
@@ -100,6 +121,35 @@ impl<T> Method<T> {
     &mut self.properties
   }
 
+  /// Changes the `type` of the verification `Method`, re-encoding the key
+  /// data in the representation of the new type.
+  ///
+  /// The key material and `id` of the method are preserved. Migrations are
+  /// only defined between `Ed25519VerificationKey2018` and
+  /// `Ed25519VerificationKey2020`.
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::InvalidMethodMigration`] for any other combination of
+  /// the current and new `type`.
+  pub fn migrate_key_type(&mut self, key_type: MethodType) -> Result<()> {
+    let key_data: MethodData = match (self.key_type, key_type) {
+      (current, target) if current == target => return Ok(()),
+      (MethodType::Ed25519VerificationKey2018, MethodType::Ed25519VerificationKey2020) => {
+        MethodData::new_multibase(self.key_data.try_decode()?)
+      }
+      (MethodType::Ed25519VerificationKey2020, MethodType::Ed25519VerificationKey2018) => {
+        MethodData::new_b58(self.key_data.try_decode()?)
+      }
+      (_, _) => return Err(Error::InvalidMethodMigration),
+    };
+
+    self.key_type = key_type;
+    self.key_data = key_data;
+
+    Ok(())
+  }
+
   pub fn try_into_fragment(&self) -> Result<String> {
     self
       .id
@@ -132,3 +182,68 @@ impl<T> AsRef<DID> for Method<T> {
     self.id()
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use identity_core::utils::encode_b58;
+
+  use super::*;
+
+  fn method(key_type: MethodType, key_data: MethodData) -> Method {
+    MethodBuilder::default()
+      .id("did:example:1234#key-1".parse().unwrap())
+      .controller("did:example:1234".parse().unwrap())
+      .key_type(key_type)
+      .key_data(key_data)
+      .build()
+      .unwrap()
+  }
+
+  #[test]
+  fn test_migrate_key_type() {
+    let mut method: Method = method(MethodType::Ed25519VerificationKey2018, MethodData::new_b58(b"#key-1"));
+
+    method.migrate_key_type(MethodType::Ed25519VerificationKey2020).unwrap();
+
+    assert_eq!(method.id(), "did:example:1234#key-1");
+    assert_eq!(method.key_type(), MethodType::Ed25519VerificationKey2020);
+    assert_eq!(method.key_data(), &MethodData::new_multibase(b"#key-1"));
+    assert_eq!(method.key_data().try_decode().unwrap(), b"#key-1");
+
+    // Multibase keys are multicodec-prefixed
+    let data: MethodData = MethodData::PublicKeyMultibase("z6MkmM42vxfqZQsv4ehtTjFFxQ4sQKS2w6WR7emozFAn5cxu".into());
+    let public: Vec<u8> = data.try_decode().unwrap();
+
+    assert_eq!(public.len(), 32);
+    assert_eq!(MethodData::new_multibase(&public), data);
+    assert!(MethodData::PublicKeyMultibase(format!("z{}", encode_b58(&public)))
+      .try_decode()
+      .is_err());
+
+    method.migrate_key_type(MethodType::Ed25519VerificationKey2018).unwrap();
+
+    assert_eq!(method.key_data(), &MethodData::new_b58(b"#key-1"));
+
+    // Ed25519 keys can't be migrated to key agreement keys
+    assert!(matches!(
+      method.migrate_key_type(MethodType::X25519KeyAgreementKey2019),
+      Err(Error::InvalidMethodMigration)
+    ));
+  }
+
+  #[test]
+  fn test_migrate_key_type_unsupported() {
+    let mut method: Method = method(MethodType::X25519KeyAgreementKey2019, MethodData::new_b58(b"#key-1"));
+
+    assert!(matches!(
+      method.migrate_key_type(MethodType::Ed25519VerificationKey2020),
+      Err(Error::InvalidMethodMigration)
+    ));
+
+    // Migrating to the current type is a no-op
+    method.migrate_key_type(MethodType::X25519KeyAgreementKey2019).unwrap();
+
+    assert_eq!(method.key_type(), MethodType::X25519KeyAgreementKey2019);
+    assert_eq!(method.key_data(), &MethodData::new_b58(b"#key-1"));
+  }
+}
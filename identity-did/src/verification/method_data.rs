@@ -18,9 +18,13 @@ pub enum MethodData {
   PublicKeyBase58(String),
   PublicKeyHex(String),
   PublicKeyJwk(Object),
+  PublicKeyMultibase(String),
 }
 
 impl MethodData {
+  const MULTIBASE_B58: char = 'z';
+  const MULTICODEC_ED25519: [u8; 2] = [0xed, 0x01];
+
   /// Creates a new `MethodData` variant with base16-encoded content.
   pub fn new_b16(data: impl AsRef<[u8]>) -> Self {
     Self::PublicKeyHex(encode_b16(&data))
@@ -31,6 +35,19 @@ impl MethodData {
     Self::PublicKeyBase58(encode_b58(&data))
   }
 
+  /// Creates a new `MethodData` variant with multibase-encoded content.
+  ///
+  /// The content is an Ed25519 public key, prefixed with the `ed25519-pub`
+  /// multicodec (`0xed01`) and encoded as base58-btc, identified by the `z`
+  /// prefix - as required by `Ed25519VerificationKey2020`.
+  pub fn new_multibase(data: impl AsRef<[u8]>) -> Self {
+    let mut bytes: Vec<u8> = Self::MULTICODEC_ED25519.to_vec();
+
+    bytes.extend_from_slice(data.as_ref());
+
+    Self::PublicKeyMultibase(format!("{}{}", Self::MULTIBASE_B58, encode_b58(&bytes)))
+  }
+
   /// Returns a `Vec<u8>` containing the decoded bytes of the `MethodData`.
   ///
   /// This is generally a public key identified by a `MethodType` value.
@@ -38,12 +55,23 @@ impl MethodData {
   /// # Errors
   ///
   /// Decoding can fail if `MethodData` has invalid content or cannot be
-  /// represented as a vector of bytes.
+  /// represented as a vector of bytes. Multibase content must be a base58-btc
+  /// encoded, multicodec-prefixed Ed25519 public key.
   pub fn try_decode(&self) -> Result<Vec<u8>> {
     match self {
       Self::PublicKeyBase58(input) => decode_b58(input).map_err(|_| Error::InvalidKeyDataBase58),
       Self::PublicKeyHex(input) => decode_b16(input).map_err(|_| Error::InvalidKeyDataBase16),
       Self::PublicKeyJwk(_) => Err(Error::InvalidKeyData),
+      Self::PublicKeyMultibase(input) => input
+        .strip_prefix(Self::MULTIBASE_B58)
+        .ok_or(Error::InvalidKeyDataMultibase)
+        .and_then(|input| decode_b58(input).map_err(|_| Error::InvalidKeyDataMultibase))
+        .and_then(|data| {
+          data
+            .strip_prefix(&Self::MULTICODEC_ED25519[..])
+            .map(<[u8]>::to_vec)
+            .ok_or(Error::InvalidKeyDataMultibase)
+        }),
     }
   }
 }
@@ -28,6 +28,19 @@ impl MethodScope {
       Self::CapabilityInvocation => "CapabilityInvocation",
     }
   }
+
+  /// Returns the name of the DID Document property of the scope, e.g.
+  /// `assertionMethod`.
+  pub const fn as_property(&self) -> &'static str {
+    match self {
+      Self::VerificationMethod => "verificationMethod",
+      Self::Authentication => "authentication",
+      Self::AssertionMethod => "assertionMethod",
+      Self::KeyAgreement => "keyAgreement",
+      Self::CapabilityDelegation => "capabilityDelegation",
+      Self::CapabilityInvocation => "capabilityInvocation",
+    }
+  }
 }
 
 impl Default for MethodScope {
@@ -40,14 +53,41 @@ impl FromStr for MethodScope {
   type Err = Error;
 
   fn from_str(string: &str) -> Result<Self, Self::Err> {
+    // Accept both the scope name and the DID Document property name
     match string {
-      "VerificationMethod" => Ok(Self::VerificationMethod),
-      "Authentication" => Ok(Self::Authentication),
-      "AssertionMethod" => Ok(Self::AssertionMethod),
-      "KeyAgreement" => Ok(Self::KeyAgreement),
-      "CapabilityDelegation" => Ok(Self::CapabilityDelegation),
-      "CapabilityInvocation" => Ok(Self::CapabilityInvocation),
+      "VerificationMethod" | "verificationMethod" => Ok(Self::VerificationMethod),
+      "Authentication" | "authentication" => Ok(Self::Authentication),
+      "AssertionMethod" | "assertionMethod" => Ok(Self::AssertionMethod),
+      "KeyAgreement" | "keyAgreement" => Ok(Self::KeyAgreement),
+      "CapabilityDelegation" | "capabilityDelegation" => Ok(Self::CapabilityDelegation),
+      "CapabilityInvocation" | "capabilityInvocation" => Ok(Self::CapabilityInvocation),
       _ => Err(Error::UnknownMethodScope),
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_from_str() {
+    assert_eq!(
+      "CapabilityInvocation".parse::<MethodScope>().unwrap(),
+      MethodScope::CapabilityInvocation
+    );
+    assert_eq!(
+      "capabilityInvocation".parse::<MethodScope>().unwrap(),
+      MethodScope::CapabilityInvocation
+    );
+    assert_eq!(
+      "CapabilityDelegation".parse::<MethodScope>().unwrap(),
+      MethodScope::CapabilityDelegation
+    );
+    assert_eq!(
+      "capabilityDelegation".parse::<MethodScope>().unwrap(),
+      MethodScope::CapabilityDelegation
+    );
+    assert!("capability".parse::<MethodScope>().is_err());
+  }
+}
@@ -11,14 +11,18 @@ use crate::error::Result;
 #[non_exhaustive]
 pub enum MethodType {
   Ed25519VerificationKey2018,
+  Ed25519VerificationKey2020,
   MerkleKeyCollection2021,
+  X25519KeyAgreementKey2019,
 }
 
 impl MethodType {
   pub const fn as_str(self) -> &'static str {
     match self {
       Self::Ed25519VerificationKey2018 => "Ed25519VerificationKey2018",
+      Self::Ed25519VerificationKey2020 => "Ed25519VerificationKey2020",
       Self::MerkleKeyCollection2021 => "MerkleKeyCollection2021",
+      Self::X25519KeyAgreementKey2019 => "X25519KeyAgreementKey2019",
     }
   }
 }
@@ -29,7 +33,9 @@ impl FromStr for MethodType {
   fn from_str(string: &str) -> Result<Self, Self::Err> {
     match string {
       "Ed25519VerificationKey2018" => Ok(Self::Ed25519VerificationKey2018),
+      "Ed25519VerificationKey2020" => Ok(Self::Ed25519VerificationKey2020),
       "MerkleKeyCollection2021" => Ok(Self::MerkleKeyCollection2021),
+      "X25519KeyAgreementKey2019" => Ok(Self::X25519KeyAgreementKey2019),
       _ => Err(Error::UnknownMethodType),
     }
   }
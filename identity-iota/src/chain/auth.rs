@@ -69,6 +69,13 @@ impl AuthChain {
     Ok(Self { current, history: None })
   }
 
+  // Creates an `AuthChain` ending at `current`, a document already validated
+  // as part of another auth chain; used to resolve the diffs of previous auth
+  // documents.
+  pub(crate) fn __epoch(current: Document) -> Self {
+    Self { current, history: None }
+  }
+
   /// Returns a reference to the latest document in the auth chain.
   pub fn current(&self) -> &Document {
     &self.current
@@ -84,6 +91,11 @@ impl AuthChain {
     self.current.message_id()
   }
 
+  /// Returns a slice of the previous documents in the auth chain, oldest first.
+  pub fn history(&self) -> &[Document] {
+    self.history.as_deref().unwrap_or_default()
+  }
+
   /// Adds a new document to the auth chain.
   ///
   /// # Errors
@@ -14,6 +14,7 @@ use crate::did::DocumentDiff;
 use crate::did::DID;
 use crate::error::Result;
 use crate::tangle::MessageId;
+use crate::tangle::TangleRef;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct DocumentChain {
@@ -121,6 +122,42 @@ impl DocumentChain {
     Self::__diff_message_id(&self.auth_chain, &self.diff_chain)
   }
 
+  /// Returns the document as it was after the message with the given
+  /// `message_id` was applied, if the message is part of the chain.
+  ///
+  /// Only diffs of the latest auth document can be resolved; `message_id`
+  /// may refer to any auth document in the chain. See
+  /// [`Client::read_document_at`][crate::client::Client::read_document_at]
+  /// to resolve diffs of previous auth documents.
+  pub fn document_at(&self, message_id: &MessageId) -> Result<Option<Document>> {
+    let auth: Option<&Document> = self
+      .auth_chain
+      .history()
+      .iter()
+      .chain(Some(self.auth_chain.current()))
+      .find(|document| document.message_id() == message_id);
+
+    if let Some(document) = auth {
+      return Ok(Some(document.clone()));
+    }
+
+    if !self.diff_chain.iter().any(|diff| diff.message_id() == message_id) {
+      return Ok(None);
+    }
+
+    let mut document: Document = self.auth_chain.current().clone();
+
+    for diff in self.diff_chain.iter() {
+      document.merge(diff)?;
+
+      if diff.message_id() == message_id {
+        break;
+      }
+    }
+
+    Ok(Some(document))
+  }
+
   /// Adds a new auth document to the chain.
   ///
   /// # Errors
@@ -0,0 +1,33 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/// The ledger state of a Tangle address as reported by a node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddressBalance {
+  pub(crate) address: String,
+  pub(crate) balance: u64,
+  pub(crate) spent: bool,
+  pub(crate) milestone_index: i64,
+}
+
+impl AddressBalance {
+  /// Returns the tryte-encoded Tangle address.
+  pub fn address(&self) -> &str {
+    &*self.address
+  }
+
+  /// Returns the confirmed balance of the address.
+  pub fn balance(&self) -> u64 {
+    self.balance
+  }
+
+  /// Returns `true` if the node reports the address as spent from.
+  pub fn spent(&self) -> bool {
+    self.spent
+  }
+
+  /// Returns the index of the milestone the balance was calculated at.
+  pub fn milestone_index(&self) -> i64 {
+    self.milestone_index
+  }
+}
@@ -0,0 +1,180 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::time::Duration;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::did::Document;
+use crate::did::DID;
+
+/// A size-bounded least-recently-used cache of resolved DID Documents.
+pub(crate) struct DocumentCache {
+  capacity: usize,
+  ttl: Option<Duration>,
+  state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+  entries: HashMap<String, Entry>,
+  clock: u64,
+}
+
+struct Entry {
+  document: Document,
+  inserted: Instant,
+  accessed: u64,
+}
+
+impl DocumentCache {
+  /// Creates a new `DocumentCache` holding at most `capacity` documents.
+  ///
+  /// Entries older than `ttl` (if any) are treated as missing.
+  pub(crate) fn new(capacity: usize, ttl: Option<Duration>) -> Self {
+    Self {
+      capacity,
+      ttl,
+      state: Mutex::new(State::default()),
+    }
+  }
+
+  /// Returns a copy of the cached document for `did`, if any.
+  pub(crate) fn get(&self, did: &DID) -> Option<Document> {
+    let mut state: _ = self.state.lock().ok()?;
+    let clock: u64 = state.tick();
+
+    let expired: bool = match state.entries.get_mut(did.as_str()) {
+      Some(entry) if !self.is_expired(entry) => {
+        entry.accessed = clock;
+        return Some(entry.document.clone());
+      }
+      Some(_) => true,
+      None => false,
+    };
+
+    if expired {
+      state.entries.remove(did.as_str());
+    }
+
+    None
+  }
+
+  /// Adds the `document` to the cache, evicting the least-recently-used
+  /// document if the cache is full.
+  pub(crate) fn insert(&self, did: &DID, document: Document) {
+    if self.capacity == 0 {
+      return;
+    }
+
+    let mut state: _ = match self.state.lock() {
+      Ok(state) => state,
+      Err(_) => return,
+    };
+
+    if !state.entries.contains_key(did.as_str()) && state.entries.len() >= self.capacity {
+      let oldest: Option<String> = state
+        .entries
+        .iter()
+        .min_by_key(|(_, entry)| entry.accessed)
+        .map(|(key, _)| key.clone());
+
+      if let Some(oldest) = oldest {
+        state.entries.remove(&oldest);
+      }
+    }
+
+    let entry: Entry = Entry {
+      document,
+      inserted: Instant::now(),
+      accessed: state.tick(),
+    };
+
+    state.entries.insert(did.as_str().to_string(), entry);
+  }
+
+  /// Removes the cached document for `did`.
+  pub(crate) fn remove(&self, did: &DID) {
+    if let Ok(mut state) = self.state.lock() {
+      state.entries.remove(did.as_str());
+    }
+  }
+
+  fn is_expired(&self, entry: &Entry) -> bool {
+    matches!(self.ttl, Some(ttl) if entry.inserted.elapsed() >= ttl)
+  }
+}
+
+impl Debug for DocumentCache {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_struct("DocumentCache")
+      .field("capacity", &self.capacity)
+      .field("ttl", &self.ttl)
+      .finish()
+  }
+}
+
+impl State {
+  fn tick(&mut self) -> u64 {
+    self.clock += 1;
+    self.clock
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use identity_core::crypto::KeyPair;
+
+  fn document() -> Document {
+    Document::from_keypair(&KeyPair::new_ed25519().unwrap()).unwrap()
+  }
+
+  #[test]
+  fn test_get_insert_remove() {
+    let cache: DocumentCache = DocumentCache::new(2, None);
+    let doc: Document = document();
+
+    assert!(cache.get(doc.id()).is_none());
+
+    cache.insert(doc.id(), doc.clone());
+    assert_eq!(cache.get(doc.id()).unwrap(), doc);
+
+    cache.remove(doc.id());
+    assert!(cache.get(doc.id()).is_none());
+  }
+
+  #[test]
+  fn test_evict_least_recently_used() {
+    let cache: DocumentCache = DocumentCache::new(2, None);
+    let doc1: Document = document();
+    let doc2: Document = document();
+    let doc3: Document = document();
+
+    cache.insert(doc1.id(), doc1.clone());
+    cache.insert(doc2.id(), doc2.clone());
+
+    // Access `doc1` so `doc2` becomes the least-recently-used entry
+    assert!(cache.get(doc1.id()).is_some());
+
+    cache.insert(doc3.id(), doc3.clone());
+
+    assert!(cache.get(doc1.id()).is_some());
+    assert!(cache.get(doc2.id()).is_none());
+    assert!(cache.get(doc3.id()).is_some());
+  }
+
+  #[test]
+  fn test_expired() {
+    let cache: DocumentCache = DocumentCache::new(2, Some(Duration::from_secs(0)));
+    let doc: Document = document();
+
+    cache.insert(doc.id(), doc.clone());
+    assert!(cache.get(doc.id()).is_none());
+  }
+}
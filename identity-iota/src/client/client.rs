@@ -1,21 +1,37 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use core::slice::from_ref;
+use core::time::Duration;
+use futures::future::try_join_all;
+use futures_timer::Delay;
 use identity_core::common::Url;
 use identity_core::convert::ToJson;
-use iota::client::FindTransactionsResponse;
-use iota::client::GetTrytesResponse;
-use iota::client::Transfer;
-use iota::transaction::bundled::Address;
 use iota::transaction::bundled::BundledTransaction;
-use iota::transaction::bundled::BundledTransactionField;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Instant;
 
 use crate::chain::AuthChain;
 use crate::chain::DiffChain;
 use crate::chain::DocumentChain;
+use crate::client::AddressBalance;
+use crate::client::cache::DocumentCache;
 use crate::client::ClientBuilder;
+use crate::client::MockTransport;
 use crate::client::Network;
+use crate::client::NodePool;
+use crate::client::NodeTransport;
+use crate::client::pow_limiter::PowLimiter;
+use crate::client::pow_limiter::PowPermit;
+use crate::client::PublishOptions;
+use crate::client::PublishReceipt;
+use crate::client::PublishResult;
+use crate::client::ResolutionResult;
+#[cfg(feature = "mqtt")]
+use crate::client::Subscription;
+use crate::client::Transport;
 use crate::client::TxnPrinter;
 use crate::did::Document;
 use crate::did::DocumentDiff;
@@ -24,18 +40,24 @@ use crate::error::Error;
 use crate::error::Result;
 use crate::tangle::Message;
 use crate::tangle::MessageId;
-use crate::utils::bundles_from_trytes;
-use crate::utils::create_address_from_trits;
-use crate::utils::encode_trits;
+use crate::tangle::TangleRef;
 use crate::utils::txn_hash_trytes;
 
 #[derive(Clone, Debug)]
 pub struct Client {
-  pub(crate) client: iota::Client,
+  pub(crate) transport: Arc<dyn Transport>,
   pub(crate) network: Network,
+  pub(crate) cache: Option<Arc<DocumentCache>>,
+  pub(crate) pow: Arc<PowLimiter>,
+  pub(crate) max_response_size: usize,
+  #[cfg(feature = "mqtt")]
+  pub(crate) mqtt_broker: Option<Url>,
 }
 
 impl Client {
+  /// The maximum number of controllers resolved by [`Client::read_controllers`].
+  pub const MAX_CONTROLLER_DEPTH: usize = 16;
+
   /// Creates a new `Client`  with default settings.
   pub fn new() -> Result<Self> {
     Self::from_builder(Self::builder())
@@ -49,38 +71,115 @@ impl Client {
   }
 
   /// Creates a new `Client` with default settings for the given `Network`.
+  ///
+  /// This is the same as `Client::for_network(network)`.
   pub fn from_network(network: Network) -> Result<Self> {
-    Self::builder()
-      .node(network.node_url().as_str())
-      .network(network)
-      .build()
+    Self::for_network(network)
+  }
+
+  /// Creates a new `Client` connected to the default nodes of the given
+  /// `Network`.
+  ///
+  /// The explorer URL is derived from `network`. To connect to custom nodes
+  /// while keeping the network settings, use
+  /// `Client::builder().network(network).node(..)` instead.
+  pub fn for_network(network: Network) -> Result<Self> {
+    Self::builder().network(network).build()
   }
 
   /// Creates a new `Client` based on the `ClientBuilder` configuration.
   pub fn from_builder(builder: ClientBuilder) -> Result<Self> {
-    let mut client: iota::ClientBuilder = iota::ClientBuilder::new();
-
-    if builder.nodes.is_empty() {
-      client = client.node(builder.network.node_url().as_str())?;
+    let nodes: Vec<String> = if builder.nodes.is_empty() {
+      builder
+        .network
+        .default_nodes()
+        .iter()
+        .map(ToString::to_string)
+        .collect()
     } else {
-      for node in builder.nodes {
-        client = client.node(&node)?;
-      }
+      builder.nodes
+    };
+
+    // Each node gets a separate client so failed requests can be retried on
+    // the next node
+    let mut pool: Vec<(Url, NodeTransport)> = Vec::with_capacity(nodes.len());
+
+    for node in nodes {
+      let client: iota::Client = iota::ClientBuilder::new()
+        .node(&node)?
+        .network(builder.network.into())
+        .build()?;
+
+      pool.push((Url::parse(&node)?, NodeTransport::new(client)?));
     }
 
-    client = client.network(builder.network.into());
+    let cache: Option<Arc<DocumentCache>> = if builder.cache_size > 0 {
+      Some(Arc::new(DocumentCache::new(builder.cache_size, builder.cache_ttl)))
+    } else {
+      None
+    };
 
     Ok(Self {
-      client: client.build()?,
+      transport: Arc::new(NodePool::new(pool, builder.node_cooldown)),
       network: builder.network,
+      cache,
+      pow: Arc::new(PowLimiter::new(builder.max_concurrent_pow)),
+      max_response_size: builder.max_response_size,
+      #[cfg(feature = "mqtt")]
+      mqtt_broker: builder.mqtt_broker.as_deref().map(Url::parse).transpose()?,
     })
   }
 
+  /// Creates a new `Client` for the given `Network` that sends all requests
+  /// through `transport`.
+  ///
+  /// The `Client` is created without a document cache.
+  pub fn with_transport<T>(network: Network, transport: T) -> Self
+  where
+    T: Transport + 'static,
+  {
+    Self {
+      transport: Arc::new(transport),
+      network,
+      cache: None,
+      pow: Arc::new(PowLimiter::new(None)),
+      max_response_size: ClientBuilder::DEFAULT_MAX_RESPONSE_SIZE,
+      #[cfg(feature = "mqtt")]
+      mqtt_broker: None,
+    }
+  }
+
+  /// Creates a new `Client` for the main network that stores published
+  /// messages in memory instead of sending them to a node.
+  ///
+  /// This is intended for tests and examples without network access.
+  pub fn mock() -> Self {
+    Self::with_transport(Network::Mainnet, MockTransport::new())
+  }
+
   /// Returns the `Client` Tangle network.
   pub fn network(&self) -> Network {
     self.network
   }
 
+  /// Returns the maximum number of proof-of-work operations started
+  /// concurrently, if limited.
+  pub fn max_concurrent_pow(&self) -> Option<usize> {
+    self.pow.limit()
+  }
+
+  /// Returns the maximum size (in bytes) of a single message read from the
+  /// Tangle.
+  pub fn max_response_size(&self) -> usize {
+    self.max_response_size
+  }
+
+  /// Returns the URL of the node requests are sent to first - the node that
+  /// most recently served a request - if the `Client` is connected to nodes.
+  pub fn current_node(&self) -> Option<Url> {
+    self.transport.current_node()
+  }
+
   /// Returns the default node URL of the `Client` network.
   pub fn default_node_url(&self) -> &'static Url {
     self.network.node_url()
@@ -110,11 +209,20 @@ impl Client {
     txn_hash_trytes(transaction)
   }
 
+  /// Publishes an DID Document to the Tangle; returns a [`PublishResult`]
+  /// with the `MessageId`, explorer URL, and DID of the published document.
+  pub async fn publish(&self, document: &Document) -> Result<PublishResult> {
+    let message_id: MessageId = self.publish_document(document).await?;
+
+    Ok(PublishResult::new(self.network, document.id().clone(), message_id))
+  }
+
   /// Publishes an DID Document to the Tangle; returns the `MessageId` of
   /// the bundled transaction.
   ///
   /// Note: The only validation performed is to ensure the correct Tangle
   /// network is selected.
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, document), fields(did = %document.id()), err))]
   pub async fn publish_document(&self, document: &Document) -> Result<MessageId> {
     trace!("Publish Document: {}", document.id());
     trace!("Tangle Address: {}", document.id().address());
@@ -122,10 +230,40 @@ impl Client {
     self.check_network(document.id())?;
 
     let address: String = document.id().address();
-    let transfer: Transfer = create_transfer(&address, document)?;
-    let bundled: BundledTransaction = self.send_transfer(transfer).await?;
+    let message_id: MessageId = self.send_message(&address, document).await?;
+
+    self.invalidate(document.id());
+
+    Ok(message_id)
+  }
+
+  /// Publishes an DID Document to the Tangle with the given `options`;
+  /// returns a [`PublishReceipt`] of the bundled transaction.
+  ///
+  /// If the message is not confirmed it will be promoted or reattached up to
+  /// `options.reattach` times. The returned `MessageId` refers to the latest
+  /// reattachment.
+  ///
+  /// If `options.confirm_timeout` is set, the confirmation state is polled
+  /// until the message is confirmed or the timeout elapses.
+  pub async fn publish_document_with_options(
+    &self,
+    document: &Document,
+    options: PublishOptions,
+  ) -> Result<PublishReceipt> {
+    let message_id: MessageId = self.publish_document(document).await?;
+    let (message_id, confirmed): (MessageId, bool) = self.reattach_until_confirmed(message_id, options).await?;
+
+    let confirmed: bool = match options.confirm_timeout {
+      Some(timeout) if !confirmed => {
+        self
+          .wait_until_confirmed(&message_id, timeout, options.confirm_interval)
+          .await?
+      }
+      _ => confirmed,
+    };
 
-    Ok(txn_hash_trytes(&bundled).into())
+    Ok(PublishReceipt { message_id, confirmed })
   }
 
   /// Publishes a `DocumentDiff` to the Tangle; returns the `MessageId` of
@@ -133,6 +271,10 @@ impl Client {
   ///
   /// Note: The only validation performed is to ensure the correct Tangle
   /// network is selected.
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip(self, message_id, diff), fields(did = %diff.id(), message_id = %message_id), err)
+  )]
   pub async fn publish_diff(&self, message_id: &MessageId, diff: &DocumentDiff) -> Result<MessageId> {
     trace!("Publish Diff: {}", diff.id());
     trace!("Tangle Address: {}", Document::diff_address(message_id)?);
@@ -140,23 +282,352 @@ impl Client {
     self.check_network(diff.id())?;
 
     let address: String = Document::diff_address(message_id)?;
-    let transfer: Transfer = create_transfer(&address, diff)?;
-    let bundled: BundledTransaction = self.send_transfer(transfer).await?;
+    let message_id: MessageId = self.send_message(&address, diff).await?;
+
+    self.invalidate(diff.id());
+
+    Ok(message_id)
+  }
+
+  /// Returns `true` if the bundle with the given tail transaction has been
+  /// confirmed by a milestone.
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, message_id), fields(message_id = %message_id), err))]
+  pub async fn is_confirmed(&self, message_id: &MessageId) -> Result<bool> {
+    self.transport.is_confirmed(message_id).await
+  }
+
+  /// Reattaches the bundle with the given tail transaction to the Tangle;
+  /// returns the `MessageId` of the new tail transaction.
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, message_id), fields(message_id = %message_id), err))]
+  pub async fn reattach(&self, message_id: &MessageId) -> Result<MessageId> {
+    trace!("Reattach Message: {}", message_id);
 
-    Ok(txn_hash_trytes(&bundled).into())
+    let _permit: PowPermit<'_> = self.pow.acquire().await;
+
+    self.transport.reattach(message_id).await
+  }
+
+  /// Promotes the bundle with the given tail transaction by referencing it
+  /// from a new zero-value transaction; returns the `MessageId` of the
+  /// promoting transaction.
+  ///
+  /// Only bundles that are still promotable can be promoted, see
+  /// [`Client::reattach`] otherwise.
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, message_id), fields(message_id = %message_id), err))]
+  pub async fn promote(&self, message_id: &MessageId) -> Result<MessageId> {
+    trace!("Promote Message: {}", message_id);
+
+    let _permit: PowPermit<'_> = self.pow.acquire().await;
+
+    self.transport.promote(message_id).await
   }
 
+  /// Returns the node-reported ledger state of the given tryte-encoded Tangle
+  /// `address`.
+  ///
+  /// Publishing is feeless, but this can help to diagnose transactions
+  /// rejected by a node.
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), err))]
+  pub async fn get_balance(&self, address: &str) -> Result<AddressBalance> {
+    trace!("Get Balance: {}", address);
+
+    self.transport.balance(address).await
+  }
+
+  /// Returns the node-reported ledger state of the auth chain address of `did`.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Error::NetworkMismatch`] if `did` belongs to a different network
+  /// than the `Client`.
+  pub async fn get_did_balance(&self, did: &DID) -> Result<AddressBalance> {
+    self.check_network(did)?;
+    self.get_balance(&did.address()).await
+  }
+
+  /// Subscribes to the messages published on the auth chain of `did` through
+  /// the MQTT event stream of the node.
+  ///
+  /// The `callback` is invoked with the `MessageId` of each new message;
+  /// messages published before subscribing are not reported. The
+  /// subscription reconnects after transient disconnects and stops when the
+  /// returned [`Subscription`] is canceled or dropped.
+  ///
+  /// The broker set with [`ClientBuilder::mqtt_broker`] is used if present,
+  /// otherwise the broker on port 1883 of the current node.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Error::NetworkMismatch`] if `did` belongs to a different network
+  /// than the `Client`, [`Error::MissingMqttBroker`] if no broker is known,
+  /// and [`Error::SubscriptionError`] if the subscription thread fails to
+  /// start.
+  #[cfg(feature = "mqtt")]
+  pub async fn subscribe<F>(&self, did: &DID, callback: F) -> Result<Subscription>
+  where
+    F: FnMut(MessageId) + Send + 'static,
+  {
+    trace!("Subscribe: {}", did);
+
+    self.check_network(did)?;
+
+    let broker: Url = self.mqtt_broker().ok_or(Error::MissingMqttBroker)?;
+
+    // Existing messages are not reported to the callback.
+    let seen: HashSet<MessageId> = self
+      .read_did_messages(&did.address())
+      .await?
+      .iter()
+      .map(|message| message.message_id())
+      .collect();
+
+    Subscription::spawn(self.clone(), did.clone(), broker, seen, callback)
+  }
+
+  /// Returns the URL of the MQTT broker used by [`Client::subscribe`], if
+  /// known.
+  #[cfg(feature = "mqtt")]
+  pub fn mqtt_broker(&self) -> Option<Url> {
+    self.mqtt_broker.clone().or_else(|| {
+      let node: Url = self.current_node()?;
+      Url::parse(&format!("mqtt://{}", node.host_str()?)).ok()
+    })
+  }
+
+  /// Resolves the DID Document of `did`.
+  ///
+  /// If the `Client` was configured with a cache, cached documents are
+  /// returned without querying the node.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Error::NetworkMismatch`] if `did` belongs to a different network
+  /// than the `Client`.
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, did), fields(did = %did), err))]
   pub async fn read_document(&self, did: &DID) -> Result<Document> {
-    self.read_document_chain(did).await.and_then(DocumentChain::fold)
+    if let Some(document) = self.cache.as_ref().and_then(|cache| cache.get(did)) {
+      #[cfg(feature = "tracing")]
+      tracing::debug!("Cached Document");
+
+      return Ok(document);
+    }
+
+    let document: Document = self.read_document_chain(did).await.and_then(DocumentChain::fold)?;
+
+    if let Some(cache) = self.cache.as_ref() {
+      cache.insert(did, document.clone());
+    }
+
+    Ok(document)
+  }
+
+  /// Returns `true` if any message has been published on the auth chain of
+  /// `did`.
+  ///
+  /// Unlike [`read_document`][Client::read_document], the messages are not
+  /// parsed or verified; this is a cheap presence check and does not
+  /// guarantee that `did` resolves to a valid DID Document.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Error::NetworkMismatch`] if `did` belongs to a different network
+  /// than the `Client`.
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, did), fields(did = %did), err))]
+  pub async fn exists(&self, did: &DID) -> Result<bool> {
+    trace!("Exists: {}", did);
+
+    self.check_network(did)?;
+
+    if self.cache.as_ref().and_then(|cache| cache.get(did)).is_some() {
+      return Ok(true);
+    }
+
+    self
+      .read_did_messages(&did.address())
+      .await
+      .map(|messages| !messages.is_empty())
+  }
+
+  /// Resolves the DID Document of `did`, returning it with the resolution and
+  /// document metadata defined by the DID Resolution specification.
+  pub async fn resolve(&self, did: &DID) -> Result<ResolutionResult> {
+    let start: Instant = Instant::now();
+    let document: Document = self.read_document(did).await?;
+
+    Ok(ResolutionResult::new(document, start.elapsed()))
+  }
+
+  /// Resolves the DID Documents of all `dids`; returns a map of each DID to
+  /// its [`ResolutionResult`].
+  ///
+  /// Duplicate DIDs are resolved once and unique DIDs are resolved
+  /// concurrently.
+  ///
+  /// # Errors
+  ///
+  /// Fails if any of the DIDs cannot be resolved.
+  pub async fn resolve_many(&self, dids: &[DID]) -> Result<HashMap<DID, ResolutionResult>> {
+    let unique: BTreeSet<&DID> = dids.iter().collect();
+
+    trace!("Resolve Many: {} ({} unique)", dids.len(), unique.len());
+
+    let results: Vec<(DID, ResolutionResult)> = try_join_all(
+      unique
+        .into_iter()
+        .map(|did| async move { self.resolve(did).await.map(|result| (did.clone(), result)) }),
+    )
+    .await?;
+
+    Ok(results.into_iter().collect())
+  }
+
+  /// Resolves `did` and verifies the domain linkage of the origin of each of
+  /// its `LinkedDomains` services; returns the origins verifiably linked to
+  /// `did`.
+  ///
+  /// The DID configuration resource of each origin is fetched from
+  /// [`Document::DID_CONFIGURATION_PATH`]. Origins that don't use HTTPS, or
+  /// whose resource cannot be fetched, exceeds the
+  /// [maximum response size][Client::max_response_size], or has no valid
+  /// domain linkage credential are not linked.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `did` cannot be resolved.
+  pub async fn verify_domain_linkage(&self, did: &DID) -> Result<Vec<Url>> {
+    let document: Document = self.read_document(did).await?;
+    let mut linked: Vec<Url> = Vec::new();
+
+    for service in document.service().iter() {
+      if service.type_() != "LinkedDomains" {
+        continue;
+      }
+
+      let origin: &Url = service.service_endpoint();
+
+      if origin.scheme() != "https" {
+        continue;
+      }
+
+      let url: Url = origin.join(Document::DID_CONFIGURATION_PATH)?;
+
+      trace!("Verify Domain Linkage: {}", url);
+
+      let configuration: Vec<u8> = match self.transport.fetch(&url).await {
+        Ok(configuration) if configuration.len() <= self.max_response_size => configuration,
+        Ok(_) | Err(_) => continue,
+      };
+
+      if document.verify_domain_linkage(origin, &configuration).is_ok() {
+        linked.push(origin.clone());
+      }
+    }
+
+    Ok(linked)
+  }
+
+  /// Resolves the controller documents of `did`, starting with the document
+  /// of its direct controller and following each `controller` reference.
+  ///
+  /// At most [`Client::MAX_CONTROLLER_DEPTH`] controllers are resolved.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Error::ControllerCycle`] if the controllers reference a DID
+  /// that was already visited and [`Error::ControllerDepth`] if the maximum
+  /// depth is exceeded.
+  pub async fn read_controllers(&self, did: &DID) -> Result<Vec<Document>> {
+    let mut visited: HashSet<DID> = HashSet::new();
+    let mut documents: Vec<Document> = Vec::new();
+    let mut current: Document = self.read_document(did).await?;
+
+    visited.insert(did.clone());
+
+    while let Some(controller) = current.controller() {
+      let controller: DID = DID::try_from_borrowed(controller)?.clone();
+
+      trace!("Read Controller: {}", controller);
+
+      if !visited.insert(controller.clone()) {
+        return Err(Error::ControllerCycle(controller.to_string()));
+      }
+
+      if documents.len() >= Self::MAX_CONTROLLER_DEPTH {
+        return Err(Error::ControllerDepth);
+      }
+
+      current = self.read_document(&controller).await?;
+      documents.push(current.clone());
+    }
+
+    Ok(documents)
   }
 
+  /// Resolves the DID Document of `did` as it was when the message with the
+  /// given `message_id` was published.
+  ///
+  /// `message_id` may refer to any auth (integration) message of the chain or
+  /// a diff message of any auth document. The cache is bypassed.
+  ///
+  /// Diffs of previous auth documents are not part of the resolved document
+  /// chain; their diff chains are fetched, latest first, only if `message_id`
+  /// is not found in the current one.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `message_id` is not part of the document chain of `did`.
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip(self, did, message_id), fields(did = %did, message_id = %message_id), err)
+  )]
+  pub async fn read_document_at(&self, did: &DID, message_id: &MessageId) -> Result<Document> {
+    let chain: DocumentChain = self.read_document_chain(did).await?;
+
+    if let Some(document) = chain.document_at(message_id)? {
+      return Ok(document);
+    }
+
+    for document in chain.auth().history().iter().rev() {
+      if document.immutable() {
+        continue;
+      }
+
+      let address: String = Document::diff_address(document.message_id())?;
+      let messages: Vec<Message> = self.read_did_messages(&address).await?;
+
+      let auth: AuthChain = AuthChain::__epoch(document.clone());
+      let diff: DiffChain = DiffChain::try_from_messages(&auth, &messages)?;
+
+      if let Some(document) = DocumentChain::with_diff_chain(auth, diff)?.document_at(message_id)? {
+        return Ok(document);
+      }
+    }
+
+    Err(Error::ChainError {
+      error: "Unknown Message Id",
+    })
+  }
+
+  /// Removes the cached DID Document of `did`, if any.
+  ///
+  /// Documents published with this `Client` are invalidated automatically.
+  pub fn invalidate(&self, did: &DID) {
+    if let Some(cache) = self.cache.as_ref() {
+      cache.remove(did);
+    }
+  }
+
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, did), fields(did = %did), err))]
   pub async fn read_document_chain(&self, did: &DID) -> Result<DocumentChain> {
     trace!("Read Document Chain: {}", did);
     trace!("Auth Chain Address: {}", did.address());
 
+    // Resolving a DID from another network would query the wrong nodes.
+    self.check_network(did)?;
+
     // Fetch all messages for the auth chain.
     let address: String = did.address();
-    let messages: Vec<Message> = self.read_messages(&address).await?;
+    let messages: Vec<Message> = self.read_did_messages(&address).await?;
 
     let auth: AuthChain = AuthChain::try_from_messages(did, &messages)?;
 
@@ -165,7 +636,7 @@ impl Client {
     } else {
       // Fetch all messages for the diff chain.
       let address: String = Document::diff_address(auth.current_message_id())?;
-      let messages: Vec<Message> = self.read_messages(&address).await?;
+      let messages: Vec<Message> = self.read_did_messages(&address).await?;
 
       trace!("Tangle Messages: {:?}", messages);
 
@@ -175,81 +646,711 @@ impl Client {
     DocumentChain::with_diff_chain(auth, diff)
   }
 
+  /// Returns the `MessageId` and raw contents of all messages published at
+  /// the given Tangle address (`index`).
+  ///
+  /// This is the low-level primitive DID resolution is built upon and can be
+  /// used to inspect arbitrary messages.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Error::ResponseTooLarge`] if a message exceeds the
+  /// [maximum response size][Client::max_response_size]. DID resolution skips
+  /// such messages instead.
+  pub async fn find_messages(&self, index: &str) -> Result<Vec<(MessageId, Vec<u8>)>> {
+    self
+      .read_messages(index)
+      .await?
+      .iter()
+      .map(|message| Ok((message.message_id(), message.message_bytes()?)))
+      .collect()
+  }
+
+  /// Returns all messages published at `address`.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Error::ResponseTooLarge`] if a message exceeds the
+  /// [maximum response size][Client::max_response_size].
   #[doc(hidden)]
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), err))]
   pub async fn read_messages(&self, address: &str) -> Result<Vec<Message>> {
-    let address: Address = create_address_from_trits(address)?;
+    trace!("Read Messages: {}", address);
+
+    let messages: Vec<Message> = self.transport.messages(address).await?;
+
+    if let Some(message) = messages.iter().find(|message| message.size() > self.max_response_size) {
+      return Err(Error::ResponseTooLarge {
+        size: message.size(),
+        limit: self.max_response_size,
+      });
+    }
 
-    trace!("Read Transactions: {}", encode_trits(address.to_inner()));
+    Ok(messages)
+  }
 
-    // Fetch all transaction hashes containing the tangle address.
-    let response: FindTransactionsResponse = self
-      .client
-      .find_transactions()
-      .addresses(from_ref(&address))
-      .send()
-      .await?;
+  // Returns the messages published at the DID address `address`, skipping
+  // messages exceeding the maximum response size.
+  //
+  // Anyone can publish at the address of a DID, so a single oversized message
+  // must not make the DID unresolvable.
+  pub(crate) async fn read_did_messages(&self, address: &str) -> Result<Vec<Message>> {
+    trace!("Read DID Messages: {}", address);
 
-    trace!("Transactions Found: {:?}", __dbg_transactions(&response));
+    let mut messages: Vec<Message> = self.transport.messages(address).await?;
 
-    if response.hashes.is_empty() {
-      return Ok(Vec::new());
-    }
+    messages.retain(|message| {
+      if message.size() > self.max_response_size {
+        warn!("Oversized Message: {} ({} bytes)", message.message_id(), message.size());
+        false
+      } else {
+        true
+      }
+    });
 
-    // Fetch the content of all transactions.
-    let content: GetTrytesResponse = self.client.get_trytes(&response.hashes).await?;
+    Ok(messages)
+  }
 
-    trace!("Transaction Trytes: {:?}", __dbg_trytes(&content));
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, data), err))]
+  pub(crate) async fn send_message<T>(&self, address: &str, data: &T) -> Result<MessageId>
+  where
+    T: ToJson,
+  {
+    let message: String = data.to_json()?;
+    let _permit: PowPermit<'_> = self.pow.acquire().await;
 
-    if content.trytes.is_empty() {
-      return Err(Error::InvalidTransactionTrytes);
+    self.transport.publish(address, message).await
+  }
+
+  // Promotes or reattaches the message up to `options.reattach` times until it
+  // is confirmed; returns the latest `MessageId` and whether it is confirmed.
+  pub(crate) async fn reattach_until_confirmed(
+    &self,
+    mut message_id: MessageId,
+    options: PublishOptions,
+  ) -> Result<(MessageId, bool)> {
+    for _ in 0..options.reattach {
+      if self.is_confirmed(&message_id).await? {
+        return Ok((message_id, true));
+      }
+
+      // Promote the message if possible, otherwise create a new attachment
+      if self.transport.is_promotable(&message_id).await? {
+        self.promote(&message_id).await?;
+      } else {
+        message_id = self.reattach(&message_id).await?;
+      }
     }
 
-    // Re-build the fragmented messages stored in the bundle.
-    bundles_from_trytes(content.trytes)
-      .into_iter()
-      .map(Message::try_from_bundle)
-      .collect()
+    // The last promotion or reattachment may have confirmed the message
+    let confirmed: bool = options.reattach > 0 && self.is_confirmed(&message_id).await?;
+
+    Ok((message_id, confirmed))
   }
 
-  pub(crate) async fn send_transfer(&self, transfer: Transfer) -> Result<BundledTransaction> {
-    trace!("Sending Transfer: {:?}", transfer.message);
+  /// Polls the confirmation state of the bundle with the given tail
+  /// transaction every `interval` until it is confirmed or `timeout` elapses;
+  /// returns `true` if the bundle was confirmed.
+  pub async fn wait_until_confirmed(
+    &self,
+    message_id: &MessageId,
+    timeout: Duration,
+    interval: Duration,
+  ) -> Result<bool> {
+    let start: Instant = Instant::now();
 
-    self
-      .client
-      .send(None)
-      .transfers(vec![transfer])
-      .send()
-      .await?
-      .into_iter()
-      .find(BundledTransaction::is_tail)
-      .ok_or(Error::InvalidBundleTail)
+    loop {
+      if self.is_confirmed(message_id).await? {
+        return Ok(true);
+      }
+
+      let elapsed: Duration = start.elapsed();
+
+      if elapsed >= timeout {
+        return Ok(false);
+      }
+
+      Delay::new(interval.min(timeout - elapsed)).await;
+    }
   }
 
   pub(crate) fn check_network(&self, did: &DID) -> Result<()> {
     if !self.network.matches_did(did) {
-      return Err(Error::InvalidDIDNetwork);
+      return Err(Error::NetworkMismatch {
+        expected: self.network.as_str(),
+        found: did.network().to_string(),
+      });
     }
 
     Ok(())
   }
 }
 
-fn create_transfer<T>(address: &str, data: &T) -> Result<Transfer>
-where
-  T: ToJson,
-{
-  Ok(Transfer {
-    address: create_address_from_trits(address)?,
-    value: 0,
-    message: Some(data.to_json()?),
-    tag: None,
-  })
-}
+#[cfg(test)]
+mod tests {
+  use futures::executor::block_on;
+  use identity_core::common::Object;
+  use identity_core::common::Timestamp;
+  use identity_core::common::Url;
+  use identity_core::convert::SerdeInto;
+  use identity_core::convert::ToJson;
+  use identity_core::crypto::KeyPair;
+  use identity_core::json;
+  use identity_credential::credential::Credential;
+  use identity_credential::credential::Subject;
+  use identity_credential::credential::VerifiableCredential;
+  use std::collections::HashMap;
+  use std::time::Duration;
 
-fn __dbg_transactions(response: &FindTransactionsResponse) -> Vec<String> {
-  response.hashes.iter().map(|hash| encode_trits(hash)).collect()
-}
+  use crate::chain::DocumentChain;
+  use crate::client::AddressBalance;
+  use crate::client::Client;
+  use crate::client::ClientBuilder;
+  use crate::client::MockTransport;
+  use crate::client::Network;
+  use crate::client::NodePool;
+  use crate::client::PublishOptions;
+  use crate::client::PublishReceipt;
+  use crate::client::ResolutionResult;
+  use crate::client::Transport;
+  use crate::did::Document;
+  use crate::did::DocumentDiff;
+  use crate::did::DID;
+  use crate::error::Error;
+  use crate::tangle::MessageId;
+  use crate::tangle::TangleRef;
+
+  fn controlled(keypair: &KeyPair, controller: &DID) -> Document {
+    let mut object: Object = Document::from_keypair(keypair).unwrap().serde_into().unwrap();
+
+    object.insert("controller".into(), controller.as_str().into());
+
+    let mut document: Document = object.serde_into().unwrap();
+
+    document.sign(keypair.secret()).unwrap();
+    document
+  }
+
+  fn did_configuration(document: &Document, keypair: &KeyPair, origin: &Url) -> Vec<u8> {
+    let did: Url = Url::parse(document.id().as_str()).unwrap();
+
+    let mut properties: Object = Object::new();
+
+    properties.insert("origin".into(), origin.as_str().into());
+
+    let credential: Credential = Credential::builder(Object::new())
+      .context(Url::parse("https://identity.foundation/.well-known/did-configuration/v1").unwrap())
+      .type_("DomainLinkageCredential")
+      .issuer(did.clone())
+      .issuance_date(Timestamp::now())
+      .subject(Subject::with_id_and_properties(did, properties))
+      .build()
+      .unwrap();
+
+    let mut credential: VerifiableCredential = VerifiableCredential::new(credential, Vec::new());
+
+    document.sign_data(&mut credential, keypair.secret()).unwrap();
+
+    json!({
+      "@context": "https://identity.foundation/.well-known/did-configuration/v1",
+      "linked_dids": [credential],
+    })
+    .to_json_vec()
+    .unwrap()
+  }
+
+  #[test]
+  fn test_publish_and_resolve() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    document.sign(keypair.secret()).unwrap();
+
+    let client: Client = Client::mock();
+
+    block_on(async {
+      client.publish_document(&document).await.unwrap();
+
+      let resolved: Document = client.read_document(document.id()).await.unwrap();
+
+      assert_eq!(resolved.id(), document.id());
+      assert_eq!(resolved.authentication(), document.authentication());
+    });
+  }
+
+  #[test]
+  fn test_exists() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    document.sign(keypair.secret()).unwrap();
+
+    let client: Client = Client::mock();
+
+    block_on(async {
+      assert!(!client.exists(document.id()).await.unwrap());
+
+      client.publish_document(&document).await.unwrap();
+
+      assert!(client.exists(document.id()).await.unwrap());
+    });
+  }
+
+  #[test]
+  fn test_publish_with_options() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    document.sign(keypair.secret()).unwrap();
+
+    let transport: MockTransport = MockTransport::new();
+
+    transport.set_pending(true);
+
+    let client: Client = Client::with_transport(Network::Mainnet, transport);
+
+    block_on(async {
+      // Confirmation is neither awaited nor checked
+      let receipt: PublishReceipt = client
+        .publish_document_with_options(&document, PublishOptions::new())
+        .await
+        .unwrap();
+
+      assert!(!receipt.confirmed());
+
+      // The message is confirmed by the last reattachment
+      let receipt: PublishReceipt = client
+        .publish_document_with_options(&document, PublishOptions::new().reattach(1))
+        .await
+        .unwrap();
+
+      assert!(receipt.confirmed());
+    });
+  }
+
+  #[test]
+  fn test_publish_confirm_timeout() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    document.sign(keypair.secret()).unwrap();
+
+    let options: PublishOptions = PublishOptions::new()
+      .confirm_timeout(Duration::from_millis(50))
+      .confirm_interval(Duration::from_millis(10));
+
+    block_on(async {
+      // The message is confirmed immediately
+      let client: Client = Client::mock();
+      let receipt: PublishReceipt = client.publish_document_with_options(&document, options).await.unwrap();
+
+      assert!(receipt.confirmed());
+      assert!(client.read_document(document.id()).await.is_ok());
+
+      // The message is never confirmed and the wait times out
+      let transport: MockTransport = MockTransport::new();
+
+      transport.set_pending(true);
+
+      let client: Client = Client::with_transport(Network::Mainnet, transport);
+      let receipt: PublishReceipt = client.publish_document_with_options(&document, options).await.unwrap();
+
+      assert!(!receipt.confirmed());
+      assert!(!client.is_confirmed(receipt.message_id()).await.unwrap());
+    });
+  }
+
+  #[test]
+  fn test_get_balance() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let did: DID = DID::new(keypair.public().as_ref()).unwrap();
+    let client: Client = Client::mock();
+
+    block_on(async {
+      let balance: AddressBalance = client.get_did_balance(&did).await.unwrap();
+
+      assert_eq!(balance.address(), did.address());
+      assert_eq!(balance.balance(), 0);
+      assert!(!balance.spent());
+
+      assert_eq!(client.get_balance(&did.address()).await.unwrap(), balance);
+
+      // The DID belongs to another network
+      let did: DID = DID::with_network(keypair.public().as_ref(), "dev").unwrap();
+
+      assert!(matches!(
+        client.get_did_balance(&did).await.unwrap_err(),
+        Error::NetworkMismatch { expected: "main", found } if found == "dev"
+      ));
+    });
+  }
+
+  #[test]
+  fn test_read_document_network_mismatch() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    document.sign(keypair.secret()).unwrap();
+
+    let client: Client = Client::mock();
+
+    assert_eq!(client.network(), Network::Mainnet);
+
+    block_on(async {
+      client.publish_document(&document).await.unwrap();
+
+      assert!(client.read_document(document.id()).await.is_ok());
+
+      // The DID belongs to another network
+      let did: DID = DID::with_network(keypair.public().as_ref(), "dev").unwrap();
+
+      assert!(matches!(
+        client.read_document(&did).await.unwrap_err(),
+        Error::NetworkMismatch { expected: "main", found } if found == "dev"
+      ));
+
+      let client: Client = Client::with_transport(Network::Devnet, MockTransport::new());
+
+      assert_eq!(client.network(), Network::Devnet);
+      assert!(matches!(
+        client.read_document(document.id()).await.unwrap_err(),
+        Error::NetworkMismatch { expected: "dev", found } if found == "main"
+      ));
+    });
+  }
+
+  #[test]
+  fn test_max_response_size() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    document.sign(keypair.secret()).unwrap();
+
+    let mut client: Client = Client::mock();
+
+    assert_eq!(client.max_response_size(), ClientBuilder::DEFAULT_MAX_RESPONSE_SIZE);
+
+    block_on(async {
+      client.publish_document(&document).await.unwrap();
+
+      // Spam an oversized message at the address of the document
+      let spam: Object = json!({ "spam": "x".repeat(8 * 1024) }).serde_into().unwrap();
+
+      client.send_message(&document.id().address(), &spam).await.unwrap();
+      client.max_response_size = 4 * 1024;
+
+      // Reading the messages reports the oversized message
+      assert!(matches!(
+        client.find_messages(&document.id().address()).await.unwrap_err(),
+        Error::ResponseTooLarge { limit, .. } if limit == 4 * 1024
+      ));
+
+      // Resolution skips the oversized message
+      assert!(client.exists(document.id()).await.unwrap());
+      assert_eq!(client.read_document(document.id()).await.unwrap().id(), document.id());
+
+      client.max_response_size = 64;
+
+      assert!(matches!(
+        client.find_messages(&document.id().address()).await.unwrap_err(),
+        Error::ResponseTooLarge { limit: 64, .. }
+      ));
+      assert!(!client.exists(document.id()).await.unwrap());
+      assert!(client.read_document(document.id()).await.is_err());
+    });
+  }
 
-fn __dbg_trytes(response: &GetTrytesResponse) -> Vec<TxnPrinter<'_>> {
-  response.trytes.iter().map(TxnPrinter::full).collect()
+  #[test]
+  fn test_node_failover() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    document.sign(keypair.secret()).unwrap();
+
+    let primary: Url = Url::parse("https://node-1.example.com").unwrap();
+    let backup: Url = Url::parse("https://node-2.example.com").unwrap();
+
+    let pool: NodePool<MockTransport> = NodePool::new(
+      vec![
+        (primary.clone(), MockTransport::new()),
+        (backup.clone(), MockTransport::new()),
+      ],
+      Duration::from_secs(60),
+    );
+
+    pool.transports().next().unwrap().set_offline(true);
+
+    let client: Client = Client::with_transport(Network::Mainnet, pool);
+
+    assert_eq!(client.current_node(), None);
+
+    block_on(async {
+      client.publish_document(&document).await.unwrap();
+
+      assert_eq!(client.current_node(), Some(backup));
+
+      let resolved: Document = client.read_document(document.id()).await.unwrap();
+
+      assert_eq!(resolved.id(), document.id());
+
+      // Requests fail if no node is available
+      let offline: MockTransport = MockTransport::new();
+
+      offline.set_offline(true);
+
+      let pool: NodePool<MockTransport> = NodePool::new(vec![(primary, offline)], Duration::from_secs(60));
+
+      assert!(matches!(
+        pool.messages(&document.id().address()).await,
+        Err(Error::NodeUnavailable)
+      ));
+    });
+  }
+
+  #[test]
+  fn test_verify_domain_linkage() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    let linked: Url = Url::parse("https://example.com").unwrap();
+    let unlinked: Url = Url::parse("https://example.org").unwrap();
+    let missing: Url = Url::parse("https://example.net").unwrap();
+    let insecure: Url = Url::parse("http://example.com").unwrap();
+
+    document.insert_linked_domains("domain-1", linked.clone()).unwrap();
+    document.insert_linked_domains("domain-2", unlinked.clone()).unwrap();
+    document.insert_linked_domains("domain-3", missing).unwrap();
+    document.insert_linked_domains("domain-4", insecure.clone()).unwrap();
+    document.sign(keypair.secret()).unwrap();
+
+    // Both origins serve a credential linking the DID to `linked` only
+    let configuration: Vec<u8> = did_configuration(&document, &keypair, &linked);
+    let transport: MockTransport = MockTransport::new();
+
+    for origin in &[&linked, &unlinked] {
+      let url: Url = origin.join(Document::DID_CONFIGURATION_PATH).unwrap();
+
+      transport.insert_resource(&url, configuration.clone()).unwrap();
+    }
+
+    // A valid credential served from an insecure origin is never fetched
+    let url: Url = insecure.join(Document::DID_CONFIGURATION_PATH).unwrap();
+
+    transport
+      .insert_resource(&url, did_configuration(&document, &keypair, &insecure))
+      .unwrap();
+
+    let client: Client = Client::with_transport(Network::Mainnet, transport);
+
+    block_on(async {
+      client.publish_document(&document).await.unwrap();
+
+      let origins: Vec<Url> = client.verify_domain_linkage(document.id()).await.unwrap();
+
+      assert_eq!(origins, vec![linked.clone()]);
+    });
+
+    assert!(document.verify_domain_linkage(&linked, &configuration).is_ok());
+    assert!(document.verify_domain_linkage(&unlinked, &configuration).is_err());
+    assert!(document.verify_domain_linkage(&linked, b"{}").is_err());
+  }
+
+  #[test]
+  fn test_read_document_at() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    document.sign(keypair.secret()).unwrap();
+
+    let client: Client = Client::mock();
+
+    block_on(async {
+      document.publish(&client).await.unwrap();
+
+      // Publish a diff of the first auth document
+      let mut update: Document = document.clone();
+
+      update.properties_mut().insert("foo".into(), 1.into());
+
+      let mut diff_1: DocumentDiff = document
+        .diff(&update, document.message_id().clone(), keypair.secret())
+        .unwrap();
+
+      diff_1.publish(document.message_id(), &client).await.unwrap();
+
+      // Publish a new auth document, starting a new diff chain
+      let mut auth: Document = document.clone();
+
+      auth.set_updated(Timestamp::now());
+      auth.set_previous_message_id(document.message_id().clone());
+
+      document.sign_data(&mut auth, keypair.secret()).unwrap();
+      auth.publish(&client).await.unwrap();
+
+      let mut update: Document = auth.clone();
+
+      update.properties_mut().insert("bar".into(), 2.into());
+
+      let mut diff_2: DocumentDiff = auth.diff(&update, auth.message_id().clone(), keypair.secret()).unwrap();
+
+      diff_2.publish(auth.message_id(), &client).await.unwrap();
+
+      // Only diffs of the latest auth document are part of the document chain
+      let chain: DocumentChain = client.read_document_chain(document.id()).await.unwrap();
+
+      assert!(chain.document_at(diff_1.message_id()).unwrap().is_none());
+      assert!(chain.document_at(diff_2.message_id()).unwrap().is_some());
+
+      let resolved: Document = client
+        .read_document_at(document.id(), document.message_id())
+        .await
+        .unwrap();
+
+      assert_eq!(resolved.message_id(), document.message_id());
+      assert!(resolved.properties().get("foo").is_none());
+
+      let resolved: Document = client
+        .read_document_at(document.id(), diff_1.message_id())
+        .await
+        .unwrap();
+
+      assert_eq!(resolved.properties().get("foo"), Some(&1.into()));
+
+      let resolved: Document = client.read_document_at(document.id(), auth.message_id()).await.unwrap();
+
+      assert_eq!(resolved.message_id(), auth.message_id());
+      assert!(resolved.properties().get("bar").is_none());
+
+      let resolved: Document = client
+        .read_document_at(document.id(), diff_2.message_id())
+        .await
+        .unwrap();
+
+      assert_eq!(resolved.properties().get("bar"), Some(&2.into()));
+
+      let unknown: MessageId = MessageId::new("A".repeat(81));
+
+      assert!(matches!(
+        client.read_document_at(document.id(), &unknown).await.unwrap_err(),
+        Error::ChainError {
+          error: "Unknown Message Id"
+        }
+      ));
+    });
+  }
+
+  #[test]
+  fn test_resolve_many() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    document.sign(keypair.secret()).unwrap();
+
+    let client: Client = Client::mock();
+    let dids: Vec<DID> = vec![document.id().clone(), document.id().clone()];
+
+    block_on(async {
+      client.publish_document(&document).await.unwrap();
+
+      let resolved: HashMap<DID, ResolutionResult> = client.resolve_many(&dids).await.unwrap();
+
+      assert_eq!(resolved.len(), 1);
+      assert_eq!(resolved[document.id()].document().id(), document.id());
+    });
+  }
+
+  #[test]
+  fn test_read_controllers() {
+    let keypairs: Vec<KeyPair> = (0..3).map(|_| KeyPair::new_ed25519().unwrap()).collect();
+    let dids: Vec<DID> = keypairs
+      .iter()
+      .map(|keypair| DID::new(keypair.public().as_ref()).unwrap())
+      .collect();
+
+    let mut root: Document = Document::from_keypair(&keypairs[2]).unwrap();
+
+    root.sign(keypairs[2].secret()).unwrap();
+
+    let client: Client = Client::mock();
+
+    block_on(async {
+      client
+        .publish_document(&controlled(&keypairs[0], &dids[1]))
+        .await
+        .unwrap();
+      client
+        .publish_document(&controlled(&keypairs[1], &dids[2]))
+        .await
+        .unwrap();
+      client.publish_document(&root).await.unwrap();
+
+      let controllers: Vec<Document> = client.read_controllers(&dids[0]).await.unwrap();
+
+      assert_eq!(controllers.len(), 2);
+      assert_eq!(controllers[0].id(), &dids[1]);
+      assert_eq!(controllers[1].id(), &dids[2]);
+    });
+  }
+
+  #[test]
+  fn test_read_controllers_cycle() {
+    let keypairs: Vec<KeyPair> = (0..2).map(|_| KeyPair::new_ed25519().unwrap()).collect();
+    let dids: Vec<DID> = keypairs
+      .iter()
+      .map(|keypair| DID::new(keypair.public().as_ref()).unwrap())
+      .collect();
+
+    let client: Client = Client::mock();
+
+    block_on(async {
+      client
+        .publish_document(&controlled(&keypairs[0], &dids[1]))
+        .await
+        .unwrap();
+      client
+        .publish_document(&controlled(&keypairs[1], &dids[0]))
+        .await
+        .unwrap();
+
+      let error: Error = client.read_controllers(&dids[0]).await.unwrap_err();
+
+      assert!(matches!(error, Error::ControllerCycle(did) if did == dids[0].as_str()));
+    });
+  }
+
+  #[cfg(feature = "mqtt")]
+  #[test]
+  fn test_subscribe_mqtt_broker() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let did: DID = DID::new(keypair.public().as_ref()).unwrap();
+
+    // The mock transport has no node to derive a broker from
+    let client: Client = Client::mock();
+
+    assert!(client.mqtt_broker().is_none());
+    assert!(matches!(
+      block_on(client.subscribe(&did, |_| {})).unwrap_err(),
+      Error::MissingMqttBroker
+    ));
+
+    let client: Client = ClientBuilder::new()
+      .node("https://nodes.iota.org:443")
+      .mqtt_broker("mqtt://localhost:1883")
+      .build()
+      .unwrap();
+
+    assert_eq!(client.mqtt_broker().unwrap().as_str(), "mqtt://localhost:1883");
+
+    let client: Client = ClientBuilder::new().node("https://nodes.iota.org:443").build().unwrap();
+
+    assert_eq!(client.mqtt_broker().unwrap().as_str(), "mqtt://nodes.iota.org");
+
+    // The DID belongs to another network
+    let did: DID = DID::with_network(keypair.public().as_ref(), "dev").unwrap();
+
+    assert!(matches!(
+      block_on(client.subscribe(&did, |_| {})).unwrap_err(),
+      Error::NetworkMismatch { .. }
+    ));
+  }
 }
@@ -1,6 +1,8 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use core::time::Duration;
+
 use crate::client::Client;
 use crate::client::Network;
 use crate::error::Result;
@@ -10,14 +12,37 @@ use crate::error::Result;
 pub struct ClientBuilder {
   pub(crate) network: Network,
   pub(crate) nodes: Vec<String>,
+  pub(crate) cache_size: usize,
+  pub(crate) cache_ttl: Option<Duration>,
+  pub(crate) max_concurrent_pow: Option<usize>,
+  pub(crate) max_response_size: usize,
+  pub(crate) node_cooldown: Duration,
+  #[cfg(feature = "mqtt")]
+  pub(crate) mqtt_broker: Option<String>,
 }
 
 impl ClientBuilder {
+  /// The default maximum size (in bytes) of a message read from the Tangle.
+  ///
+  /// This is not a protocol limit: a bundle may span any number of
+  /// transactions, each carrying about 1 KiB of message data.
+  pub const DEFAULT_MAX_RESPONSE_SIZE: usize = 32 * 1024;
+
+  /// The default duration a persistently failing node is skipped.
+  pub const DEFAULT_NODE_COOLDOWN: Duration = Duration::from_secs(30);
+
   /// Creates a new `ClientBuilder`.
   pub const fn new() -> Self {
     Self {
       network: Network::Mainnet,
       nodes: Vec::new(),
+      cache_size: 0,
+      cache_ttl: None,
+      max_concurrent_pow: None,
+      max_response_size: Self::DEFAULT_MAX_RESPONSE_SIZE,
+      node_cooldown: Self::DEFAULT_NODE_COOLDOWN,
+      #[cfg(feature = "mqtt")]
+      mqtt_broker: None,
     }
   }
 
@@ -29,6 +54,9 @@ impl ClientBuilder {
   }
 
   /// Adds an IOTA node to the generated `Client`.
+  ///
+  /// Requests failing on one node are retried on the next node in the order
+  /// the nodes were added.
   #[must_use]
   pub fn node(mut self, node: impl Into<String>) -> Self {
     self.nodes.push(node.into());
@@ -41,6 +69,72 @@ impl ClientBuilder {
     self
   }
 
+  /// Sets the maximum number of resolved DID Documents cached by the
+  /// generated `Client`. A size of `0` (the default) disables caching.
+  #[must_use]
+  pub fn cache_size(mut self, value: usize) -> Self {
+    self.cache_size = value;
+    self
+  }
+
+  /// Sets the duration after which cached DID Documents are resolved again.
+  #[must_use]
+  pub fn cache_ttl(mut self, value: Duration) -> Self {
+    self.cache_ttl = Some(value);
+    self
+  }
+
+  /// Sets the maximum number of proof-of-work operations the generated
+  /// `Client` starts concurrently when publishing, reattaching, or promoting.
+  ///
+  /// Further operations wait until a running operation completes. A value of
+  /// `1` runs all operations sequentially; `0` is treated as `1`. By default
+  /// the number of concurrent operations is not limited.
+  ///
+  /// Note: This does not configure the number of threads used by a single
+  /// proof-of-work operation.
+  #[must_use]
+  pub fn max_concurrent_pow(mut self, value: usize) -> Self {
+    self.max_concurrent_pow = Some(value);
+    self
+  }
+
+  /// Sets the maximum size (in bytes) of a single message - a DID Document or
+  /// diff - read from the Tangle by the generated `Client`.
+  ///
+  /// Reading the messages of an address fails with
+  /// [`Error::ResponseTooLarge`][crate::error::Error::ResponseTooLarge] if a
+  /// message is larger; DID resolution skips larger messages before they are
+  /// decoded or parsed. Independent of this limit, nodes are queried for at
+  /// most [`NodeTransport::MAX_TRANSACTIONS`][crate::client::NodeTransport::MAX_TRANSACTIONS]
+  /// transactions per address.
+  #[must_use]
+  pub fn max_response_size(mut self, value: usize) -> Self {
+    self.max_response_size = value;
+    self
+  }
+
+  /// Sets the duration a node is skipped after failing
+  /// [`NodePool::FAILURE_THRESHOLD`][crate::client::NodePool::FAILURE_THRESHOLD]
+  /// requests in a row.
+  #[must_use]
+  pub fn node_cooldown(mut self, value: Duration) -> Self {
+    self.node_cooldown = value;
+    self
+  }
+
+  /// Sets the URL of the MQTT broker used by
+  /// [`Client::subscribe`][crate::client::Client::subscribe], e.g.
+  /// `mqtt://localhost:1883`.
+  ///
+  /// By default the broker on port 1883 of the current node is used.
+  #[cfg(feature = "mqtt")]
+  #[must_use]
+  pub fn mqtt_broker(mut self, value: impl Into<String>) -> Self {
+    self.mqtt_broker = Some(value.into());
+    self
+  }
+
   /// Creates a new `Client` based on the `ClientBuilder` configuration.
   pub fn build(self) -> Result<Client> {
     Client::from_builder(self)
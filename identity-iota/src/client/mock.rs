@@ -0,0 +1,185 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use identity_core::common::Url;
+use iota::ternary::T1B1Buf;
+use iota::ternary::TryteBuf;
+use iota::transaction::bundled::BundledTransactionField;
+use iota::transaction::bundled::Timestamp;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+
+use crate::client::AddressBalance;
+use crate::client::Transport;
+use crate::error::Error;
+use crate::error::Result;
+use crate::tangle::Message;
+use crate::tangle::MessageId;
+use crate::utils::create_hash_from_trits;
+use crate::utils::utf8_to_trytes;
+
+type MessageMap = HashMap<String, Vec<(MessageId, String)>>;
+type ResourceMap = HashMap<String, Vec<u8>>;
+
+/// An in-memory [`Transport`] for tests and examples without network access.
+///
+/// Published messages are stored by address and are confirmed immediately,
+/// unless the transport is set to [pending][MockTransport::set_pending].
+/// Resources returned by [`Transport::fetch`] must be inserted beforehand.
+#[derive(Debug, Default)]
+pub struct MockTransport {
+  messages: Mutex<MessageMap>,
+  resources: Mutex<ResourceMap>,
+  offline: AtomicBool,
+  pending: AtomicBool,
+}
+
+impl MockTransport {
+  /// Creates a new `MockTransport` without any messages.
+  pub fn new() -> Self {
+    Self {
+      messages: Mutex::new(MessageMap::new()),
+      resources: Mutex::new(ResourceMap::new()),
+      offline: AtomicBool::new(false),
+      pending: AtomicBool::new(false),
+    }
+  }
+
+  /// Sets whether the transport is offline; requests to an offline transport
+  /// fail with [`Error::NodeUnavailable`].
+  pub fn set_offline(&self, value: bool) {
+    self.offline.store(value, Ordering::SeqCst);
+  }
+
+  /// Sets whether published messages are pending; pending messages are not
+  /// confirmed until they are reattached.
+  pub fn set_pending(&self, value: bool) {
+    self.pending.store(value, Ordering::SeqCst);
+  }
+
+  /// Stores `data` as the resource at `url`.
+  pub fn insert_resource(&self, url: &Url, data: impl Into<Vec<u8>>) -> Result<()> {
+    self.resources_guard()?.insert(url.to_string(), data.into());
+
+    Ok(())
+  }
+
+  /// Returns the number of messages published at `address`.
+  pub fn count(&self, address: &str) -> Result<usize> {
+    self
+      .messages_guard()
+      .map(|messages| messages.get(address).map_or(0, Vec::len))
+  }
+
+  fn check_online(&self) -> Result<()> {
+    if self.offline.load(Ordering::SeqCst) {
+      Err(Error::NodeUnavailable)
+    } else {
+      Ok(())
+    }
+  }
+
+  fn messages_guard(&self) -> Result<MutexGuard<'_, MessageMap>> {
+    self.messages.lock().map_err(|_| Error::MutexPoisoned)
+  }
+
+  fn resources_guard(&self) -> Result<MutexGuard<'_, ResourceMap>> {
+    self.resources.lock().map_err(|_| Error::MutexPoisoned)
+  }
+}
+
+#[async_trait(?Send)]
+impl Transport for MockTransport {
+  async fn publish(&self, address: &str, message: String) -> Result<MessageId> {
+    self.check_online()?;
+
+    let mut messages: MutexGuard<'_, MessageMap> = self.messages_guard()?;
+    let index: usize = messages.values().map(Vec::len).sum();
+
+    // Message ids are 81 trytes, like the hash of a tail transaction
+    let mut message_id: String = utf8_to_trytes(&(index as u64).to_be_bytes());
+    message_id.extend(core::iter::repeat('9').take(81 - message_id.len()));
+
+    let message_id: MessageId = MessageId::new(message_id);
+
+    messages
+      .entry(address.to_string())
+      .or_default()
+      .push((message_id.clone(), message));
+
+    Ok(message_id)
+  }
+
+  async fn messages(&self, address: &str) -> Result<Vec<Message>> {
+    self.check_online()?;
+
+    let messages: MutexGuard<'_, MessageMap> = self.messages_guard()?;
+
+    messages
+      .get(address)
+      .map(Vec::as_slice)
+      .unwrap_or_default()
+      .iter()
+      .enumerate()
+      .map(|(index, (message_id, message))| {
+        Ok(Message {
+          address: address.to_string(),
+          message: TryteBuf::try_from_str(&utf8_to_trytes(message))?
+            .as_trits()
+            .encode::<T1B1Buf>(),
+          tail_hash: create_hash_from_trits(message_id.as_str())?,
+          timestamp: Timestamp::from_inner_unchecked(index as u64),
+        })
+      })
+      .collect()
+  }
+
+  async fn is_confirmed(&self, _message_id: &MessageId) -> Result<bool> {
+    self.check_online()?;
+
+    Ok(!self.pending.load(Ordering::SeqCst))
+  }
+
+  async fn is_promotable(&self, _message_id: &MessageId) -> Result<bool> {
+    self.check_online()?;
+
+    Ok(false)
+  }
+
+  async fn reattach(&self, message_id: &MessageId) -> Result<MessageId> {
+    self.check_online()?;
+
+    self.pending.store(false, Ordering::SeqCst);
+
+    Ok(message_id.clone())
+  }
+
+  async fn promote(&self, message_id: &MessageId) -> Result<MessageId> {
+    self.check_online()?;
+
+    Ok(message_id.clone())
+  }
+
+  async fn balance(&self, address: &str) -> Result<AddressBalance> {
+    self.check_online()?;
+
+    Ok(AddressBalance {
+      address: address.to_string(),
+      balance: 0,
+      spent: false,
+      milestone_index: 0,
+    })
+  }
+
+  async fn fetch(&self, url: &Url) -> Result<Vec<u8>> {
+    self
+      .resources_guard()?
+      .get(url.as_str())
+      .cloned()
+      .ok_or_else(|| Error::MissingResource(url.to_string()))
+  }
+}
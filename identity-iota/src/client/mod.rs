@@ -3,13 +3,37 @@
 
 #![allow(clippy::module_inception)]
 
+mod address_balance;
+mod cache;
 mod client;
 mod client_builder;
+mod mock;
 mod network;
+mod node_pool;
+mod pow_limiter;
+mod publish_options;
+mod publish_receipt;
+mod publish_result;
+mod resolution_result;
 mod resolver;
+#[cfg(feature = "mqtt")]
+mod subscription;
+mod transport;
 mod txn_printer;
 
+pub use self::address_balance::AddressBalance;
 pub use self::client::Client;
 pub use self::client_builder::ClientBuilder;
+pub use self::mock::MockTransport;
 pub use self::network::Network;
+pub use self::node_pool::NodePool;
+pub use self::publish_options::PublishOptions;
+pub use self::publish_receipt::PublishReceipt;
+pub use self::publish_result::PublishResult;
+pub use self::resolution_result::ResolutionResult;
+pub use self::resolution_result::ResolutionResultMetadata;
+#[cfg(feature = "mqtt")]
+pub use self::subscription::Subscription;
+pub use self::transport::NodeTransport;
+pub use self::transport::Transport;
 pub use self::txn_printer::TxnPrinter;
@@ -1,10 +1,16 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use crypto::hashes::blake2b::Blake2b256;
+use crypto::hashes::Digest;
 use identity_core::common::Url;
 use iota::client::builder;
 
 use crate::did::DID;
+use crate::error::Error;
+use crate::error::Result;
+use crate::tangle::MessageId;
+use crate::utils::encode_bech32;
 
 lazy_static! {
   static ref EXPLORER_MAIN: Url = Url::parse("https://explorer.iota.org/mainnet").unwrap();
@@ -13,6 +19,15 @@ lazy_static! {
   static ref NODE_MAIN: Url = Url::parse("https://nodes.iota.org:443").unwrap();
   static ref NODE_DEV: Url = Url::parse("https://nodes.devnet.iota.org:443").unwrap();
   static ref NODE_COM: Url = Url::parse("https://nodes.comnet.thetangle.org:443").unwrap();
+  static ref NODES_MAIN: Vec<Url> = vec![
+    NODE_MAIN.clone(),
+    Url::parse("https://nodes.thetangle.org:443").unwrap(),
+  ];
+  static ref NODES_DEV: Vec<Url> = vec![
+    NODE_DEV.clone(),
+    Url::parse("https://nodes.devnet.thetangle.org:443").unwrap(),
+  ];
+  static ref NODES_COM: Vec<Url> = vec![NODE_COM.clone()];
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -23,6 +38,8 @@ pub enum Network {
 }
 
 impl Network {
+  const ED25519_ADDRESS: u8 = 0x00;
+
   pub fn from_name(string: &str) -> Self {
     match string {
       "dev" => Self::Devnet,
@@ -31,6 +48,30 @@ impl Network {
     }
   }
 
+  /// Parses the name of a Tangle network (`main`, `dev`, or `com`).
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Error::InvalidDIDNetwork`] if the network is not supported.
+  pub fn try_from_name(string: &str) -> Result<Self> {
+    match string {
+      "main" => Ok(Self::Mainnet),
+      "dev" => Ok(Self::Devnet),
+      "com" => Ok(Self::Comnet),
+      _ => Err(Error::InvalidDIDNetwork),
+    }
+  }
+
+  /// Returns the Tangle network of `did`; DIDs without a network segment
+  /// belong to the mainnet.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Error::InvalidDIDNetwork`] if the network is not supported.
+  pub fn try_from_did(did: &DID) -> Result<Self> {
+    Self::try_from_name(did.network())
+  }
+
   pub fn matches_did(self, did: &DID) -> bool {
     did.network() == self.as_str()
   }
@@ -44,6 +85,17 @@ impl Network {
     }
   }
 
+  /// Returns the list of default node URLs of the Tangle network.
+  ///
+  /// The first entry is always the same as [`Network::node_url`].
+  pub fn default_nodes(self) -> &'static [Url] {
+    match self {
+      Self::Mainnet => &*NODES_MAIN,
+      Self::Devnet => &*NODES_DEV,
+      Self::Comnet => &*NODES_COM,
+    }
+  }
+
   /// Returns the web explorer URL of the Tangle network.
   pub fn explorer_url(self) -> &'static Url {
     match self {
@@ -53,6 +105,38 @@ impl Network {
     }
   }
 
+  /// Returns the web explorer URL of the message with the given `message_id`.
+  pub fn transaction_url(self, message_id: &MessageId) -> Url {
+    let mut url: Url = self.explorer_url().clone();
+
+    url
+      .path_segments_mut()
+      .unwrap()
+      .push("transaction")
+      .push(message_id.as_str());
+
+    url
+  }
+
+  /// Returns the bech32 human-readable part used for addresses of the
+  /// Tangle network.
+  pub const fn bech32_hrp(self) -> &'static str {
+    match self {
+      Self::Mainnet => "iota",
+      Self::Devnet => "atoi",
+      Self::Comnet => "atoi",
+    }
+  }
+
+  /// Returns the bech32-encoded Ed25519 address of the given `public` key
+  /// on the Tangle network.
+  pub fn ed25519_address(self, public: &[u8]) -> String {
+    // An Ed25519 address is the type byte followed by the BLAKE2b-256 hash
+    let address: Vec<u8> = [&[Self::ED25519_ADDRESS][..], &Blake2b256::digest(public)[..]].concat();
+
+    encode_bech32(self.bech32_hrp(), &address)
+  }
+
   /// Returns the name of the network as a static `str`.
   pub const fn as_str(self) -> &'static str {
     match self {
@@ -107,6 +191,24 @@ mod tests {
     assert_eq!(Network::from_name("anything"), Network::Mainnet);
   }
 
+  #[test]
+  fn test_try_from_did() {
+    let did: DID = DID::new(b"").unwrap();
+    assert_eq!(Network::try_from_did(&did).unwrap(), Network::Mainnet);
+
+    let did: DID = DID::with_network(b"", "main").unwrap();
+    assert_eq!(Network::try_from_did(&did).unwrap(), Network::Mainnet);
+
+    let did: DID = DID::with_network(b"", "dev").unwrap();
+    assert_eq!(Network::try_from_did(&did).unwrap(), Network::Devnet);
+
+    let did: DID = DID::with_network(b"", "com").unwrap();
+    assert_eq!(Network::try_from_did(&did).unwrap(), Network::Comnet);
+
+    let did: DID = DID::with_network(b"", "anything").unwrap();
+    assert!(matches!(Network::try_from_did(&did), Err(Error::InvalidDIDNetwork)));
+  }
+
   #[test]
   fn test_matches_did() {
     let did: DID = DID::new(b"").unwrap();
@@ -124,4 +226,40 @@ mod tests {
     assert!(!Network::matches_did(Network::Mainnet, &did));
     assert!(!Network::matches_did(Network::Comnet, &did));
   }
+
+  #[test]
+  fn test_ed25519_address() {
+    let public: Vec<u8> = (0..32).collect();
+
+    assert_eq!(
+      Network::Mainnet.ed25519_address(&public),
+      "iota1qr9j75tqls0hupd9tm6f6dqtfrdzukncpxw4xwfn28x408w5y5pavuw7gf2"
+    );
+    assert_eq!(
+      Network::Devnet.ed25519_address(&public),
+      "atoi1qr9j75tqls0hupd9tm6f6dqtfrdzukncpxw4xwfn28x408w5y5pavmq0fn8"
+    );
+  }
+
+  #[test]
+  fn test_transaction_url() {
+    let message_id: MessageId = MessageId::new("ABCDEF".to_string());
+
+    assert_eq!(
+      Network::Mainnet.transaction_url(&message_id).as_str(),
+      "https://explorer.iota.org/mainnet/transaction/ABCDEF"
+    );
+    assert_eq!(
+      Network::Comnet.transaction_url(&message_id).as_str(),
+      "https://comnet.thetangle.org/transaction/ABCDEF"
+    );
+  }
+
+  #[test]
+  fn test_default_nodes() {
+    for network in &[Network::Mainnet, Network::Devnet, Network::Comnet] {
+      assert!(!network.default_nodes().is_empty());
+      assert_eq!(&network.default_nodes()[0], network.node_url());
+    }
+  }
 }
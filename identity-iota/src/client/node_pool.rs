@@ -0,0 +1,281 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use core::future::Future;
+use core::time::Duration;
+use identity_core::common::Url;
+use std::error::Error as StdError;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::time::Instant;
+
+use crate::client::AddressBalance;
+use crate::client::NodeTransport;
+use crate::client::Transport;
+use crate::error::Error;
+use crate::error::Result;
+use crate::tangle::Message;
+use crate::tangle::MessageId;
+
+/// A [`Transport`] sending requests to a pool of nodes, failing over to the
+/// next node when a request fails.
+///
+/// Requests are sent to the node that served the last successful request
+/// first. A node failing [`FAILURE_THRESHOLD`][Self::FAILURE_THRESHOLD] times
+/// in a row is skipped until its cooldown has elapsed, unless no other node
+/// is available.
+///
+/// Only connection failures, timeouts, and server errors are failed over;
+/// all other errors are returned as-is. Publishing is only failed over if the
+/// request never reached the node, so a message is never broadcast twice.
+#[derive(Debug)]
+pub struct NodePool<T = NodeTransport> {
+  nodes: Vec<Node<T>>,
+  // The index of the node tried first; `NO_NODE` until a request succeeds
+  current: AtomicUsize,
+  cooldown: Duration,
+}
+
+#[derive(Debug)]
+struct Node<T> {
+  url: Url,
+  transport: T,
+  health: Mutex<Health>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Health {
+  failures: usize,
+  skip_until: Option<Instant>,
+}
+
+impl<T> NodePool<T>
+where
+  T: Transport,
+{
+  /// The number of consecutive failures after which a node is skipped.
+  pub const FAILURE_THRESHOLD: usize = 3;
+
+  const NO_NODE: usize = usize::MAX;
+
+  /// Creates a new `NodePool` of the given `nodes`; nodes exceeding the
+  /// failure threshold are skipped for `cooldown`.
+  pub fn new(nodes: impl IntoIterator<Item = (Url, T)>, cooldown: Duration) -> Self {
+    Self {
+      nodes: nodes
+        .into_iter()
+        .map(|(url, transport)| Node {
+          url,
+          transport,
+          health: Mutex::new(Health::default()),
+        })
+        .collect(),
+      current: AtomicUsize::new(Self::NO_NODE),
+      cooldown,
+    }
+  }
+
+  /// Returns the URLs of all nodes in the pool.
+  pub fn nodes(&self) -> impl Iterator<Item = &Url> + '_ {
+    self.nodes.iter().map(|node| &node.url)
+  }
+
+  /// Returns the transports of all nodes in the pool.
+  pub fn transports(&self) -> impl Iterator<Item = &T> + '_ {
+    self.nodes.iter().map(|node| &node.transport)
+  }
+
+  // Returns the node indices in the order they are tried: healthy nodes
+  // starting at the current node, followed by the skipped nodes.
+  fn candidates(&self) -> Result<Vec<usize>> {
+    let now: Instant = Instant::now();
+    let start: usize = match self.current.load(Ordering::SeqCst) {
+      Self::NO_NODE => 0,
+      index => index,
+    };
+
+    let mut healthy: Vec<usize> = Vec::with_capacity(self.nodes.len());
+    let mut skipped: Vec<usize> = Vec::new();
+
+    for offset in 0..self.nodes.len() {
+      let index: usize = (start + offset) % self.nodes.len();
+
+      match self.nodes[index].health()?.skip_until {
+        Some(instant) if instant > now => skipped.push(index),
+        Some(_) | None => healthy.push(index),
+      }
+    }
+
+    healthy.extend(skipped);
+
+    Ok(healthy)
+  }
+
+  fn record_success(&self, index: usize) -> Result<()> {
+    *self.nodes[index].health()? = Health::default();
+
+    self.current.store(index, Ordering::SeqCst);
+
+    Ok(())
+  }
+
+  fn record_failure(&self, index: usize) -> Result<()> {
+    let mut health: MutexGuard<'_, Health> = self.nodes[index].health()?;
+
+    health.failures += 1;
+
+    if health.failures >= Self::FAILURE_THRESHOLD {
+      health.skip_until = Some(Instant::now() + self.cooldown);
+    }
+
+    Ok(())
+  }
+
+  async fn request<'a, F, O, R>(&'a self, f: F) -> Result<R>
+  where
+    F: Fn(&'a T) -> O,
+    O: Future<Output = Result<R>>,
+  {
+    self.request_with(f, is_node_failure).await
+  }
+
+  // Sends a request to the candidate nodes in order, failing over to the next
+  // node if `failover` returns `true` for the error.
+  async fn request_with<'a, F, O, R>(&'a self, f: F, failover: fn(&Error) -> bool) -> Result<R>
+  where
+    F: Fn(&'a T) -> O,
+    O: Future<Output = Result<R>>,
+  {
+    let mut error: Option<Error> = None;
+
+    for index in self.candidates()? {
+      match f(&self.nodes[index].transport).await {
+        Ok(output) => {
+          trace!("Served By: {}", self.nodes[index].url);
+
+          self.record_success(index)?;
+
+          return Ok(output);
+        }
+        Err(error_) if failover(&error_) => {
+          warn!("Node Failed: {} ({})", self.nodes[index].url, error_);
+
+          self.record_failure(index)?;
+
+          error = Some(error_);
+        }
+        Err(error_) => return Err(error_),
+      }
+    }
+
+    Err(error.unwrap_or(Error::NodeUnavailable))
+  }
+}
+
+impl<T> Node<T> {
+  fn health(&self) -> Result<MutexGuard<'_, Health>> {
+    self.health.lock().map_err(|_| Error::MutexPoisoned)
+  }
+}
+
+#[async_trait(?Send)]
+impl<T> Transport for NodePool<T>
+where
+  T: Transport,
+{
+  // A publish that timed out or failed with a server error may still have
+  // been broadcast; failing over would publish the message a second time.
+  async fn publish(&self, address: &str, message: String) -> Result<MessageId> {
+    self
+      .request_with(
+        |transport| transport.publish(address, message.clone()),
+        is_unsent_failure,
+      )
+      .await
+  }
+
+  async fn messages(&self, address: &str) -> Result<Vec<Message>> {
+    self.request(|transport| transport.messages(address)).await
+  }
+
+  async fn is_confirmed(&self, message_id: &MessageId) -> Result<bool> {
+    self.request(|transport| transport.is_confirmed(message_id)).await
+  }
+
+  async fn is_promotable(&self, message_id: &MessageId) -> Result<bool> {
+    self.request(|transport| transport.is_promotable(message_id)).await
+  }
+
+  // Reattaching or promoting on another node at worst creates an additional
+  // attachment or promotion, which is harmless.
+  async fn reattach(&self, message_id: &MessageId) -> Result<MessageId> {
+    self.request(|transport| transport.reattach(message_id)).await
+  }
+
+  async fn promote(&self, message_id: &MessageId) -> Result<MessageId> {
+    self.request(|transport| transport.promote(message_id)).await
+  }
+
+  async fn balance(&self, address: &str) -> Result<AddressBalance> {
+    self.request(|transport| transport.balance(address)).await
+  }
+
+  // Resources are not served by the nodes; any transport can fetch them
+  async fn fetch(&self, url: &Url) -> Result<Vec<u8>> {
+    match self.nodes.first() {
+      Some(node) => node.transport.fetch(url).await,
+      None => Err(Error::NodeUnavailable),
+    }
+  }
+
+  fn current_node(&self) -> Option<Url> {
+    self
+      .nodes
+      .get(self.current.load(Ordering::SeqCst))
+      .map(|node| node.url.clone())
+  }
+}
+
+// Returns `true` if the request failed because the node is unreachable, timed
+// out, or failed with a server error - the request may succeed on another
+// node.
+fn is_node_failure(error: &Error) -> bool {
+  match error {
+    Error::NodeUnavailable => true,
+    Error::HttpError(error) => is_connection_failure(error),
+    Error::ClientError(error) => http_error(error).map(is_connection_failure).unwrap_or(false),
+    _ => false,
+  }
+}
+
+// Returns `true` if the request failed before it reached the node.
+fn is_unsent_failure(error: &Error) -> bool {
+  match error {
+    Error::NodeUnavailable => true,
+    Error::HttpError(error) => error.is_connect(),
+    Error::ClientError(error) => http_error(error).map(reqwest::Error::is_connect).unwrap_or(false),
+    _ => false,
+  }
+}
+
+fn is_connection_failure(error: &reqwest::Error) -> bool {
+  error.is_connect() || error.is_timeout() || error.status().map(|status| status.is_server_error()).unwrap_or(false)
+}
+
+// Returns the HTTP error that caused a node client error, if any.
+fn http_error(error: &(dyn StdError + 'static)) -> Option<&reqwest::Error> {
+  let mut current: Option<&(dyn StdError + 'static)> = Some(error);
+
+  while let Some(error) = current {
+    if let Some(error) = error.downcast_ref::<reqwest::Error>() {
+      return Some(error);
+    }
+
+    current = error.source();
+  }
+
+  None
+}
@@ -0,0 +1,81 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use async_lock::Semaphore;
+use async_lock::SemaphoreGuard;
+
+/// Bounds the number of proof-of-work operations a `Client` performs
+/// concurrently.
+///
+/// This only limits how many operations are started at once - the number of
+/// threads used by a single operation is up to the node or PoW backend.
+#[derive(Debug)]
+pub(crate) struct PowLimiter {
+  limit: Option<usize>,
+  semaphore: Option<Semaphore>,
+}
+
+impl PowLimiter {
+  /// Creates a new `PowLimiter` allowing `limit` concurrent operations; a
+  /// `limit` of `Some(0)` is treated as `Some(1)` and `None` disables the
+  /// limit.
+  pub(crate) fn new(limit: Option<usize>) -> Self {
+    let limit: Option<usize> = limit.map(|limit| limit.max(1));
+
+    Self {
+      limit,
+      semaphore: limit.map(Semaphore::new),
+    }
+  }
+
+  pub(crate) fn limit(&self) -> Option<usize> {
+    self.limit
+  }
+
+  /// Waits until an operation slot is available; the slot is released when
+  /// the returned `PowPermit` is dropped.
+  pub(crate) async fn acquire(&self) -> PowPermit<'_> {
+    match self.semaphore {
+      Some(ref semaphore) => PowPermit(Some(semaphore.acquire().await)),
+      None => PowPermit(None),
+    }
+  }
+}
+
+pub(crate) struct PowPermit<'a>(Option<SemaphoreGuard<'a>>);
+
+#[cfg(test)]
+mod tests {
+  use futures::executor::block_on;
+
+  use super::*;
+
+  #[test]
+  fn test_limit() {
+    assert_eq!(PowLimiter::new(None).limit(), None);
+    assert_eq!(PowLimiter::new(Some(0)).limit(), Some(1));
+    assert_eq!(PowLimiter::new(Some(1)).limit(), Some(1));
+    assert_eq!(PowLimiter::new(Some(8)).limit(), Some(8));
+  }
+
+  #[test]
+  fn test_permit_release() {
+    let limiter: PowLimiter = PowLimiter::new(Some(1));
+    let semaphore: &Semaphore = limiter.semaphore.as_ref().unwrap();
+
+    let permit: PowPermit<'_> = block_on(limiter.acquire());
+
+    assert!(semaphore.try_acquire().is_none());
+
+    drop(permit);
+
+    assert!(semaphore.try_acquire().is_some());
+  }
+
+  #[test]
+  fn test_unlimited() {
+    let limiter: PowLimiter = PowLimiter::new(None);
+
+    let _permits: Vec<PowPermit<'_>> = (0..16).map(|_| block_on(limiter.acquire())).collect();
+  }
+}
@@ -0,0 +1,55 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::time::Duration;
+
+/// Options used to configure the publishing of Tangle messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PublishOptions {
+  pub(crate) reattach: usize,
+  pub(crate) confirm_timeout: Option<Duration>,
+  pub(crate) confirm_interval: Duration,
+}
+
+impl PublishOptions {
+  const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+
+  /// Creates a new `PublishOptions` with default settings.
+  pub const fn new() -> Self {
+    Self {
+      reattach: 0,
+      confirm_timeout: None,
+      confirm_interval: Self::DEFAULT_INTERVAL,
+    }
+  }
+
+  /// Sets the maximum number of times an unconfirmed message is promoted or
+  /// reattached before giving up.
+  #[must_use]
+  pub fn reattach(mut self, value: usize) -> Self {
+    self.reattach = value;
+    self
+  }
+
+  /// Waits up to `value` for the message to be confirmed by a milestone
+  /// before returning.
+  #[must_use]
+  pub fn confirm_timeout(mut self, value: Duration) -> Self {
+    self.confirm_timeout = Some(value);
+    self
+  }
+
+  /// Sets the interval at which the confirmation state is polled while
+  /// waiting for confirmation.
+  #[must_use]
+  pub fn confirm_interval(mut self, value: Duration) -> Self {
+    self.confirm_interval = value;
+    self
+  }
+}
+
+impl Default for PublishOptions {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -0,0 +1,27 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tangle::MessageId;
+
+/// The result of publishing a message to the Tangle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublishReceipt {
+  pub(crate) message_id: MessageId,
+  pub(crate) confirmed: bool,
+}
+
+impl PublishReceipt {
+  /// Returns the `MessageId` of the published message.
+  ///
+  /// If the message was reattached this refers to the latest reattachment.
+  pub fn message_id(&self) -> &MessageId {
+    &self.message_id
+  }
+
+  /// Returns `true` if the message was observed to be confirmed by a milestone.
+  ///
+  /// This is `false` if confirmation was not awaited or the wait timed out.
+  pub fn confirmed(&self) -> bool {
+    self.confirmed
+  }
+}
@@ -0,0 +1,41 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Url;
+
+use crate::client::Network;
+use crate::did::DID;
+use crate::tangle::MessageId;
+
+/// The result of publishing a DID Document to the Tangle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PublishResult {
+  pub(crate) message_id: MessageId,
+  pub(crate) explorer_url: Url,
+  pub(crate) did: DID,
+}
+
+impl PublishResult {
+  pub(crate) fn new(network: Network, did: DID, message_id: MessageId) -> Self {
+    Self {
+      explorer_url: network.transaction_url(&message_id),
+      message_id,
+      did,
+    }
+  }
+
+  /// Returns the `MessageId` of the published message.
+  pub fn message_id(&self) -> &MessageId {
+    &self.message_id
+  }
+
+  /// Returns the web explorer URL of the published message.
+  pub fn explorer_url(&self) -> &Url {
+    &self.explorer_url
+  }
+
+  /// Returns the DID of the published document, which can be used to resolve it.
+  pub fn did(&self) -> &DID {
+    &self.did
+  }
+}
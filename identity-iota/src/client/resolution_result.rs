@@ -0,0 +1,123 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::time::Duration;
+use identity_did::resolution::DocumentMetadata;
+use identity_did::resolution::MIME_DID_LD;
+
+use crate::did::Document;
+use crate::did::DID;
+
+/// The result of resolving a DID Document, as defined by the
+/// [DID Resolution][SPEC] specification.
+///
+/// [SPEC]: https://w3c-ccg.github.io/did-resolution/#did-resolution-result
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ResolutionResult {
+  #[serde(rename = "didDocument")]
+  pub(crate) document: Document,
+  #[serde(rename = "didResolutionMetadata")]
+  pub(crate) metadata: ResolutionResultMetadata,
+  #[serde(rename = "didDocumentMetadata")]
+  pub(crate) document_metadata: DocumentMetadata,
+}
+
+impl ResolutionResult {
+  pub(crate) fn new(document: Document, duration: Duration) -> Self {
+    let metadata: ResolutionResultMetadata = ResolutionResultMetadata {
+      content_type: MIME_DID_LD.to_string(),
+      duration: duration.as_millis() as u64,
+      resolved: document.id().clone(),
+    };
+
+    let mut document_metadata: DocumentMetadata = DocumentMetadata::new();
+    document_metadata.created = Some(document.created());
+    document_metadata.updated = Some(document.updated());
+    document_metadata.deactivated = Some(document.deactivated());
+
+    Self {
+      document,
+      metadata,
+      document_metadata,
+    }
+  }
+
+  /// Returns the resolved DID Document.
+  pub fn document(&self) -> &Document {
+    &self.document
+  }
+
+  /// Returns the metadata of the resolution process.
+  pub fn metadata(&self) -> &ResolutionResultMetadata {
+    &self.metadata
+  }
+
+  /// Returns the metadata of the resolved DID Document.
+  pub fn document_metadata(&self) -> &DocumentMetadata {
+    &self.document_metadata
+  }
+
+  /// Consumes the `ResolutionResult`, returning the resolved DID Document.
+  pub fn into_document(self) -> Document {
+    self.document
+  }
+}
+
+/// The metadata of the resolution process of a [`ResolutionResult`].
+///
+/// [More Info](https://w3c-ccg.github.io/did-resolution/#output-resolutionmetadata)
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionResultMetadata {
+  pub(crate) content_type: String,
+  pub(crate) duration: u64,
+  pub(crate) resolved: DID,
+}
+
+impl ResolutionResultMetadata {
+  /// Returns the MIME type of the resolved DID Document.
+  pub fn content_type(&self) -> &str {
+    &self.content_type
+  }
+
+  /// Returns the duration of the resolution process.
+  pub fn duration(&self) -> Duration {
+    Duration::from_millis(self.duration)
+  }
+
+  /// Returns the resolved DID.
+  pub fn resolved(&self) -> &DID {
+    &self.resolved
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Value;
+  use identity_core::convert::FromJson;
+  use identity_core::convert::ToJson;
+  use identity_core::crypto::KeyPair;
+  use identity_core::json;
+
+  use super::*;
+
+  #[test]
+  fn test_json_roundtrip() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let document: Document = Document::from_keypair(&keypair).unwrap();
+    let result: ResolutionResult = ResolutionResult::new(document, Duration::from_millis(10));
+
+    let json: String = result.to_json().unwrap();
+
+    assert_eq!(ResolutionResult::from_json(&json).unwrap(), result);
+
+    let value: Value = result.to_json_value().unwrap();
+    let metadata: &Value = &value["didResolutionMetadata"];
+
+    assert_eq!(metadata["contentType"], json!(MIME_DID_LD));
+    assert_eq!(metadata["duration"], json!(10));
+    assert_eq!(metadata["resolved"], json!(result.document().id().as_str()));
+    assert_eq!(value["didDocumentMetadata"]["deactivated"], json!(false));
+    assert_eq!(value["didDocument"]["id"], json!(result.document().id().as_str()));
+  }
+}
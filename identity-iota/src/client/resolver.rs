@@ -33,6 +33,7 @@ impl ResolverMethod for Client {
     let mut meta: DocumentMetadata = DocumentMetadata::new();
     meta.created = Some(document.created());
     meta.updated = Some(document.updated());
+    meta.deactivated = Some(document.deactivated());
 
     Ok(Some(MetaDocument {
       data: document.serde_into()?,
@@ -0,0 +1,213 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::time::Duration;
+use futures_timer::Delay;
+use identity_core::common::Url;
+use rumqttc::AsyncClient;
+use rumqttc::Event;
+use rumqttc::EventLoop;
+use rumqttc::MqttOptions;
+use rumqttc::Packet;
+use rumqttc::QoS;
+use std::collections::HashSet;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::thread;
+use tokio::runtime::Builder;
+use tokio::runtime::Runtime;
+use tokio::sync::oneshot;
+
+use crate::client::Client;
+use crate::did::DID;
+use crate::error::Error;
+use crate::error::Result;
+use crate::tangle::MessageId;
+
+/// The port of the node MQTT broker if the broker URL does not specify one.
+const DEFAULT_MQTT_PORT: u16 = 1883;
+
+/// The number of requests buffered by the MQTT client.
+const REQUEST_CAPACITY: usize = 10;
+
+/// Distinguishes the MQTT client ids of the subscriptions of a process; a
+/// broker disconnects clients connecting with the id of a connected client.
+static NEXT_CLIENT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// The delay before the first reconnection attempt; doubled after every
+/// consecutive failure up to `MAX_RECONNECT_DELAY`.
+const MIN_RECONNECT_DELAY: Duration = Duration::from_millis(500);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// A subscription to the messages published on the auth chain of a DID,
+/// created with [`Client::subscribe`].
+///
+/// The subscription runs on a background thread until it is canceled or
+/// dropped. Lost connections to the MQTT broker are re-established; messages
+/// published while disconnected are reported after reconnecting.
+pub struct Subscription {
+  did: DID,
+  cancel: Option<oneshot::Sender<()>>,
+}
+
+impl Subscription {
+  pub(crate) fn spawn<F>(client: Client, did: DID, broker: Url, seen: HashSet<MessageId>, callback: F) -> Result<Self>
+  where
+    F: FnMut(MessageId) + Send + 'static,
+  {
+    let (cancel, canceled): (oneshot::Sender<()>, oneshot::Receiver<()>) = oneshot::channel();
+
+    let task: SubscriptionTask<F> = SubscriptionTask {
+      client,
+      did: did.clone(),
+      address: did.address(),
+      seen,
+      callback,
+    };
+
+    // Tangle requests are not `Send`, so the subscription is driven by a
+    // runtime of its own instead of a task on the runtime of the caller.
+    let runtime: Runtime = Builder::new_current_thread()
+      .enable_all()
+      .build()
+      .map_err(Error::SubscriptionError)?;
+
+    thread::Builder::new()
+      .name(format!("subscription-{}", did.tag()))
+      .spawn(move || runtime.block_on(task.run(broker, canceled)))
+      .map_err(Error::SubscriptionError)?;
+
+    Ok(Self {
+      did,
+      cancel: Some(cancel),
+    })
+  }
+
+  /// Returns the DID of the subscription.
+  pub fn did(&self) -> &DID {
+    &self.did
+  }
+
+  /// Returns `true` if the subscription has been canceled.
+  pub fn is_canceled(&self) -> bool {
+    self.cancel.is_none()
+  }
+
+  /// Cancels the subscription; the connection to the MQTT broker is closed
+  /// in the background and the callback is no longer invoked.
+  pub fn cancel(&mut self) {
+    if let Some(cancel) = self.cancel.take() {
+      // The subscription has already stopped if the receiver is gone.
+      let _ = cancel.send(());
+    }
+  }
+}
+
+impl Drop for Subscription {
+  fn drop(&mut self) {
+    self.cancel();
+  }
+}
+
+impl Debug for Subscription {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_struct("Subscription")
+      .field("did", &self.did)
+      .field("canceled", &self.is_canceled())
+      .finish()
+  }
+}
+
+struct SubscriptionTask<F> {
+  client: Client,
+  did: DID,
+  address: String,
+  seen: HashSet<MessageId>,
+  callback: F,
+}
+
+impl<F> SubscriptionTask<F>
+where
+  F: FnMut(MessageId) + Send + 'static,
+{
+  async fn run(mut self, broker: Url, mut canceled: oneshot::Receiver<()>) {
+    let host: String = broker.host_str().unwrap_or_default().to_string();
+    let port: u16 = broker.port().unwrap_or(DEFAULT_MQTT_PORT);
+    let client_id: String = format!(
+      "identity-{}-{}",
+      std::process::id(),
+      NEXT_CLIENT_ID.fetch_add(1, Ordering::Relaxed)
+    );
+
+    let mut options: MqttOptions = MqttOptions::new(client_id, host, port);
+    options.set_keep_alive(30);
+
+    let (mqtt, mut events): (AsyncClient, EventLoop) = AsyncClient::new(options, REQUEST_CAPACITY);
+    let mut delay: Duration = MIN_RECONNECT_DELAY;
+
+    loop {
+      tokio::select! {
+        _ = &mut canceled => break,
+        event = events.poll() => match event {
+          Ok(Event::Incoming(Packet::ConnAck(_))) => {
+            trace!("Subscription Connected: {} -> {}", self.did, broker);
+
+            delay = MIN_RECONNECT_DELAY;
+
+            // Subscriptions do not survive a reconnect; the node is also
+            // queried once for messages published while disconnected.
+            if let Err(error) = mqtt.subscribe(self.address.as_str(), QoS::AtMostOnce).await {
+              warn!("Subscription Failed: {} -> {}", self.did, error);
+            }
+
+            self.notify().await;
+          }
+          Ok(Event::Incoming(Packet::Publish(publish))) if publish.topic == self.address => {
+            self.notify().await;
+          }
+          Ok(_) => {}
+          Err(error) => {
+            warn!("Subscription Disconnected: {} -> {} (retrying in {:?})", self.did, error, delay);
+
+            // The next poll of the event loop reconnects to the broker.
+            tokio::select! {
+              _ = &mut canceled => break,
+              _ = Delay::new(delay) => {}
+            }
+
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+          }
+        },
+      }
+    }
+
+    trace!("Subscription Canceled: {}", self.did);
+
+    let _ = mqtt.disconnect().await;
+  }
+
+  /// Reports the messages on the DID address not seen before.
+  ///
+  /// A transaction notification carries the transaction hash, not the id of
+  /// the message its bundle belongs to, so the messages are read from the
+  /// node and compared against the messages already reported.
+  async fn notify(&mut self) {
+    let message_ids: Vec<MessageId> = match self.client.read_did_messages(&self.address).await {
+      Ok(messages) => messages.iter().map(|message| message.message_id()).collect(),
+      Err(error) => {
+        warn!("Subscription Read Failed: {} -> {}", self.did, error);
+        return;
+      }
+    };
+
+    for message_id in message_ids {
+      if self.seen.insert(message_id.clone()) {
+        trace!("Subscription Message: {} -> {}", self.did, message_id);
+        (self.callback)(message_id);
+      }
+    }
+  }
+}
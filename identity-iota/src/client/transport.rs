@@ -0,0 +1,308 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use core::fmt::Debug;
+use core::slice::from_ref;
+use core::time::Duration;
+use identity_core::common::Url;
+use iota::client::FindTransactionsResponse;
+use iota::client::GetBalancesResponse;
+use iota::client::GetTrytesResponse;
+use iota::client::Transfer;
+use iota::crypto::ternary::Hash;
+use iota::transaction::bundled::Address;
+use iota::transaction::bundled::BundledTransaction;
+use iota::transaction::bundled::BundledTransactionField;
+use reqwest::redirect::Policy;
+
+use crate::client::AddressBalance;
+use crate::client::TxnPrinter;
+use crate::error::Error;
+use crate::error::Result;
+use crate::tangle::Message;
+use crate::tangle::MessageId;
+use crate::utils::bundles_from_trytes;
+use crate::utils::create_address_from_trits;
+use crate::utils::create_hash_from_trits;
+use crate::utils::encode_trits;
+use crate::utils::txn_hash_trytes;
+
+/// The operations a [`Client`][crate::client::Client] performs on the Tangle.
+///
+/// Addresses are tryte-encoded Tangle addresses and messages are the
+/// JSON-encoded DID Documents and diffs published at them.
+#[async_trait(?Send)]
+pub trait Transport: Debug + Send + Sync {
+  /// Publishes `message` at `address`; returns the `MessageId` of the message.
+  async fn publish(&self, address: &str, message: String) -> Result<MessageId>;
+
+  /// Returns all messages published at `address`.
+  async fn messages(&self, address: &str) -> Result<Vec<Message>>;
+
+  /// Returns `true` if the message has been confirmed.
+  async fn is_confirmed(&self, message_id: &MessageId) -> Result<bool>;
+
+  /// Returns `true` if the message can be promoted.
+  async fn is_promotable(&self, message_id: &MessageId) -> Result<bool>;
+
+  /// Creates a new attachment of the message; returns the `MessageId` of the
+  /// new attachment.
+  async fn reattach(&self, message_id: &MessageId) -> Result<MessageId>;
+
+  /// Promotes the message; returns the `MessageId` of the promoting message.
+  async fn promote(&self, message_id: &MessageId) -> Result<MessageId>;
+
+  /// Returns the ledger state of `address`.
+  async fn balance(&self, address: &str) -> Result<AddressBalance>;
+
+  /// Returns the contents of the resource at `url`, e.g. the DID
+  /// configuration resource of a linked domain.
+  ///
+  /// Transports can not fetch resources by default.
+  async fn fetch(&self, _url: &Url) -> Result<Vec<u8>> {
+    Err(Error::UnsupportedFetch)
+  }
+
+  /// Returns the URL of the node requests are sent to first - the node that
+  /// most recently served a request - if the transport is connected to nodes.
+  ///
+  /// This is shared by all requests of the transport; concurrent requests
+  /// may be served by other nodes.
+  fn current_node(&self) -> Option<Url> {
+    None
+  }
+}
+
+// =============================================================================
+// =============================================================================
+
+/// The number of message trits carried by a single bundled transaction.
+const PAYLOAD_TRITS: usize = 6561;
+
+/// A [`Transport`] sending requests to IOTA nodes.
+///
+/// Resources are fetched from HTTPS URLs only.
+#[derive(Clone, Debug)]
+pub struct NodeTransport {
+  client: iota::Client,
+  http: reqwest::Client,
+}
+
+impl NodeTransport {
+  /// The maximum duration of a request for a resource.
+  pub const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+  /// The maximum number of redirects followed when fetching a resource.
+  pub const MAX_REDIRECTS: usize = 5;
+
+  /// The maximum number of transactions read from a single address.
+  pub const MAX_TRANSACTIONS: usize = 1000;
+
+  /// The number of transactions requested from a node at once.
+  pub const TRYTES_BATCH_SIZE: usize = 100;
+
+  pub(crate) fn new(client: iota::Client) -> Result<Self> {
+    // Never follow redirects to insecure URLs
+    let redirect: Policy = Policy::custom(|attempt| {
+      if attempt.url().scheme() != "https" {
+        attempt.error("Insecure Redirect")
+      } else if attempt.previous().len() >= Self::MAX_REDIRECTS {
+        attempt.stop()
+      } else {
+        attempt.follow()
+      }
+    });
+
+    let http: reqwest::Client = reqwest::Client::builder()
+      .timeout(Self::FETCH_TIMEOUT)
+      .redirect(redirect)
+      .build()?;
+
+    Ok(Self { client, http })
+  }
+}
+
+#[async_trait(?Send)]
+impl Transport for NodeTransport {
+  async fn publish(&self, address: &str, message: String) -> Result<MessageId> {
+    trace!("Sending Transfer: {}", message);
+
+    let transfer: Transfer = Transfer {
+      address: create_address_from_trits(address)?,
+      value: 0,
+      message: Some(message),
+      tag: None,
+    };
+
+    self
+      .client
+      .send(None)
+      .transfers(vec![transfer])
+      .send()
+      .await?
+      .into_iter()
+      .find(BundledTransaction::is_tail)
+      .ok_or(Error::InvalidBundleTail)
+      .map(|bundled| txn_hash_trytes(&bundled).into())
+  }
+
+  async fn messages(&self, address: &str) -> Result<Vec<Message>> {
+    let address: Address = create_address_from_trits(address)?;
+
+    trace!("Read Transactions: {}", encode_trits(address.to_inner()));
+
+    // Fetch all transaction hashes containing the tangle address.
+    let response: FindTransactionsResponse = self
+      .client
+      .find_transactions()
+      .addresses(from_ref(&address))
+      .send()
+      .await?;
+
+    trace!("Transactions Found: {:?}", __dbg_transactions(&response));
+
+    if response.hashes.is_empty() {
+      return Ok(Vec::new());
+    }
+
+    // Refuse to buffer the content of an unbounded number of transactions.
+    if response.hashes.len() > Self::MAX_TRANSACTIONS {
+      return Err(Error::ResponseTooLarge {
+        size: response.hashes.len() * PAYLOAD_TRITS / 6,
+        limit: Self::MAX_TRANSACTIONS * PAYLOAD_TRITS / 6,
+      });
+    }
+
+    // Fetch the content of all transactions, a bounded batch at a time.
+    let mut transactions: Vec<BundledTransaction> = Vec::with_capacity(response.hashes.len());
+
+    for hashes in response.hashes.chunks(Self::TRYTES_BATCH_SIZE) {
+      let content: GetTrytesResponse = self.client.get_trytes(hashes).await?;
+
+      trace!("Transaction Trytes: {:?}", __dbg_trytes(&content));
+
+      transactions.extend(content.trytes);
+    }
+
+    if transactions.is_empty() {
+      return Err(Error::InvalidTransactionTrytes);
+    }
+
+    // Re-build the fragmented messages stored in the bundle.
+    bundles_from_trytes(transactions)
+      .into_iter()
+      .map(Message::try_from_bundle)
+      .collect()
+  }
+
+  async fn is_confirmed(&self, message_id: &MessageId) -> Result<bool> {
+    let hash: Hash = create_hash_from_trits(message_id.as_str())?;
+    let states: Vec<bool> = self.client.get_latest_inclusion(from_ref(&hash)).await?;
+
+    Ok(states.first().copied().unwrap_or(false))
+  }
+
+  async fn is_promotable(&self, message_id: &MessageId) -> Result<bool> {
+    let hash: Hash = create_hash_from_trits(message_id.as_str())?;
+
+    self.client.is_promotable(&hash).await.map_err(Into::into)
+  }
+
+  async fn reattach(&self, message_id: &MessageId) -> Result<MessageId> {
+    let hash: Hash = create_hash_from_trits(message_id.as_str())?;
+
+    self
+      .client
+      .replay_bundle(&hash)
+      .send()
+      .await?
+      .into_iter()
+      .find(BundledTransaction::is_tail)
+      .ok_or(Error::InvalidBundleTail)
+      .map(|bundled| txn_hash_trytes(&bundled).into())
+  }
+
+  async fn promote(&self, message_id: &MessageId) -> Result<MessageId> {
+    let hash: Hash = create_hash_from_trits(message_id.as_str())?;
+
+    let transfer: Transfer = Transfer {
+      address: Address::zeros(),
+      value: 0,
+      message: None,
+      tag: None,
+    };
+
+    self
+      .client
+      .send(None)
+      .transfers(vec![transfer])
+      .reference(hash)
+      .send()
+      .await?
+      .into_iter()
+      .find(BundledTransaction::is_tail)
+      .ok_or(Error::InvalidBundleTail)
+      .map(|bundled| txn_hash_trytes(&bundled).into())
+  }
+
+  async fn balance(&self, address: &str) -> Result<AddressBalance> {
+    let address: Address = create_address_from_trits(address)?;
+
+    let response: GetBalancesResponse = self.client.get_balances().addresses(from_ref(&address)).send().await?;
+
+    let spent: Vec<bool> = self.client.were_addresses_spent_from(from_ref(&address)).await?.states;
+
+    Ok(AddressBalance {
+      address: encode_trits(address.to_inner()),
+      balance: response.balances.first().copied().unwrap_or_default(),
+      spent: spent.first().copied().unwrap_or(false),
+      milestone_index: response.milestone_index,
+    })
+  }
+
+  async fn fetch(&self, url: &Url) -> Result<Vec<u8>> {
+    trace!("Fetch Resource: {}", url);
+
+    if url.scheme() != "https" {
+      return Err(Error::InvalidResourceUrl(url.to_string()));
+    }
+
+    let limit: usize = self.max_message_size;
+    let mut response: reqwest::Response = self.http.get(url.as_str()).send().await?.error_for_status()?;
+
+    if let Some(size) = response.content_length() {
+      if size > limit as u64 {
+        return Err(Error::ResponseTooLarge {
+          size: size as usize,
+          limit,
+        });
+      }
+    }
+
+    // The content length is optional and may be wrong; stream the body and
+    // stop reading once the limit is exceeded
+    let mut data: Vec<u8> = Vec::new();
+
+    while let Some(chunk) = response.chunk().await? {
+      if data.len() + chunk.len() > limit {
+        return Err(Error::ResponseTooLarge {
+          size: data.len() + chunk.len(),
+          limit,
+        });
+      }
+
+      data.extend_from_slice(&chunk);
+    }
+
+    Ok(data)
+  }
+}
+
+fn __dbg_transactions(response: &FindTransactionsResponse) -> Vec<String> {
+  response.hashes.iter().map(|hash| encode_trits(hash)).collect()
+}
+
+fn __dbg_trytes(response: &GetTrytesResponse) -> Vec<TxnPrinter<'_>> {
+  response.trytes.iter().map(TxnPrinter::full).collect()
+}
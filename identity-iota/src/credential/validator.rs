@@ -8,8 +8,10 @@ use identity_credential::presentation::VerifiablePresentation;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::collections::BTreeMap;
+use std::collections::HashMap;
 
 use crate::client::Client;
+use crate::client::ResolutionResult;
 use crate::did::Document;
 use crate::did::DID;
 use crate::error::Error;
@@ -77,12 +79,23 @@ impl<'a> CredentialValidator<'a> {
   /// Note: The credential issuer URL is expected to be a valid DID.
   /// Note: Credential subject IDs are expected to be valid DIDs (if present).
   pub async fn validate_credential<T>(&self, credential: VerifiableCredential<T>) -> Result<CredentialValidation<T>>
+  where
+    T: Serialize,
+  {
+    self.validate_credential_with(credential, &HashMap::new()).await
+  }
+
+  async fn validate_credential_with<T>(
+    &self,
+    credential: VerifiableCredential<T>,
+    resolved: &HashMap<DID, ResolutionResult>,
+  ) -> Result<CredentialValidation<T>>
   where
     T: Serialize,
   {
     // Resolve the issuer DID Document and validate the digital signature.
     let issuer_url: &str = credential.issuer.url().as_str();
-    let issuer_doc: DocumentValidation = self.validate_document(issuer_url).await?;
+    let issuer_doc: DocumentValidation = self.validate_document(issuer_url, resolved).await?;
 
     let mut subjects: BTreeMap<String, DocumentValidation> = BTreeMap::new();
 
@@ -92,7 +105,7 @@ impl<'a> CredentialValidator<'a> {
       .iter()
       .filter_map(|subject| subject.id.as_ref())
     {
-      subjects.insert(id.to_string(), self.validate_document(id.as_str()).await?);
+      subjects.insert(id.to_string(), self.validate_document(id.as_str(), resolved).await?);
     }
 
     // Verify the credential signature using the issuers DID Document
@@ -131,13 +144,16 @@ impl<'a> CredentialValidator<'a> {
       .ok_or(Error::InvalidPresentationHolder)?;
 
     // Resolve the holder DID Document and validate the digital signature.
-    let holder_doc: DocumentValidation = self.validate_document(holder_url).await?;
+    let holder_doc: DocumentValidation = self.validate_document(holder_url, &HashMap::new()).await?;
+
+    // Resolve each credential issuer once, regardless of how many credentials it issued.
+    let issuers: HashMap<DID, ResolutionResult> = self.resolve_issuers(&presentation).await?;
 
     let mut credentials: Vec<CredentialValidation<U>> = Vec::new();
 
     // Resolve and validate all associated credentials.
     for credential in presentation.verifiable_credential.iter() {
-      credentials.push(self.validate_credential(credential.clone()).await?);
+      credentials.push(self.validate_credential_with(credential.clone(), &issuers).await?);
     }
 
     // Verify the presentation signature using the holders DID Document
@@ -157,9 +173,72 @@ impl<'a> CredentialValidator<'a> {
     })
   }
 
-  async fn validate_document(&self, did: &str) -> Result<DocumentValidation> {
+  /// Verifies the `VerifiablePresentation` was signed by the authentication
+  /// method of its holder and all embedded credentials are verified.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`Error::InvalidPresentationProof`] if the holder signature is
+  /// invalid, or [`Error::InvalidPresentationCredential`] with the index of
+  /// the first credential that failed validation.
+  pub async fn verify_presentation<T, U>(&self, presentation: &VerifiablePresentation<T, U>) -> Result<()>
+  where
+    T: Serialize,
+    U: Clone + Serialize,
+  {
+    let holder: DID = presentation
+      .holder
+      .as_ref()
+      .map(|holder| holder.as_str())
+      .ok_or(Error::InvalidPresentationHolder)?
+      .parse()?;
+
+    // Resolve the holder DID Document and check the holder binding.
+    self
+      .client
+      .read_document(&holder)
+      .await?
+      .verify_presentation(presentation)?;
+
+    let issuers: HashMap<DID, ResolutionResult> = self.resolve_issuers(presentation).await?;
+
+    for (index, credential) in presentation.verifiable_credential.iter().enumerate() {
+      if !self
+        .validate_credential_with(credential.clone(), &issuers)
+        .await?
+        .verified
+      {
+        return Err(Error::InvalidPresentationCredential(index));
+      }
+    }
+
+    Ok(())
+  }
+
+  async fn resolve_issuers<T, U>(
+    &self,
+    presentation: &VerifiablePresentation<T, U>,
+  ) -> Result<HashMap<DID, ResolutionResult>> {
+    let issuers: Vec<DID> = presentation
+      .verifiable_credential
+      .iter()
+      .map(|credential| credential.issuer.url().as_str().parse())
+      .collect::<Result<_>>()?;
+
+    self.client.resolve_many(&issuers).await
+  }
+
+  async fn validate_document(
+    &self,
+    did: &str,
+    resolved: &HashMap<DID, ResolutionResult>,
+  ) -> Result<DocumentValidation> {
     let did: DID = did.parse()?;
-    let document: Document = self.client.read_document(&did).await?;
+
+    let document: Document = match resolved.get(&did) {
+      Some(result) => result.document().clone(),
+      None => self.client.read_document(&did).await?,
+    };
     let verified: bool = document.verify().is_ok();
 
     Ok(DocumentValidation {
@@ -170,3 +249,54 @@ impl<'a> CredentialValidator<'a> {
     })
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use futures::executor::block_on;
+  use identity_core::common::Url;
+  use identity_core::crypto::KeyPair;
+  use std::collections::HashMap;
+
+  use crate::client::Client;
+  use crate::credential::CredentialValidator;
+  use crate::credential::DocumentValidation;
+  use crate::did::Document;
+  use crate::did::DocumentDiff;
+  use crate::tangle::TangleRef;
+
+  #[test]
+  fn test_validate_document_with_diff() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    document.sign(keypair.secret()).unwrap();
+
+    let client: Client = Client::mock();
+
+    block_on(async {
+      document.publish(&client).await.unwrap();
+
+      let mut updated: Document = document.clone();
+
+      updated
+        .insert_linked_domains("linked-domain", Url::parse("https://example.com").unwrap())
+        .unwrap();
+      updated.sign(keypair.secret()).unwrap();
+
+      let mut diff: DocumentDiff = document
+        .diff(&updated, document.message_id().clone(), keypair.secret())
+        .unwrap();
+
+      diff.publish(document.message_id(), &client).await.unwrap();
+
+      // The resolved document is the result of merging the diff
+      let validation: DocumentValidation = CredentialValidator::new(&client)
+        .validate_document(document.id().as_str(), &HashMap::new())
+        .await
+        .unwrap();
+
+      assert_eq!(validation.document.service(), updated.service());
+      assert!(validation.verified);
+    });
+  }
+}
@@ -1,6 +1,11 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use identity_core::common::Object;
+use identity_core::common::Value;
 use identity_core::convert::AsJson;
 use identity_core::convert::SerdeInto;
 use identity_core::crypto::SetSignature;
@@ -8,8 +13,14 @@ use identity_core::crypto::Signature;
 use identity_core::crypto::TrySignature;
 use identity_core::crypto::TrySignatureMut;
 use identity_core::diff::Diff;
+use identity_core::utils::decode_b64;
+use identity_core::utils::encode_b58;
+use identity_core::utils::encode_b64;
 use identity_did::diff::DiffDocument;
 use identity_did::document::Document as CoreDocument;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::io::Write;
 
 use crate::client::Client;
 use crate::client::Network;
@@ -20,26 +31,68 @@ use crate::error::Result;
 use crate::tangle::MessageId;
 use crate::tangle::TangleRef;
 
+/// The DID Document properties containing sets of identifiable items.
+const SETS: &[&str] = &[
+  "verificationMethod",
+  "authentication",
+  "assertionMethod",
+  "keyAgreement",
+  "capabilityDelegation",
+  "capabilityInvocation",
+  "service",
+];
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct DocumentDiff {
   pub(crate) did: DID,
   pub(crate) diff: String,
   pub(crate) previous_message_id: MessageId,
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  pub(crate) base: BTreeMap<String, String>,
   pub(crate) proof: Option<Signature>,
   #[serde(skip)]
   pub(crate) message_id: MessageId,
 }
 
 impl DocumentDiff {
+  /// The maximum size (in bytes) of a decompressed compact diff (1MB), which
+  /// bounds the memory used to decode untrusted diffs.
+  pub const MAX_COMPACT_BYTES: usize = 1_048_576;
+
+  /// Creates a new `DocumentDiff` with the changes between `current` and
+  /// `updated`.
+  ///
+  /// Only changed fields are included, along with the `proof` of `updated`
+  /// so the merged document stays verifiable. A digest of the previous value
+  /// of each changed field (excluding the `proof`) is recorded to detect
+  /// conflicts with diverged documents.
   pub fn new(current: &Document, updated: &Document, previous_message_id: MessageId) -> Result<Self> {
     let a: CoreDocument = current.serde_into()?;
     let b: CoreDocument = updated.serde_into()?;
     let diff: String = Diff::diff(&a, &b)?.to_json()?;
 
+    let a: Object = Self::unsigned(current)?.serde_into()?;
+    let b: Object = Self::unsigned(updated)?.serde_into()?;
+
+    let before: BTreeMap<String, &Value> = fields(&a);
+    let after: BTreeMap<String, &Value> = fields(&b);
+
+    let mut base: BTreeMap<String, String> = BTreeMap::new();
+
+    for path in before.keys().chain(after.keys()) {
+      // `updated` changes with every update and is not a conflict by itself
+      if path == "updated" || before.get(path) == after.get(path) {
+        continue;
+      }
+
+      base.insert(path.clone(), digest(before.get(path).copied())?);
+    }
+
     Ok(Self {
       did: current.id().clone(),
       previous_message_id,
       diff,
+      base,
       proof: None,
       message_id: MessageId::NONE,
     })
@@ -65,6 +118,33 @@ impl DocumentDiff {
     self.proof.as_ref()
   }
 
+  /// Returns the paths of the fields changed by the diff, e.g. `service` or
+  /// `service[did:iota:123#linked-domain]`, each mapped to a digest of the
+  /// value the diff was computed against.
+  pub fn base(&self) -> &BTreeMap<String, String> {
+    &self.base
+  }
+
+  /// Returns the paths of the fields changed by the diff whose value in
+  /// `document` differs from the value the diff was computed against.
+  ///
+  /// Merging a diff with conflicts overwrites the diverged fields of
+  /// `document`. Diffs without base digests never report conflicts.
+  pub fn conflicts(&self, document: &Document) -> Result<Vec<String>> {
+    let current: Object = Self::unsigned(document)?.serde_into()?;
+    let current: BTreeMap<String, &Value> = fields(&current);
+
+    let mut conflicts: Vec<String> = Vec::new();
+
+    for (path, expected) in self.base.iter() {
+      if digest(current.get(path).copied())? != *expected {
+        conflicts.push(path.clone());
+      }
+    }
+
+    Ok(conflicts)
+  }
+
   /// Returns a new DID Document which is the result of merging `self`
   /// with the given Document.
   pub fn merge(&self, document: &Document) -> Result<Document> {
@@ -75,6 +155,52 @@ impl DocumentDiff {
     Ok(this.serde_into()?)
   }
 
+  /// Encodes the `DocumentDiff` as a compact string: the JCS-canonicalized
+  /// JSON, DEFLATE-compressed and base64url-encoded.
+  ///
+  /// The `proof` is part of the encoded data and stays verifiable.
+  pub fn to_compact(&self) -> Result<String> {
+    let mut encoder: DeflateEncoder<Vec<u8>> = DeflateEncoder::new(Vec::new(), Compression::best());
+
+    encoder
+      .write_all(&self.to_jcs()?)
+      .map_err(|_| Error::InvalidDiffEncoding)?;
+
+    let data: Vec<u8> = encoder.finish().map_err(|_| Error::InvalidDiffEncoding)?;
+
+    Ok(encode_b64(&data))
+  }
+
+  /// Decodes a `DocumentDiff` encoded with [`to_compact`][Self::to_compact].
+  ///
+  /// Diffs larger than [`MAX_COMPACT_BYTES`][Self::MAX_COMPACT_BYTES] once
+  /// decompressed are rejected.
+  pub fn from_compact(data: &str) -> Result<Self> {
+    let data: Vec<u8> = decode_b64(data)?;
+    let mut json: Vec<u8> = Vec::new();
+
+    // Read one byte past the limit to detect oversized diffs without
+    // decompressing them entirely
+    DeflateDecoder::new(&data[..])
+      .take(Self::MAX_COMPACT_BYTES as u64 + 1)
+      .read_to_end(&mut json)
+      .map_err(|_| Error::InvalidDiffEncoding)?;
+
+    if json.len() > Self::MAX_COMPACT_BYTES {
+      return Err(Error::InvalidDiffLength);
+    }
+
+    Self::from_json_slice(&json).map_err(Into::into)
+  }
+
+  fn unsigned(document: &Document) -> Result<CoreDocument> {
+    let mut this: CoreDocument = document.serde_into()?;
+
+    this.properties_mut().remove("proof");
+
+    Ok(this)
+  }
+
   /// Publishes the DID Document diff to the Tangle
   ///
   /// Uses the provided [`client`][``Client``] or a default `Client` based on
@@ -99,6 +225,41 @@ impl DocumentDiff {
   }
 }
 
+/// Returns the values of all top-level fields of the serialized `document`;
+/// sets of identifiable items are split into one field per item id.
+fn fields(document: &Object) -> BTreeMap<String, &Value> {
+  let mut fields: BTreeMap<String, &Value> = BTreeMap::new();
+
+  for (key, value) in document.iter() {
+    match value.as_array() {
+      Some(items) if SETS.contains(&key.as_str()) => {
+        for (index, item) in items.iter().enumerate() {
+          let path: String = match item.as_str().or_else(|| item.get("id").and_then(Value::as_str)) {
+            Some(id) => format!("{}[{}]", key, id),
+            None => format!("{}[{}]", key, index),
+          };
+
+          fields.insert(path, item);
+        }
+      }
+      Some(_) | None => {
+        fields.insert(key.clone(), value);
+      }
+    }
+  }
+
+  fields
+}
+
+/// Returns a digest of the JCS representation of `value`; missing values have
+/// an empty digest.
+fn digest(value: Option<&Value>) -> Result<String> {
+  match value {
+    Some(value) => Ok(encode_b58(&value.to_jcs_sha256()?)),
+    None => Ok(String::new()),
+  }
+}
+
 impl TangleRef for DocumentDiff {
   fn message_id(&self) -> &MessageId {
     &self.message_id
@@ -134,3 +295,144 @@ impl SetSignature for DocumentDiff {
     self.proof = Some(value);
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Object;
+  use identity_core::common::Timestamp;
+  use identity_core::common::Url;
+  use identity_core::common::Value;
+  use identity_core::convert::FromJson;
+  use identity_core::crypto::KeyPair;
+
+  use super::*;
+
+  #[test]
+  fn test_minimal_service_diff() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut current: Document = Document::from_keypair(&keypair).unwrap();
+
+    current.sign(keypair.secret()).unwrap();
+    current.set_message_id(MessageId::new("CURRENT".to_string()));
+
+    let mut updated: Document = current.clone();
+
+    updated
+      .insert_linked_domains("linked-domain", Url::parse("https://example.com").unwrap())
+      .unwrap();
+    updated.set_updated(Timestamp::parse("2030-01-01T00:00:00Z").unwrap());
+    updated.sign(keypair.secret()).unwrap();
+
+    let diff: DocumentDiff = current
+      .diff(&updated, current.message_id().clone(), keypair.secret())
+      .unwrap();
+
+    let data: Object = Object::from_json(diff.diff()).unwrap();
+    let mut keys: Vec<&str> = data.keys().map(String::as_str).collect();
+
+    keys.sort_unstable();
+
+    // Unchanged methods are not restated
+    assert_eq!(keys, ["properties", "service"]);
+
+    // Only the `updated` timestamp and the proof changed
+    let properties: &[Value] = data["properties"].as_array().unwrap();
+    let mut changed: Vec<&str> = properties.iter().filter_map(|item| item["c:k"].as_str()).collect();
+
+    changed.sort_unstable();
+
+    assert_eq!(changed, ["proof", "updated"]);
+
+    let merged: Document = diff.merge(&current).unwrap();
+
+    assert_eq!(merged.as_document(), updated.as_document());
+    assert!(merged.verify().is_ok());
+    assert!(current.verify_data(&diff).is_ok());
+  }
+
+  #[test]
+  fn test_conflicts() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut current: Document = Document::from_keypair(&keypair).unwrap();
+
+    current.sign(keypair.secret()).unwrap();
+    current.set_message_id(MessageId::new("CURRENT".to_string()));
+
+    let mut updated: Document = current.clone();
+
+    updated
+      .insert_linked_domains("linked-domain", Url::parse("https://example.com").unwrap())
+      .unwrap();
+    updated.set_updated(Timestamp::parse("2030-01-01T00:00:00Z").unwrap());
+    updated.sign(keypair.secret()).unwrap();
+
+    let diff: DocumentDiff = current
+      .diff(&updated, current.message_id().clone(), keypair.secret())
+      .unwrap();
+
+    let path: String = format!("service[{}#linked-domain]", current.id());
+
+    assert_eq!(diff.base().keys().collect::<Vec<_>>(), [&path]);
+    assert!(current.merge_conflicts(&diff).unwrap().is_empty());
+
+    // Unrelated local changes do not conflict
+    let mut local: Document = current.clone();
+
+    local.set_updated(Timestamp::parse("2029-01-01T00:00:00Z").unwrap());
+
+    assert!(local.merge_conflicts(&diff).unwrap().is_empty());
+
+    // A local service with the same id conflicts
+    local
+      .insert_linked_domains("linked-domain", Url::parse("https://example.org").unwrap())
+      .unwrap();
+
+    assert_eq!(local.merge_conflicts(&diff).unwrap(), [path]);
+  }
+
+  #[test]
+  fn test_compact() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut current: Document = Document::from_keypair(&keypair).unwrap();
+
+    current.sign(keypair.secret()).unwrap();
+    current.set_message_id(MessageId::new("CURRENT".to_string()));
+
+    let mut updated: Document = current.clone();
+
+    updated
+      .insert_linked_domains("linked-domain", Url::parse("https://example.com").unwrap())
+      .unwrap();
+    updated.sign(keypair.secret()).unwrap();
+
+    let diff: DocumentDiff = current
+      .diff(&updated, current.message_id().clone(), keypair.secret())
+      .unwrap();
+
+    let compact: String = diff.to_compact().unwrap();
+    let decoded: DocumentDiff = DocumentDiff::from_compact(&compact).unwrap();
+
+    assert_eq!(decoded, diff);
+    assert!(compact.len() < diff.to_jcs_base64url().unwrap().len());
+    assert!(current.verify_data(&decoded).is_ok());
+
+    assert!(matches!(
+      DocumentDiff::from_compact(&encode_b64(b"not deflated")),
+      Err(Error::InvalidDiffEncoding)
+    ));
+
+    // Oversized payloads are rejected without decompressing them entirely
+    let mut encoder: DeflateEncoder<Vec<u8>> = DeflateEncoder::new(Vec::new(), Compression::best());
+
+    encoder
+      .write_all(&vec![b' '; DocumentDiff::MAX_COMPACT_BYTES + 1])
+      .unwrap();
+
+    let bomb: String = encode_b64(&encoder.finish().unwrap());
+
+    assert!(matches!(
+      DocumentDiff::from_compact(&bomb),
+      Err(Error::InvalidDiffLength)
+    ));
+  }
+}
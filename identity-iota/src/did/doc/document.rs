@@ -7,19 +7,33 @@ use core::fmt::Display;
 use core::fmt::Formatter;
 use core::fmt::Result as FmtResult;
 use core::ops::Deref;
+use identity_core::common::Context;
 use identity_core::common::Object;
+use identity_core::common::OneOrMany;
 use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use identity_core::common::Value;
+use identity_core::convert::FromJson;
 use identity_core::convert::SerdeInto;
+use identity_core::crypto::JcsEd25519Signature2020;
 use identity_core::crypto::KeyPair;
 use identity_core::crypto::SecretKey;
 use identity_core::crypto::SetSignature;
 use identity_core::crypto::Signature;
+use identity_core::crypto::SignatureName;
+use identity_core::crypto::SignatureValue;
 use identity_core::crypto::TrySignature;
 use identity_core::crypto::TrySignatureMut;
+use identity_core::utils::encode_b58;
+use identity_credential::credential::VerifiableCredential;
+use identity_credential::presentation::VerifiablePresentation;
+use identity_did::did::DID as CoreDID;
 use identity_did::document::Document as CoreDocument;
+use identity_did::service::Service;
 use identity_did::verifiable::DocumentSigner;
 use identity_did::verifiable::DocumentVerifier;
 use identity_did::verifiable::Properties as VerifiableProperties;
+use identity_did::verifiable::SignatureSuiteRegistry;
 use identity_did::verification::Method as CoreMethod;
 use identity_did::verification::MethodQuery;
 use identity_did::verification::MethodRef;
@@ -29,9 +43,15 @@ use serde::Serialize;
 
 use crate::client::Client;
 use crate::client::Network;
+use crate::client::PublishOptions;
+use crate::client::PublishReceipt;
+use crate::client::PublishResult;
+use crate::did::doc::reconcile::rebase_document;
 use crate::did::DocumentDiff;
 use crate::did::Method;
 use crate::did::Properties as BaseProperties;
+use crate::did::ReconcilePolicy;
+use crate::did::Reconciliation;
 use crate::did::DID;
 use crate::error::Error;
 use crate::error::Result;
@@ -40,10 +60,19 @@ use crate::tangle::TangleRef;
 use crate::utils::utf8_to_trytes;
 
 type Properties = VerifiableProperties<BaseProperties>;
-type BaseDocument = CoreDocument<Properties, Object, ()>;
+// Services keep their custom properties (e.g. the `routingKeys` of a
+// `DIDCommMessaging` service); with `()` they would be dropped on
+// deserialization and invalidate the document proof.
+pub(crate) type BaseDocument = CoreDocument<Properties, Object, Object>;
 
-pub type Signer<'a, 'b, 'c> = DocumentSigner<'a, 'b, 'c, Properties, Object, ()>;
-pub type Verifier<'a> = DocumentVerifier<'a, Properties, Object, ()>;
+pub type Signer<'a, 'b, 'c> = DocumentSigner<'a, 'b, 'c, Properties, Object, Object>;
+pub type Verifier<'a> = DocumentVerifier<'a, Properties, Object, Object>;
+
+const ED25519_2020_CONTEXT: &str = "https://w3id.org/security/suites/ed25519-2020/v1";
+const X25519_2019_CONTEXT: &str = "https://w3id.org/security/suites/x25519-2019/v1";
+const JCS_ED25519_2020_CONTEXT: &str = "https://w3id.org/security/suites/jcs-ed25519-2020/v1";
+const DID_CONFIGURATION_CONTEXT: &str = "https://identity.foundation/.well-known/did-configuration/v1";
+const DIDCOMM_MESSAGING_CONTEXT: &str = "https://didcomm.org/messaging/contexts/v2";
 
 /// A DID Document adhering to the IOTA DID method specification.
 ///
@@ -57,6 +86,26 @@ pub struct Document {
 }
 
 impl Document {
+  const METADATA: &'static [&'static str] = &["created", "updated", "immutable", "deactivated", "previous_message_id"];
+
+  /// The JSON-LD contexts defining the terms known to the library; terms of
+  /// the base DID context are omitted.
+  const TERM_CONTEXTS: &'static [(&'static str, &'static str)] = &[
+    ("Ed25519VerificationKey2020", ED25519_2020_CONTEXT),
+    ("X25519KeyAgreementKey2019", X25519_2019_CONTEXT),
+    ("JcsEd25519Signature2020", JCS_ED25519_2020_CONTEXT),
+    ("LinkedDomains", DID_CONFIGURATION_CONTEXT),
+    ("DIDCommMessaging", DIDCOMM_MESSAGING_CONTEXT),
+    ("routingKeys", DIDCOMM_MESSAGING_CONTEXT),
+    ("accept", DIDCOMM_MESSAGING_CONTEXT),
+  ];
+
+  /// The length of an Ed25519 signature attached with `attach_signature`.
+  const SIGNATURE_LENGTH: usize = 64;
+
+  /// The path of the DID configuration resource of a linked domain.
+  pub const DID_CONFIGURATION_PATH: &'static str = "/.well-known/did-configuration.json";
+
   /// Creates a new DID Document from the given KeyPair.
   ///
   /// The DID Document will be pre-populated with a single authentication
@@ -79,6 +128,24 @@ impl Document {
     Ok(unsafe { Self::from_authentication_unchecked(method) })
   }
 
+  /// Creates a new DID Document from the given verification [`method`][`Method`];
+  /// the method is stored under `verificationMethod` and referenced by
+  /// `authentication`.
+  pub fn from_verification_method(method: Method) -> Result<Self> {
+    Self::check_authentication(&method)?;
+
+    let id: CoreDID = method.id().clone().into();
+
+    CoreDocument::builder(Default::default())
+      .id(method.controller().clone().into())
+      .verification_method(method.into())
+      .authentication(id)
+      .build()
+      .map(CoreDocument::into_verifiable)
+      .map(Into::into)
+      .map_err(Into::into)
+  }
+
   /// Creates a new DID Document from the given verification [`method`][`Method`]
   /// without performing validation checks.
   /// # Safety
@@ -127,18 +194,178 @@ impl Document {
     // Ensure the verification method type is supported
     match method.key_type() {
       MethodType::Ed25519VerificationKey2018 => {}
+      MethodType::Ed25519VerificationKey2020 => {}
       MethodType::MerkleKeyCollection2021 => return Err(Error::InvalidDocumentAuthType),
+      MethodType::X25519KeyAgreementKey2019 => return Err(Error::InvalidDocumentAuthType),
       _ => {}
     }
 
     Ok(())
   }
 
+  /// Validates the JSON-LD `@context` of the DID Document.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the `@context` property is missing, malformed, or does not
+  /// contain the base DID context as the first entry.
+  pub fn check_context(&self) -> Result<()> {
+    let context: &Value = self.properties().get("@context").ok_or(Error::InvalidDocumentContext)?;
+
+    let context: OneOrMany<Context> = context.serde_into().map_err(|_| Error::InvalidDocumentContext)?;
+
+    // Ensure the base context is present and in the correct location
+    match context.get(0) {
+      Some(context) if context == BaseDocument::base_context() => Ok(()),
+      Some(_) | None => Err(Error::InvalidDocumentContext),
+    }
+  }
+
+  /// Validates that the DID Document contains no properties beyond those
+  /// defined by the DID Core and IOTA DID method specifications.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the document contains an unknown top-level property.
+  pub fn check_properties(&self) -> Result<()> {
+    match self.properties().keys().find(|key| *key != "@context") {
+      Some(key) => Err(Error::InvalidDocumentProperty(key.clone())),
+      None => Ok(()),
+    }
+  }
+
+  /// Returns the entries of the JSON-LD `@context` of the DID Document; an
+  /// empty list if the document has no `@context`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the `@context` property is malformed.
+  pub fn contexts(&self) -> Result<Vec<Context>> {
+    match self.properties().get("@context") {
+      Some(context) => context
+        .serde_into()
+        .map(OneOrMany::into_vec)
+        .map_err(|_| Error::InvalidDocumentContext),
+      None => Ok(Vec::new()),
+    }
+  }
+
+  /// Adds `context` to the JSON-LD `@context` of the DID Document; returns
+  /// `false` if the context is already present.
+  ///
+  /// The base DID context is inserted as the first entry if the document has
+  /// no `@context`. Contexts must be added before the document is signed.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the `@context` property is malformed.
+  pub fn add_context(&mut self, context: impl Into<Context>) -> Result<bool> {
+    let context: Context = context.into();
+    let mut contexts: Vec<Context> = self.contexts()?;
+
+    if contexts.is_empty() {
+      contexts.push(Context::Url(Url::parse(BaseDocument::base_context())?));
+    }
+
+    if contexts.contains(&context) {
+      return Ok(false);
+    }
+
+    contexts.push(context);
+
+    let contexts: OneOrMany<Context> = contexts.into();
+
+    self.properties_mut().insert("@context".into(), contexts.serde_into()?);
+
+    Ok(true)
+  }
+
+  /// Validates that every term used by the DID Document - the types and
+  /// custom properties of verification methods and services, the type of the
+  /// proof, and custom properties - is covered by an entry of its JSON-LD
+  /// `@context`.
+  ///
+  /// Contexts are not dereferenced: terms known to the library require their
+  /// defining context, all other terms require an inline definition. The
+  /// Tangle bookkeeping properties are not checked as they are reported as
+  /// document metadata.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the `@context` property is invalid (see
+  /// [`check_context`][Self::check_context]) or a term isn't covered.
+  pub fn check_terms(&self) -> Result<()> {
+    self.check_context()?;
+
+    let contexts: Vec<Context> = self.contexts()?;
+
+    let is_covered = |term: &str| {
+      let defined: bool = contexts.iter().any(|context| match context {
+        Context::Obj(object) => object.contains_key(term),
+        Context::Url(_) => false,
+      });
+
+      defined
+        || Self::TERM_CONTEXTS
+          .iter()
+          .filter(|(known, _)| *known == term)
+          .any(|(_, url)| contexts.iter().any(|context| context == url))
+    };
+
+    // Ed25519VerificationKey2018 is defined by the base DID context
+    let methods: _ = self.document.methods().flat_map(|method| {
+      Some(method.key_type())
+        .filter(|type_| *type_ != MethodType::Ed25519VerificationKey2018)
+        .map(MethodType::as_str)
+        .into_iter()
+        .chain(method.properties().keys().map(String::as_str))
+    });
+
+    let services: _ = self.document.service().iter().flat_map(|service| {
+      Some(service.type_())
+        .into_iter()
+        .chain(service.properties().keys().map(String::as_str))
+    });
+
+    let proof: _ = self.signature().map(|signature| signature.type_());
+    let properties: _ = self
+      .properties()
+      .keys()
+      .map(String::as_str)
+      .filter(|key| *key != "@context");
+
+    match methods
+      .chain(services)
+      .chain(proof)
+      .chain(properties)
+      .find(|term| !is_covered(*term))
+    {
+      Some(term) => Err(Error::InvalidDocumentTerm(term.to_string())),
+      None => Ok(()),
+    }
+  }
+
   /// Returns a reference to the underlying [`Document`][`CoreDocument`].
   pub fn as_document(&self) -> &BaseDocument {
     &self.document
   }
 
+  /// Returns the DID Document without the properties used for Tangle
+  /// bookkeeping, which are reported as document metadata when resolving.
+  ///
+  /// The `created`, `updated`, `immutable`, `deactivated`, and
+  /// `previous_message_id` properties are removed. The `proof` is removed as
+  /// well, since it covers the removed properties and would fail verification.
+  pub fn to_core_document(&self) -> Result<CoreDocument> {
+    let mut document: CoreDocument = self.document.serde_into()?;
+
+    for key in Self::METADATA.iter().chain(&["proof"]) {
+      document.properties_mut().remove(*key);
+    }
+
+    Ok(document)
+  }
+
   /// Returns a mutable reference to the underlying [`Document`][`CoreDocument`].
   ///
   /// # Safety
@@ -221,6 +448,16 @@ impl Document {
     self.document.properties_mut().immutable = value;
   }
 
+  /// Returns true if the DID Document has been deactivated.
+  pub fn deactivated(&self) -> bool {
+    self.document.properties().deactivated
+  }
+
+  /// Sets the value of the `deactivated` flag.
+  pub fn set_deactivated(&mut self, value: bool) {
+    self.document.properties_mut().deactivated = value;
+  }
+
   /// Returns a reference to the custom DID Document properties.
   pub fn properties(&self) -> &Object {
     &self.document.properties().properties
@@ -251,6 +488,40 @@ impl Document {
     Ok(())
   }
 
+  /// Removes verification methods with the same key material as a preceding
+  /// method; relationships of a removed method are transferred to the method
+  /// it duplicates. The default authentication method is never removed.
+  ///
+  /// Returns the ids of the removed methods.
+  pub fn dedupe_keys(&mut self) -> Vec<CoreDID> {
+    self.document.dedupe_keys()
+  }
+
+  /// Changes the `type` of the Verification Method matching the provided
+  /// `query`, re-encoding its key data without changing the key material.
+  ///
+  /// # Errors
+  ///
+  /// Fails if no method matches `query` or the migration is not defined for
+  /// the key type of the method.
+  pub fn migrate_method_type<'query, Q>(&mut self, query: Q, key_type: MethodType) -> Result<()>
+  where
+    Q: Into<MethodQuery<'query>>,
+  {
+    self.document.try_resolve_mut(query)?.migrate_key_type(key_type)?;
+
+    Ok(())
+  }
+
+  /// Returns `true` if the Verification Method matching the provided `query`
+  /// is authorized under `capabilityInvocation`.
+  pub fn is_capability_invoker<'query, Q>(&self, query: Q) -> bool
+  where
+    Q: Into<MethodQuery<'query>>,
+  {
+    self.document.is_capability_invoker(query)
+  }
+
   #[doc(hidden)]
   pub fn try_resolve_mut<'query, Q>(&mut self, query: Q) -> Result<&mut CoreMethod>
   where
@@ -259,6 +530,83 @@ impl Document {
     self.document.try_resolve_mut(query).map_err(Into::into)
   }
 
+  // ===========================================================================
+  // Services
+  // ===========================================================================
+
+  /// Adds a new Service to the DID Document.
+  pub fn insert_service(&mut self, service: Service) -> bool {
+    self.document.service_mut().append(service.into())
+  }
+
+  /// Removes the Service with the given id.
+  ///
+  /// `query` is either an absolute DID URL or a fragment (`#domain`), which is
+  /// resolved against the DID of the document.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `query` is not a valid DID URL or no Service matches `query`.
+  pub fn remove_service(&mut self, query: &str) -> Result<()> {
+    let id: CoreDID = if query.starts_with('#') {
+      self.id().join(query)?.into()
+    } else {
+      CoreDID::parse(query)?
+    };
+
+    if !self.document.service().iter().any(|service| service.id() == &id) {
+      return Err(Error::MissingService(query.to_string()));
+    }
+
+    self.document.service_mut().remove(&id);
+
+    Ok(())
+  }
+
+  /// Adds a new `LinkedDomains` Service pointing at the given `origin`.
+  pub fn insert_linked_domains(&mut self, fragment: &str, origin: Url) -> Result<bool> {
+    let service: Service = Service::builder(Object::new())
+      .id(self.id().join(format!("#{}", fragment))?.into())
+      .type_("LinkedDomains")
+      .service_endpoint(origin)
+      .build()?;
+
+    Ok(self.insert_service(service))
+  }
+
+  /// Adds a new `DIDCommMessaging` Service with the given `endpoint`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `routing_keys` is empty.
+  pub fn insert_didcomm_messaging(
+    &mut self,
+    fragment: &str,
+    endpoint: Url,
+    routing_keys: Vec<String>,
+    accept: Vec<String>,
+  ) -> Result<bool> {
+    if routing_keys.is_empty() {
+      return Err(Error::InvalidServiceRoutingKeys);
+    }
+
+    let mut properties: Object = Object::new();
+
+    properties.insert("routingKeys".into(), routing_keys.into());
+
+    if !accept.is_empty() {
+      properties.insert("accept".into(), accept.into());
+    }
+
+    let service: Service = Service::builder(properties)
+      .id(self.id().join(format!("#{}", fragment))?.into())
+      .type_("DIDCommMessaging")
+      .service_endpoint(endpoint)
+      .build()?;
+
+    Ok(self.insert_service(service))
+  }
+
   // ===========================================================================
   // Signatures
   // ===========================================================================
@@ -280,9 +628,92 @@ impl Document {
   /// # Errors
   ///
   /// Fails if an unsupported verification method is used, document
-  /// serialization fails, or the verification operation fails.
+  /// serialization fails, or the verification operation fails. Documents
+  /// signed with a Merkle Key Collection method must have a
+  /// `MerkleKeySignature2021` proof.
   pub fn verify(&self) -> Result<()> {
-    self.document.verify_this().map_err(Into::into)
+    self.document.verify_this_with_revocation().map_err(Into::into)
+  }
+
+  /// Returns the verification method referenced by the `proof` of the DID
+  /// document.
+  ///
+  /// The proof itself is not verified; see [`verify`][`Document::verify`].
+  ///
+  /// # Errors
+  ///
+  /// Fails if the DID document has no proof or the method is not found.
+  pub fn method_for_proof(&self) -> Result<&CoreMethod> {
+    let signature: &Signature = self.try_signature()?;
+
+    self.document.try_resolve(signature).map_err(Into::into)
+  }
+
+  /// Writes an unsigned `JcsEd25519Signature2020` proof referencing the
+  /// verification method matching `query` and returns the bytes to sign.
+  ///
+  /// This allows signing with a key held outside of the process (e.g. in an
+  /// HSM): the returned payload is signed externally and the signature is
+  /// installed with [`attach_signature`][`Document::attach_signature`]. The
+  /// document must not be modified in between.
+  ///
+  /// # Errors
+  ///
+  /// Fails if no method matches `query`, the method is not an `Ed25519`
+  /// method, or document serialization fails.
+  pub fn signing_payload<'query, Q>(&mut self, query: Q) -> Result<Vec<u8>>
+  where
+    Q: Into<MethodQuery<'query>>,
+  {
+    let method: &CoreMethod = self.document.try_resolve(query)?;
+
+    if !matches!(
+      method.key_type(),
+      MethodType::Ed25519VerificationKey2018 | MethodType::Ed25519VerificationKey2020
+    ) {
+      return Err(identity_did::Error::InvalidMethodType.into());
+    }
+
+    let signature: Signature = Signature::new(JcsEd25519Signature2020.name(), method.try_into_fragment()?);
+
+    self.set_signature(signature);
+    self.document.signing_input().map_err(Into::into)
+  }
+
+  /// Installs the `signature` of the payload returned by
+  /// [`signing_payload`][`Document::signing_payload`] into the proof of the
+  /// DID document.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the proof does not reference the method matching `query`,
+  /// `signature` is not a 64-byte Ed25519 signature, or the signature was not
+  /// created with the key of the method.
+  pub fn attach_signature<'query, Q>(&mut self, query: Q, signature: &[u8]) -> Result<()>
+  where
+    Q: Into<MethodQuery<'query>>,
+  {
+    let fragment: String = self.document.try_resolve(query)?.try_into_fragment()?;
+    let proof: &mut Signature = self.try_signature_mut()?;
+
+    if proof.verification_method() != fragment {
+      return Err(Error::InvalidProofMethod);
+    }
+
+    if signature.len() != Self::SIGNATURE_LENGTH {
+      return Err(identity_core::Error::InvalidSigLength(signature.len(), Self::SIGNATURE_LENGTH).into());
+    }
+
+    proof.set_value(SignatureValue::Signature(encode_b58(signature)));
+
+    // Don't leave a proof behind that resolvers would reject
+    if self.verify().is_err() {
+      self.try_signature_mut()?.clear_value();
+
+      return Err(Error::InvalidProofKey);
+    }
+
+    Ok(())
   }
 
   /// Signs the provided data with the default authentication method.
@@ -319,104 +750,589 @@ impl Document {
     self.document.verifier().verify(data).map_err(Into::into)
   }
 
-  // ===========================================================================
-  // Diffs
-  // ===========================================================================
+  /// Verifies the signature of the provided data, dispatching signatures of
+  /// `Ed25519` methods to the suite in `registry` matching the proof `type`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if no suite is registered for the proof `type`, an unsupported
+  /// verification method is used, or the verification operation fails.
+  pub fn verify_data_with_registry<X>(&self, data: &X, registry: &SignatureSuiteRegistry) -> Result<()>
+  where
+    X: Serialize + TrySignature,
+  {
+    self
+      .document
+      .verifier()
+      .registry(registry)
+      .verify(data)
+      .map_err(Into::into)
+  }
 
-  /// Creates a `DocumentDiff` representing the changes between `self` and `other`.
+  /// Verifies the signature of the provided data and ensures the signing
+  /// method has the same key material as the method with the same id in the
+  /// `pinned` document.
   ///
-  /// The returned `DocumentDiff` will have a digital signature created using the
-  /// default authentication method and `secret`.
+  /// This detects keys that were silently changed after `pinned` was first
+  /// resolved and trusted.
   ///
   /// # Errors
   ///
-  /// Fails if the diff operation or signature operation fails.
-  pub fn diff(&self, other: &Self, message: MessageId, secret: &SecretKey) -> Result<DocumentDiff> {
-    let mut diff: DocumentDiff = DocumentDiff::new(self, other, message)?;
+  /// Fails if [`verify_data`][`Document::verify_data`] fails, `pinned` has a
+  /// different DID, or the signing method is missing or changed in `pinned`.
+  pub fn verify_data_pinned<X>(&self, data: &X, pinned: &Document) -> Result<()>
+  where
+    X: Serialize + TrySignature,
+  {
+    if self.id() != pinned.id() {
+      return Err(Error::InvalidPinnedDID);
+    }
 
-    self.sign_data(&mut diff, secret)?;
+    self.verify_data(data)?;
 
-    Ok(diff)
+    let signature: &Signature = data.try_signature()?;
+    let method: &CoreMethod = self.document.try_resolve(signature)?;
+
+    match pinned.document.resolve(signature) {
+      Some(pinned) if pinned.key_type() == method.key_type() && pinned.key_data() == method.key_data() => Ok(()),
+      Some(_) | None => Err(Error::InvalidPinnedKey(method.id().to_string())),
+    }
   }
 
-  /// Verifies a `DocumentDiff` signature and merges the changes into `self`.
+  /// Verifies that the `presentation` is held by the DID of this document and
+  /// was signed with its default authentication method.
   ///
-  /// If merging fails `self` remains unmodified, otherwise `self` represents
-  /// the merged document state.
+  /// Note: The embedded credentials are not verified; use
+  /// [`CredentialValidator::verify_presentation`][crate::credential::CredentialValidator::verify_presentation]
+  /// to verify the credentials as well.
   ///
   /// # Errors
   ///
-  /// Fails if the merge operation or signature operation fails.
-  pub fn merge(&mut self, diff: &DocumentDiff) -> Result<()> {
-    self.verify_data(diff)?;
+  /// Returns [`Error::InvalidPresentationHolder`] if the presentation holder is
+  /// not the DID of this document, or [`Error::InvalidPresentationProof`] if the
+  /// presentation proof was not created by the authentication method.
+  pub fn verify_presentation<T, U>(&self, presentation: &VerifiablePresentation<T, U>) -> Result<()>
+  where
+    T: Serialize,
+    U: Serialize,
+  {
+    match presentation.holder.as_ref() {
+      Some(holder) if holder.as_str() == self.id().as_str() => {}
+      Some(_) | None => return Err(Error::InvalidPresentationHolder),
+    }
 
-    *self = diff.merge(self)?;
+    self
+      .document
+      .verifier()
+      .verify_with(presentation, self.authentication_id())
+      .map_err(|_| Error::InvalidPresentationProof)
+  }
 
-    Ok(())
+  /// Verifies that the DID configuration resource `configuration` served by
+  /// `origin` contains a valid domain linkage credential for this DID.
+  ///
+  /// A credential is valid if it is a `DomainLinkageCredential` issued by and
+  /// about this DID for `origin`, has not expired, and was signed with a
+  /// verification method of this document. Credentials in JWT format are not
+  /// supported and ignored.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `configuration` is not a DID configuration resource or has no
+  /// valid domain linkage credential.
+  pub fn verify_domain_linkage(&self, origin: &Url, configuration: &[u8]) -> Result<()> {
+    let configuration: Object = FromJson::from_json_slice(configuration)?;
+
+    let credentials: &[Value] = configuration
+      .get("linked_dids")
+      .and_then(Value::as_array)
+      .map(Vec::as_slice)
+      .ok_or(Error::InvalidDomainLinkage)?;
+
+    let now: Timestamp = Timestamp::now();
+
+    for credential in credentials.iter().filter(|credential| credential.is_object()) {
+      let credential: VerifiableCredential = match credential.serde_into() {
+        Ok(credential) => credential,
+        Err(_) => continue,
+      };
+
+      if self.is_domain_linkage(&credential, origin, now) && self.verify_data(&credential).is_ok() {
+        return Ok(());
+      }
+    }
+
+    Err(Error::InvalidDomainLinkage)
   }
 
-  // ===========================================================================
-  // Publishing
-  // ===========================================================================
+  fn is_domain_linkage(&self, credential: &VerifiableCredential, origin: &Url, now: Timestamp) -> bool {
+    let did: &str = self.id().as_str();
+
+    credential.types.iter().any(|type_| type_ == "DomainLinkageCredential")
+      && credential.issuer.url() == did
+      && credential.expiration_date.map_or(true, |expiration| expiration >= now)
+      && credential.credential_subject.iter().any(|subject| {
+        let linked: Option<Url> = subject
+          .properties
+          .get("origin")
+          .and_then(Value::as_str)
+          .and_then(|value| Url::parse(value).ok());
+
+        // Origins are compared by scheme, host, and port
+        subject.id.as_ref().map_or(false, |id| id == did)
+          && linked.map_or(false, |linked| linked.origin() == origin.origin())
+      })
+  }
 
-  /// Publishes the DID Document to the Tangle
+  /// Verifies that `data` has valid proofs from at least `threshold` distinct
+  /// authentication methods.
   ///
-  /// Uses the provided [`client`][``Client``] or a default `Client` based on
-  /// the DID network.
-  pub async fn publish<'client, C>(&mut self, client: C) -> Result<()>
-  where
-    C: Into<Option<&'client Client>>,
-  {
-    let network: Network = self.id().into();
-
-    // Publish the DID Document to the Tangle.
-    let message: MessageId = match client.into() {
-      Some(client) if client.network() == network => client.publish_document(self).await?,
-      Some(_) => return Err(Error::InvalidDIDNetwork),
-      None => Client::from_network(network)?.publish_document(self).await?,
-    };
+  /// The `proof` property of `data` may contain a single proof or a set of
+  /// proofs, each created independently over `data` without a `proof`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `data` has no proof or fewer than `threshold` valid proofs were
+  /// created with authentication methods of the DID document.
+  pub fn verify_threshold(&self, data: &Object, threshold: usize) -> Result<()> {
+    let proofs: OneOrMany<Signature> = data.get("proof").ok_or(Error::InvalidProofThreshold)?.serde_into()?;
+
+    let mut unsigned: Object = data.clone();
+    let mut signers: Vec<&str> = Vec::new();
+
+    unsigned.remove("proof");
+
+    for proof in proofs.iter() {
+      let method: &CoreMethod = match self.document.resolve(proof) {
+        Some(method) => method,
+        None => continue,
+      };
+
+      // Only proofs from authentication methods count towards the threshold
+      if !self
+        .document
+        .authentication()
+        .iter()
+        .any(|auth| auth.id() == method.id())
+      {
+        continue;
+      }
+
+      // Each method is only counted once
+      if signers.contains(&method.id().as_str()) {
+        continue;
+      }
+
+      let signed: VerifiableProperties = VerifiableProperties::with_proof(unsigned.clone(), proof.clone());
+
+      if self.verify_data(&signed).is_ok() {
+        signers.push(method.id().as_str());
+      }
+    }
 
-    // Update the `self` with the `MessageId` of the bundled transaction.
-    self.set_message_id(message);
+    if signers.len() < threshold {
+      return Err(Error::InvalidProofThreshold);
+    }
 
     Ok(())
   }
 
-  /// Returns the Tangle address of the DID diff chain.
-  pub fn diff_address(message_id: &MessageId) -> Result<String> {
-    if message_id.is_none() {
-      return Err(Error::InvalidDocumentMessageId);
-    }
+  /// Adds `proof` to the `proof` property of `data`, replacing an existing
+  /// proof with the same `proofPurpose`.
+  ///
+  /// Proofs with a different purpose are kept; each proof in the resulting
+  /// set must be created independently over `data` without a `proof`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the existing `proof` property of `data` is invalid.
+  pub fn insert_proof(data: &mut Object, proof: Signature) -> Result<()> {
+    let mut proofs: Vec<Signature> = match data.remove("proof") {
+      Some(proofs) => proofs.serde_into::<OneOrMany<Signature>>()?.into_vec(),
+      None => Vec::new(),
+    };
 
-    let hash: String = DID::encode_key(message_id.as_str().as_bytes());
+    proofs.retain(|existing| existing.purpose() != proof.purpose());
+    proofs.push(proof);
 
-    let mut trytes: String = utf8_to_trytes(&hash);
-    trytes.truncate(iota_constants::HASH_TRYTES_SIZE);
-    Ok(trytes)
-  }
-}
+    data.insert("proof".into(), OneOrMany::from(proofs).serde_into()?);
 
-impl Display for Document {
-  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-    Display::fmt(&self.document, f)
+    Ok(())
   }
-}
 
-impl Debug for Document {
-  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-    Debug::fmt(&self.document, f)
-  }
-}
+  /// Returns `data` without a `proof` property, combined with the proof of
+  /// the given `purpose` from its proof set.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `data` has no proof with the given `purpose`.
+  pub fn find_proof(data: &Object, purpose: &str) -> Result<VerifiableProperties> {
+    let proofs: OneOrMany<Signature> = data.get("proof").ok_or(Error::InvalidProofPurpose)?.serde_into()?;
 
-impl Deref for Document {
-  type Target = BaseDocument;
+    let proof: Signature = proofs
+      .iter()
+      .find(|proof| proof.purpose() == Some(purpose))
+      .cloned()
+      .ok_or(Error::InvalidProofPurpose)?;
 
-  fn deref(&self) -> &Self::Target {
-    &self.document
+    let mut unsigned: Object = data.clone();
+
+    unsigned.remove("proof");
+
+    Ok(VerifiableProperties::with_proof(unsigned, proof))
   }
-}
 
-impl From<BaseDocument> for Document {
+  /// Verifies that `data` has a valid proof with the given `purpose`; other
+  /// proofs of `data` are ignored.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `data` has no proof with the given `purpose` or the
+  /// verification operation fails.
+  pub fn verify_data_purpose(&self, data: &Object, purpose: &str) -> Result<()> {
+    let data: VerifiableProperties = Self::find_proof(data, purpose)?;
+
+    self
+      .document
+      .verifier()
+      .purpose(purpose)
+      .verify(&data)
+      .map_err(Into::into)
+  }
+
+  /// Creates a deactivated DID document signed with the default
+  /// authentication method.
+  ///
+  /// The deactivated document only contains the default authentication
+  /// method of `self` and references `self` as the previous message.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the signature operation fails.
+  pub fn sign_deactivation(&self, secret: &SecretKey) -> Result<Self> {
+    // SAFETY: The authentication method of `self` is a valid method.
+    let mut document: Self = unsafe { Self::from_authentication_unchecked(self.authentication().clone()) };
+
+    document.set_deactivated(true);
+    document.set_previous_message_id(self.message_id.clone());
+    document.sign(secret)?;
+
+    Ok(document)
+  }
+
+  /// Verifies that `document` is a deactivation of `self`, signed with the
+  /// default authentication method of `self`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `document` is not deactivated, belongs to a different DID, or
+  /// was not signed with the default authentication method of `self`.
+  pub fn verify_deactivation(&self, document: &Self) -> Result<()> {
+    if !document.deactivated() || document.id() != self.id() {
+      return Err(Error::InvalidDocumentDeactivation);
+    }
+
+    if document.authentication() != self.authentication() {
+      return Err(Error::InvalidDocumentDeactivation);
+    }
+
+    document.verify()
+  }
+
+  // ===========================================================================
+  // Diffs
+  // ===========================================================================
+
+  /// Reconciles two divergent versions of the same DID Document, e.g. edited
+  /// offline on different devices, with a three-way merge against `base` -
+  /// the last version both documents were derived from.
+  ///
+  /// Verification methods, services, and custom properties added, updated, or
+  /// removed in only one of the documents are added, updated, or removed in
+  /// the reconciled document. Entries changed differently in both documents
+  /// are resolved according to `policy` and reported as conflicts.
+  ///
+  /// Note: The reconciled document must be signed before it is published.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `self`, `other`, and `base` have different DIDs.
+  pub fn reconcile(&self, other: &Self, base: &Self, policy: ReconcilePolicy) -> Result<Reconciliation> {
+    if self.id() != other.id() || self.id() != base.id() {
+      return Err(Error::InvalidReconcileDID);
+    }
+
+    let (winner, loser): (&Self, &Self) = match policy {
+      ReconcilePolicy::Latest if self.updated() >= other.updated() => (self, other),
+      ReconcilePolicy::Latest => (other, self),
+      ReconcilePolicy::Local => (self, other),
+      ReconcilePolicy::Remote => (other, self),
+    };
+
+    let mut document: BaseDocument = winner.document.clone();
+    let mut conflicts: Vec<String> = Vec::new();
+
+    // Apply the changes of the losing document unless they conflict with the
+    // changes of the winning document
+    rebase_document(&mut document, &base.document, &loser.document, &mut conflicts);
+
+    document.properties_mut().updated = self.updated().max(other.updated());
+
+    Ok(Reconciliation {
+      document: Self {
+        document,
+        message_id: MessageId::NONE,
+      },
+      conflicts,
+    })
+  }
+
+  /// Re-applies the changes of `diff`, which was created against `self`, onto
+  /// `base` - a newer version of the same DID Document.
+  ///
+  /// Verification methods, services, and custom properties changed by both
+  /// `diff` and `base` are left as in `base` and reported as conflicts. The
+  /// rebased document can be used to create a new diff against `base`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the documents or `diff` have different DIDs, or the `diff`
+  /// signature is invalid.
+  pub fn rebase_diff(&self, diff: &DocumentDiff, base: &Self) -> Result<Reconciliation> {
+    if self.id() != base.id() || self.id() != diff.id() {
+      return Err(Error::InvalidDiffDID);
+    }
+
+    self.verify_data(diff)?;
+
+    let local: Self = diff.merge(self)?;
+
+    let mut document: BaseDocument = base.document.clone();
+    let mut conflicts: Vec<String> = Vec::new();
+
+    rebase_document(&mut document, &self.document, &local.document, &mut conflicts);
+
+    if local.immutable() != self.immutable() {
+      document.properties_mut().immutable = local.immutable();
+    }
+
+    if local.deactivated() != self.deactivated() {
+      document.properties_mut().deactivated = local.deactivated();
+    }
+
+    document.properties_mut().updated = local.updated().max(base.updated());
+
+    Ok(Reconciliation {
+      document: Self {
+        document,
+        message_id: MessageId::NONE,
+      },
+      conflicts,
+    })
+  }
+
+  /// Creates a `DocumentDiff` representing the changes between `self` and `other`.
+  ///
+  /// The returned `DocumentDiff` will have a digital signature created using the
+  /// default authentication method and `secret`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `self` and `other` have different DIDs, `self` or `message` is
+  /// missing a Tangle message id, or the diff or signature operation fails.
+  pub fn diff(&self, other: &Self, message: MessageId, secret: &SecretKey) -> Result<DocumentDiff> {
+    self.diff_with_method(other, message, self.authentication_id(), secret)
+  }
+
+  /// Creates a `DocumentDiff` representing the changes between `self` and `other`.
+  ///
+  /// The returned `DocumentDiff` will have a digital signature created using the
+  /// verification method matching `query` and `secret`; the `verificationMethod`
+  /// of the proof references the method.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `self` and `other` have different DIDs, `self` or `message` is
+  /// missing a Tangle message id, no method matches `query`, `secret` does not
+  /// belong to the method, or the diff or signature operation fails.
+  pub fn diff_with_method<'query, Q>(
+    &self,
+    other: &Self,
+    message: MessageId,
+    query: Q,
+    secret: &SecretKey,
+  ) -> Result<DocumentDiff>
+  where
+    Q: Into<MethodQuery<'query>>,
+  {
+    if self.id() != other.id() {
+      return Err(Error::InvalidDiffDID);
+    }
+
+    // A diff must be chained to a published document
+    if self.message_id.is_none() || message.is_none() {
+      return Err(Error::InvalidDocumentMessageId);
+    }
+
+    let mut diff: DocumentDiff = DocumentDiff::new(self, other, message)?;
+
+    self.document.signer(secret).method(query).sign(&mut diff)?;
+
+    // Resolvers verify the diff against the referenced method; reject a key
+    // that doesn't belong to it instead of publishing an unverifiable diff
+    if self.verify_data(&diff).is_err() {
+      return Err(Error::InvalidDiffKey);
+    }
+
+    Ok(diff)
+  }
+
+  /// Returns the paths of the fields changed by `diff` that have diverged in
+  /// `self` from the document the diff was computed against.
+  ///
+  /// Merging a diff with conflicts overwrites the local changes of the
+  /// conflicting fields.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the diff belongs to a different DID or serialization fails.
+  pub fn merge_conflicts(&self, diff: &DocumentDiff) -> Result<Vec<String>> {
+    if self.id() != diff.id() {
+      return Err(Error::InvalidDiffDID);
+    }
+
+    diff.conflicts(self)
+  }
+
+  /// Verifies a `DocumentDiff` signature and merges the changes into `self`.
+  ///
+  /// If merging fails `self` remains unmodified, otherwise `self` represents
+  /// the merged document state.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the merge operation or signature operation fails.
+  pub fn merge(&mut self, diff: &DocumentDiff) -> Result<()> {
+    self.verify_data(diff)?;
+
+    *self = diff.merge(self)?;
+
+    Ok(())
+  }
+
+  /// Verifies a `DocumentDiff` signature and merges the changes into `self`,
+  /// rejecting diffs that introduce verification methods with duplicate key
+  /// material.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the merge operation or signature operation fails, or the merged
+  /// document contains new duplicate keys.
+  pub fn merge_unique(&mut self, diff: &DocumentDiff) -> Result<()> {
+    self.verify_data(diff)?;
+
+    let merged: Self = diff.merge(self)?;
+    let existing: Vec<(CoreDID, CoreDID)> = self.document.duplicate_keys();
+
+    if let Some((duplicate, _)) = merged
+      .document
+      .duplicate_keys()
+      .into_iter()
+      .find(|duplicate| !existing.contains(duplicate))
+    {
+      return Err(Error::InvalidDocumentDuplicateKey(duplicate.to_string()));
+    }
+
+    *self = merged;
+
+    Ok(())
+  }
+
+  // ===========================================================================
+  // Publishing
+  // ===========================================================================
+
+  /// Publishes the DID Document to the Tangle; returns a [`PublishResult`]
+  /// with the `MessageId`, explorer URL, and DID of the published document.
+  ///
+  /// Uses the provided [`client`][``Client``] or a default `Client` based on
+  /// the DID network.
+  pub async fn publish<'client, C>(&mut self, client: C) -> Result<PublishResult>
+  where
+    C: Into<Option<&'client Client>>,
+  {
+    let receipt: PublishReceipt = self.publish_with_options(client, PublishOptions::new()).await?;
+
+    Ok(PublishResult::new(
+      self.id().into(),
+      self.id().clone(),
+      receipt.message_id().clone(),
+    ))
+  }
+
+  /// Publishes the DID Document to the Tangle with the given `options`;
+  /// returns a [`PublishReceipt`] describing the confirmation state.
+  ///
+  /// Uses the provided [`client`][``Client``] or a default `Client` based on
+  /// the DID network.
+  pub async fn publish_with_options<'client, C>(&mut self, client: C, options: PublishOptions) -> Result<PublishReceipt>
+  where
+    C: Into<Option<&'client Client>>,
+  {
+    let network: Network = self.id().into();
+
+    // Publish the DID Document to the Tangle.
+    let receipt: PublishReceipt = match client.into() {
+      Some(client) if client.network() == network => client.publish_document_with_options(self, options).await?,
+      Some(_) => return Err(Error::InvalidDIDNetwork),
+      None => {
+        Client::from_network(network)?
+          .publish_document_with_options(self, options)
+          .await?
+      }
+    };
+
+    // Update the `self` with the `MessageId` of the bundled transaction.
+    self.set_message_id(receipt.message_id().clone());
+
+    Ok(receipt)
+  }
+
+  /// Returns the Tangle address of the DID diff chain.
+  pub fn diff_address(message_id: &MessageId) -> Result<String> {
+    if message_id.is_none() {
+      return Err(Error::InvalidDocumentMessageId);
+    }
+
+    let hash: String = DID::encode_key(message_id.as_str().as_bytes());
+
+    let mut trytes: String = utf8_to_trytes(&hash);
+    trytes.truncate(iota_constants::HASH_TRYTES_SIZE);
+    Ok(trytes)
+  }
+}
+
+impl Display for Document {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    Display::fmt(&self.document, f)
+  }
+}
+
+impl Debug for Document {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    Debug::fmt(&self.document, f)
+  }
+}
+
+impl Deref for Document {
+  type Target = BaseDocument;
+
+  fn deref(&self) -> &Self::Target {
+    &self.document
+  }
+}
+
+impl From<BaseDocument> for Document {
   fn from(other: BaseDocument) -> Self {
     Self {
       document: other,
@@ -474,3 +1390,619 @@ impl TangleRef for Document {
     Document::set_previous_message_id(self, message_id)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::ToJson;
+
+  use super::*;
+
+  #[test]
+  fn test_check_context() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    // The `@context` property is required
+    assert!(matches!(document.check_context(), Err(Error::InvalidDocumentContext)));
+
+    document
+      .properties_mut()
+      .insert("@context".into(), BaseDocument::base_context().into());
+
+    assert!(document.check_context().is_ok());
+
+    // The base context must be the first entry
+    document.properties_mut().insert(
+      "@context".into(),
+      vec!["https://example.com/contexts/v1", BaseDocument::base_context()].into(),
+    );
+
+    assert!(matches!(document.check_context(), Err(Error::InvalidDocumentContext)));
+
+    document.properties_mut().insert("@context".into(), 123.into());
+
+    assert!(matches!(document.check_context(), Err(Error::InvalidDocumentContext)));
+  }
+
+  #[test]
+  fn test_sign_deactivation() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    let assertion: KeyPair = KeyPair::new_ed25519().unwrap();
+    let method: Method = Method::from_did(document.id().clone(), &assertion, "key-2").unwrap();
+
+    document.insert_method(MethodScope::AssertionMethod, method);
+    document.set_message_id(MessageId::new("A".repeat(81)));
+
+    let deactivated: Document = document.sign_deactivation(keypair.secret()).unwrap();
+
+    // Only the authentication method is kept
+    assert!(deactivated.deactivated());
+    assert_eq!(deactivated.methods().count(), 1);
+    assert_eq!(deactivated.previous_message_id(), document.message_id());
+    assert!(document.verify_deactivation(&deactivated).is_ok());
+
+    // The document itself is not a deactivation
+    assert!(matches!(
+      document.verify_deactivation(&document),
+      Err(Error::InvalidDocumentDeactivation)
+    ));
+
+    // The deactivation of another DID is rejected
+    let other: KeyPair = KeyPair::new_ed25519().unwrap();
+    let foreign: Document = Document::from_keypair(&other)
+      .unwrap()
+      .sign_deactivation(other.secret())
+      .unwrap();
+
+    assert!(matches!(
+      document.verify_deactivation(&foreign),
+      Err(Error::InvalidDocumentDeactivation)
+    ));
+
+    // The deactivation must be signed with the authentication key
+    let forged: Document = document.sign_deactivation(assertion.secret()).unwrap();
+
+    assert!(document.verify_deactivation(&forged).is_err());
+  }
+
+  #[test]
+  fn test_verify_threshold() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    let auth: KeyPair = KeyPair::new_ed25519().unwrap();
+    let method: Method = Method::from_did(document.id().clone(), &auth, "key-2").unwrap();
+
+    document.insert_method(MethodScope::Authentication, method);
+
+    let assertion: KeyPair = KeyPair::new_ed25519().unwrap();
+    let method: Method = Method::from_did(document.id().clone(), &assertion, "key-3").unwrap();
+
+    document.insert_method(MethodScope::AssertionMethod, method);
+
+    let mut unsigned: Object = Object::new();
+
+    unsigned.insert("value".into(), 123.into());
+
+    let sign = |method: &str, secret: &SecretKey| -> Signature {
+      let mut data: VerifiableProperties = VerifiableProperties::new(unsigned.clone());
+
+      document.signer(secret).method(method).sign(&mut data).unwrap();
+      data.proof().cloned().unwrap()
+    };
+
+    let with_proofs = |proofs: Vec<Signature>| -> Object {
+      let mut data: Object = unsigned.clone();
+
+      data.insert("proof".into(), proofs.serde_into().unwrap());
+      data
+    };
+
+    let proof1: Signature = sign(document.authentication_id(), keypair.secret());
+    let proof2: Signature = sign("#key-2", auth.secret());
+    let proof3: Signature = sign("#key-3", assertion.secret());
+
+    let data: Object = with_proofs(vec![proof1.clone(), proof2.clone()]);
+
+    assert!(document.verify_threshold(&data, 1).is_ok());
+    assert!(document.verify_threshold(&data, 2).is_ok());
+    assert!(matches!(
+      document.verify_threshold(&data, 3),
+      Err(Error::InvalidProofThreshold)
+    ));
+
+    // Duplicate proofs from the same method are counted once
+    let data: Object = with_proofs(vec![proof1.clone(), proof1.clone()]);
+
+    assert!(matches!(
+      document.verify_threshold(&data, 2),
+      Err(Error::InvalidProofThreshold)
+    ));
+
+    // Proofs from methods without the authentication relationship are ignored
+    let data: Object = with_proofs(vec![proof1.clone(), proof3]);
+
+    assert!(matches!(
+      document.verify_threshold(&data, 2),
+      Err(Error::InvalidProofThreshold)
+    ));
+
+    // Invalid proofs are ignored
+    let forged: Signature = sign("#key-2", keypair.secret());
+    let data: Object = with_proofs(vec![proof1, forged]);
+
+    assert!(matches!(
+      document.verify_threshold(&data, 2),
+      Err(Error::InvalidProofThreshold)
+    ));
+
+    // A single proof is accepted
+    let mut data: Object = unsigned.clone();
+
+    data.insert("proof".into(), proof2.serde_into().unwrap());
+
+    assert!(document.verify_threshold(&data, 1).is_ok());
+    assert!(matches!(
+      document.verify_threshold(&unsigned, 1),
+      Err(Error::InvalidProofThreshold)
+    ));
+  }
+
+  #[test]
+  fn test_check_properties() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    assert!(document.check_properties().is_ok());
+
+    // `@context` is the only custom property allowed
+    document
+      .properties_mut()
+      .insert("@context".into(), BaseDocument::base_context().into());
+
+    assert!(document.check_properties().is_ok());
+
+    document.properties_mut().insert("foo".into(), 123.into());
+
+    assert!(matches!(
+      document.check_properties(),
+      Err(Error::InvalidDocumentProperty(key)) if key == "foo"
+    ));
+  }
+
+  #[test]
+  fn test_merge_unique() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut current: Document = Document::from_keypair(&keypair).unwrap();
+
+    current.sign(keypair.secret()).unwrap();
+    current.set_message_id(MessageId::new("CURRENT".to_string()));
+
+    let message: MessageId = current.message_id().clone();
+
+    // The new method reuses the key of the authentication method
+    let mut updated: Document = current.clone();
+    let method: Method = Method::from_did(current.id().clone(), &keypair, "key-2").unwrap();
+
+    updated.insert_method(MethodScope::VerificationMethod, method);
+
+    let diff: DocumentDiff = current.diff(&updated, message.clone(), keypair.secret()).unwrap();
+    let mut merged: Document = current.clone();
+
+    assert!(matches!(
+      merged.merge_unique(&diff),
+      Err(Error::InvalidDocumentDuplicateKey(_))
+    ));
+    assert!(merged.as_document().resolve("#key-2").is_none());
+
+    merged.merge(&diff).unwrap();
+
+    assert!(merged.as_document().resolve("#key-2").is_some());
+
+    // The new method has a key of its own
+    let other: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut updated: Document = current.clone();
+    let method: Method = Method::from_did(current.id().clone(), &other, "key-3").unwrap();
+
+    updated.insert_method(MethodScope::VerificationMethod, method);
+
+    let diff: DocumentDiff = current.diff(&updated, message, keypair.secret()).unwrap();
+    let mut merged: Document = current.clone();
+
+    merged.merge_unique(&diff).unwrap();
+
+    assert!(merged.as_document().resolve("#key-3").is_some());
+  }
+
+  #[test]
+  fn test_verify_data_pinned() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut pinned: Document = Document::from_keypair(&keypair).unwrap();
+
+    let assertion: KeyPair = KeyPair::new_ed25519().unwrap();
+    let method: Method = Method::from_did(pinned.id().clone(), &assertion, "key-2").unwrap();
+
+    pinned.insert_method(MethodScope::AssertionMethod, method);
+
+    // Rotate the key of `#key-2`
+    let mut current: Document = pinned.clone();
+    let rotated: KeyPair = KeyPair::new_ed25519().unwrap();
+    let method: Method = Method::from_did(current.id().clone(), &rotated, "key-2").unwrap();
+
+    let id: DID = current.id().join("#key-2").unwrap();
+
+    current.remove_method(&id).unwrap();
+    current.insert_method(MethodScope::AssertionMethod, method);
+
+    let sign = |method: &str, secret: &SecretKey| -> VerifiableProperties {
+      let mut data: Object = Object::new();
+
+      data.insert("value".into(), 123.into());
+
+      let mut data: VerifiableProperties = VerifiableProperties::new(data);
+
+      current.signer(secret).method(method).sign(&mut data).unwrap();
+      data
+    };
+
+    // The key of the authentication method is unchanged
+    let data: VerifiableProperties = sign(current.authentication_id(), keypair.secret());
+
+    assert!(current.verify_data_pinned(&data, &pinned).is_ok());
+
+    // The key of `#key-2` differs from the pinned document
+    let data: VerifiableProperties = sign("#key-2", rotated.secret());
+
+    assert!(current.verify_data(&data).is_ok());
+    assert!(matches!(
+      current.verify_data_pinned(&data, &pinned),
+      Err(Error::InvalidPinnedKey(method)) if method.ends_with("#key-2")
+    ));
+
+    // The pinned document belongs to another DID
+    let other: Document = Document::from_keypair(&KeyPair::new_ed25519().unwrap()).unwrap();
+
+    assert!(matches!(
+      current.verify_data_pinned(&data, &other),
+      Err(Error::InvalidPinnedDID)
+    ));
+  }
+
+  #[test]
+  fn test_remove_service() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+    let origin: Url = Url::parse("https://example.com").unwrap();
+
+    document.insert_linked_domains("domain-1", origin.clone()).unwrap();
+    document.insert_linked_domains("domain-2", origin).unwrap();
+
+    // By fragment
+    document.remove_service("#domain-1").unwrap();
+
+    assert_eq!(document.service().len(), 1);
+
+    // By absolute DID URL
+    let id: String = document.id().join("#domain-2").unwrap().to_string();
+
+    document.remove_service(&id).unwrap();
+
+    assert!(document.service().is_empty());
+
+    assert!(matches!(
+      document.remove_service("#domain-1"),
+      Err(Error::MissingService(query)) if query == "#domain-1"
+    ));
+    assert!(matches!(
+      document.remove_service(&id),
+      Err(Error::MissingService(query)) if query == id
+    ));
+  }
+
+  #[test]
+  fn test_from_verification_method() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let method: Method = Method::from_keypair(&keypair, "sign-0").unwrap();
+    let mut document: Document = Document::from_verification_method(method).unwrap();
+
+    assert!(matches!(
+      document.as_document().authentication().head().map(|method| &**method),
+      Some(MethodRef::Refer(_))
+    ));
+    assert!(document.authentication().id().as_str().ends_with("#sign-0"));
+    assert_eq!(document.as_document().verification_method().len(), 1);
+
+    document.sign(keypair.secret()).unwrap();
+
+    assert!(document.verify().is_ok());
+  }
+
+  #[test]
+  fn test_to_core_document() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    document.set_previous_message_id(MessageId::new("PREVIOUS".to_string()));
+    document.sign(keypair.secret()).unwrap();
+
+    let core: CoreDocument = document.to_core_document().unwrap();
+
+    assert_eq!(core.id(), document.as_document().id());
+    assert!(!core.properties().contains_key("proof"));
+
+    for key in Document::METADATA {
+      assert!(!core.properties().contains_key(*key));
+    }
+
+    // The proof covers the removed properties and would fail verification
+    let mut exported: CoreDocument = core;
+
+    exported
+      .properties_mut()
+      .insert("proof".into(), document.proof().unwrap().serde_into().unwrap());
+
+    let exported: CoreDocument<VerifiableProperties<Object>, Object, Object> = exported.serde_into().unwrap();
+
+    assert!(document.verify().is_ok());
+    assert!(exported.verify_this().is_err());
+  }
+
+  #[test]
+  fn test_method_for_proof() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    assert!(document.method_for_proof().is_err());
+
+    document.sign(keypair.secret()).unwrap();
+
+    let method: &CoreMethod = document.method_for_proof().unwrap();
+
+    assert_eq!(method.id().as_str(), document.authentication_id());
+    assert_eq!(method.key_type(), MethodType::Ed25519VerificationKey2018);
+  }
+
+  #[test]
+  fn test_proof_purposes() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let document: Document = Document::from_keypair(&keypair).unwrap();
+
+    let mut data: Object = Object::new();
+
+    data.insert("value".into(), 123.into());
+
+    for purpose in &["authentication", "assertionMethod", "authentication"] {
+      let mut unsigned: Object = data.clone();
+
+      unsigned.remove("proof");
+
+      let mut signed: VerifiableProperties = VerifiableProperties::new(unsigned);
+
+      document
+        .signer(keypair.secret())
+        .method(document.authentication_id())
+        .purpose(*purpose)
+        .sign(&mut signed)
+        .unwrap();
+
+      Document::insert_proof(&mut data, signed.proof().cloned().unwrap()).unwrap();
+    }
+
+    let proofs: OneOrMany<Signature> = data["proof"].serde_into().unwrap();
+
+    assert_eq!(proofs.len(), 2);
+    assert!(document.verify_data_purpose(&data, "authentication").is_ok());
+    assert!(document.verify_data_purpose(&data, "assertionMethod").is_ok());
+    assert!(matches!(
+      document.verify_data_purpose(&data, "capabilityInvocation"),
+      Err(Error::InvalidProofPurpose)
+    ));
+  }
+
+  #[test]
+  fn test_diff_with_method() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut current: Document = Document::from_keypair(&keypair).unwrap();
+
+    let assertion: KeyPair = KeyPair::new_ed25519().unwrap();
+    let method: Method = Method::from_did(current.id().clone(), &assertion, "key-2").unwrap();
+
+    current.insert_method(MethodScope::AssertionMethod, method);
+    current.sign(keypair.secret()).unwrap();
+    current.set_message_id(MessageId::new("CURRENT".to_string()));
+
+    let mut updated: Document = current.clone();
+
+    updated.properties_mut().insert("foo".into(), 123.into());
+
+    let message: MessageId = current.message_id().clone();
+    let diff: DocumentDiff = current
+      .diff_with_method(&updated, message.clone(), "#key-2", assertion.secret())
+      .unwrap();
+
+    assert!(diff.proof().unwrap().verification_method().ends_with("#key-2"));
+    assert!(current.verify_data(&diff).is_ok());
+
+    // The key does not belong to the method
+    assert!(matches!(
+      current.diff_with_method(&updated, message, "#key-2", keypair.secret()),
+      Err(Error::InvalidDiffKey)
+    ));
+  }
+
+  #[test]
+  fn test_attach_signature() {
+    use core::convert::TryInto;
+    use crypto::signatures::ed25519;
+
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+    let method: String = document.authentication_id().to_string();
+
+    let payload: Vec<u8> = document.signing_payload(method.as_str()).unwrap();
+
+    assert!(document.verify().is_err());
+
+    // Sign the payload bytes as-is, like an external signer would
+    let sign = |secret: &SecretKey| -> Vec<u8> {
+      let secret: ed25519::SecretKey = ed25519::SecretKey::from_le_bytes(secret.as_ref().try_into().unwrap()).unwrap();
+
+      secret.sign(&payload).to_bytes().to_vec()
+    };
+
+    let other: KeyPair = KeyPair::new_ed25519().unwrap();
+
+    assert!(matches!(
+      document.attach_signature(method.as_str(), &sign(other.secret())),
+      Err(Error::InvalidProofKey)
+    ));
+    assert!(matches!(
+      document.attach_signature(method.as_str(), &[0; 32]),
+      Err(Error::CoreError(identity_core::Error::InvalidSigLength(32, 64)))
+    ));
+
+    document
+      .attach_signature(method.as_str(), &sign(keypair.secret()))
+      .unwrap();
+
+    assert!(document.verify().is_ok());
+  }
+
+  #[test]
+  fn test_check_terms() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    assert!(document.contexts().unwrap().is_empty());
+    assert!(matches!(document.check_terms(), Err(Error::InvalidDocumentContext)));
+
+    let extension: Url = Url::parse("https://example.com/contexts/v1").unwrap();
+
+    assert!(document.add_context(extension.clone()).unwrap());
+    assert!(!document.add_context(extension).unwrap());
+    assert_eq!(document.contexts().unwrap().len(), 2);
+    assert!(document.check_context().is_ok());
+    assert!(document.check_terms().is_ok());
+
+    // Contexts not known to the library don't cover unknown terms
+    document.properties_mut().insert("foo".into(), 123.into());
+
+    assert!(matches!(
+      document.check_terms(),
+      Err(Error::InvalidDocumentTerm(term)) if term == "foo"
+    ));
+
+    document.properties_mut().remove("foo");
+
+    // Terms known to the library require their defining context
+    document.sign(keypair.secret()).unwrap();
+
+    assert!(matches!(
+      document.check_terms(),
+      Err(Error::InvalidDocumentTerm(term)) if term == "JcsEd25519Signature2020"
+    ));
+
+    let mut inline: Object = Object::new();
+
+    inline.insert("bar".into(), "https://example.com/vocab#bar".into());
+
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+
+    document.properties_mut().insert("bar".into(), true.into());
+    document.add_context(inline).unwrap();
+
+    assert!(document.check_terms().is_ok());
+  }
+
+  #[test]
+  fn test_check_terms_service_properties() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+    let endpoint: Url = Url::parse("https://example.com/didcomm").unwrap();
+    let routing_key: String = format!("{}#key-agreement", document.id());
+
+    document
+      .insert_didcomm_messaging("didcomm", endpoint.clone(), vec![routing_key], Vec::new())
+      .unwrap();
+
+    document
+      .add_context(Url::parse(DIDCOMM_MESSAGING_CONTEXT).unwrap())
+      .unwrap();
+
+    assert!(document.check_terms().is_ok());
+
+    let mut properties: Object = Object::new();
+
+    properties.insert("priority".into(), 1.into());
+
+    let service: Service = Service::builder(properties)
+      .id(document.id().join("#custom").unwrap().into())
+      .type_("DIDCommMessaging")
+      .service_endpoint(endpoint)
+      .build()
+      .unwrap();
+
+    assert!(document.insert_service(service));
+
+    assert!(matches!(
+      document.check_terms(),
+      Err(Error::InvalidDocumentTerm(term)) if term == "priority"
+    ));
+  }
+
+  #[test]
+  fn test_insert_linked_domains() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+    let origin: Url = Url::parse("https://example.com").unwrap();
+
+    assert!(document.insert_linked_domains("linked-domain", origin.clone()).unwrap());
+    assert!(!document.insert_linked_domains("linked-domain", origin.clone()).unwrap());
+
+    let service: &Service<Object> = document.service().head().unwrap();
+
+    assert_eq!(service.id().fragment(), Some("linked-domain"));
+    assert_eq!(service.type_(), "LinkedDomains");
+    assert_eq!(service.service_endpoint(), &origin);
+  }
+
+  #[test]
+  fn test_insert_didcomm_messaging() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document::from_keypair(&keypair).unwrap();
+    let endpoint: Url = Url::parse("https://example.com/didcomm").unwrap();
+    let routing_key: String = format!("{}#key-agreement", document.id());
+
+    assert!(matches!(
+      document.insert_didcomm_messaging("didcomm", endpoint.clone(), Vec::new(), Vec::new()),
+      Err(Error::InvalidServiceRoutingKeys)
+    ));
+    assert!(document.service().is_empty());
+
+    document
+      .insert_didcomm_messaging(
+        "didcomm",
+        endpoint.clone(),
+        vec![routing_key.clone()],
+        vec!["didcomm/v2".into()],
+      )
+      .unwrap();
+
+    document.sign(keypair.secret()).unwrap();
+
+    // Custom service properties survive a round trip and stay covered by the proof
+    let document: Document = Document::from_json(&document.to_json().unwrap()).unwrap();
+    let service: &Service<Object> = document.service().head().unwrap();
+
+    assert_eq!(service.type_(), "DIDCommMessaging");
+    assert_eq!(service.service_endpoint(), &endpoint);
+    assert_eq!(
+      service.properties()["routingKeys"],
+      Value::Array(vec![routing_key.into()])
+    );
+    assert_eq!(service.properties()["accept"], Value::Array(vec!["didcomm/v2".into()]));
+    assert!(document.verify().is_ok());
+  }
+}
@@ -9,12 +9,16 @@ use core::fmt::Formatter;
 use core::fmt::Result as FmtResult;
 use core::ops::Deref;
 use identity_core::common::BitSet;
+use identity_core::common::Object;
+use identity_core::common::Value;
 use identity_core::convert::ToJson;
 use identity_core::crypto::merkle_key::MerkleDigest;
 use identity_core::crypto::merkle_tree::Hash;
 use identity_core::crypto::KeyCollection;
 use identity_core::crypto::KeyPair;
 use identity_core::crypto::KeyType;
+use identity_core::utils::decode_b64;
+use identity_core::utils::encode_b64;
 use identity_did::error::Result as DIDResult;
 use identity_did::verifiable::Revocation;
 use identity_did::verification::Method as CoreMethod;
@@ -89,6 +93,46 @@ impl Method {
     Ok(Self(builder.build()?))
   }
 
+  /// Creates a new [`Method`] object from the given `did` and JSON Web Key.
+  ///
+  /// Only `OKP` keys with the `Ed25519` or `X25519` curves are supported.
+  pub fn from_jwk<'a, F>(did: DID, jwk: &Object, fragment: F) -> Result<Self>
+  where
+    F: Into<Option<&'a str>>,
+  {
+    let kty: Option<&str> = jwk.get("kty").and_then(Value::as_str);
+    let crv: Option<&str> = jwk.get("crv").and_then(Value::as_str);
+
+    let key_type: MethodType = match (kty, crv) {
+      (Some("OKP"), Some("Ed25519")) => MethodType::Ed25519VerificationKey2018,
+      (Some("OKP"), Some("X25519")) => MethodType::X25519KeyAgreementKey2019,
+      (_, _) => return Err(Error::InvalidMethodJwk),
+    };
+
+    let public: Vec<u8> = jwk
+      .get("x")
+      .and_then(Value::as_str)
+      .ok_or(Error::InvalidMethodJwk)
+      .and_then(|x| decode_b64(x).map_err(|_| Error::InvalidMethodJwk))?;
+
+    // Both Ed25519 and X25519 public keys are 32 bytes
+    if public.len() != 32 {
+      return Err(Error::InvalidMethodJwk);
+    }
+
+    let tag: String = format!("#{}", fragment.into().unwrap_or(Self::TAG));
+    let key: DID = did.join(tag)?;
+
+    MethodBuilder::default()
+      .id(key.into())
+      .controller(did.into())
+      .key_type(key_type)
+      .key_data(MethodData::new_b58(&public))
+      .build()
+      .map_err(Into::into)
+      .map(Self)
+  }
+
   /// Converts a generic Verification Method to an IOTA Verification Method.
   ///
   /// # Errors
@@ -163,6 +207,30 @@ impl Method {
     unsafe { DID::new_unchecked_ref(self.0.controller()) }
   }
 
+  /// Returns the public key of the method as a JSON Web Key.
+  ///
+  /// Only `Ed25519` and `X25519` keys can be represented as JWKs.
+  pub fn to_jwk(&self) -> Result<Object> {
+    let crv: &str = match self.key_type() {
+      MethodType::Ed25519VerificationKey2018 | MethodType::Ed25519VerificationKey2020 => "Ed25519",
+      MethodType::X25519KeyAgreementKey2019 => "X25519",
+      _ => return Err(Error::InvalidMethodJwk),
+    };
+
+    let public: Vec<u8> = self.key_data().try_decode()?;
+
+    // JWK members are base64url-encoded without padding
+    let x: String = encode_b64(&public).trim_end_matches('=').to_string();
+
+    let mut jwk: Object = Object::new();
+
+    jwk.insert("kty".into(), "OKP".into());
+    jwk.insert("crv".into(), crv.into());
+    jwk.insert("x".into(), x.into());
+
+    Ok(jwk)
+  }
+
   /// Revokes the public key of a Merkle Key Collection at the specified `index`.
   pub fn revoke_merkle_key(&mut self, index: usize) -> Result<bool> {
     if !matches!(self.key_type(), MethodType::MerkleKeyCollection2021) {
@@ -227,3 +295,34 @@ impl Revocation for Method {
     self.0.properties().revocation()
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_jwk_roundtrip() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let method: Method = Method::from_keypair(&keypair, None).unwrap();
+
+    let jwk: Object = method.to_jwk().unwrap();
+
+    assert_eq!(jwk["kty"], "OKP");
+    assert_eq!(jwk["crv"], "Ed25519");
+
+    let from: Method = Method::from_jwk(method.controller().clone(), &jwk, None).unwrap();
+
+    assert_eq!(from, method);
+  }
+
+  #[test]
+  fn test_jwk_unsupported() {
+    let did: DID = DID::new(b"").unwrap();
+    let mut jwk: Object = Object::new();
+
+    jwk.insert("kty".into(), "EC".into());
+    jwk.insert("crv".into(), "P-256".into());
+
+    assert!(Method::from_jwk(did, &jwk, None).is_err());
+  }
+}
@@ -5,6 +5,7 @@ mod diff;
 mod document;
 mod method;
 mod properties;
+mod reconcile;
 
 pub use self::diff::DocumentDiff;
 pub use self::document::Document;
@@ -12,3 +13,5 @@ pub use self::document::Signer;
 pub use self::document::Verifier;
 pub use self::method::Method;
 pub use self::properties::Properties;
+pub use self::reconcile::ReconcilePolicy;
+pub use self::reconcile::Reconciliation;
@@ -12,6 +12,8 @@ pub struct Properties {
   pub(crate) created: Timestamp,
   pub(crate) updated: Timestamp,
   pub(crate) immutable: bool,
+  #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+  pub(crate) deactivated: bool,
   #[serde(default, skip_serializing_if = "MessageId::is_none")]
   pub(crate) previous_message_id: MessageId,
   #[serde(flatten)]
@@ -24,6 +26,7 @@ impl Properties {
       created: Timestamp::now(),
       updated: Timestamp::now(),
       immutable: false,
+      deactivated: false,
       previous_message_id: MessageId::NONE,
       properties: Object::new(),
     }
@@ -0,0 +1,316 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Value;
+use identity_did::did::DID as CoreDID;
+use identity_did::utils::DIDKey;
+use identity_did::utils::OrderedSet;
+
+use crate::did::doc::document::BaseDocument;
+use crate::did::Document;
+
+/// The policy used to resolve conflicts when reconciling two DID Documents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReconcilePolicy {
+  /// The most recently updated document wins conflicts.
+  Latest,
+  /// The local document (`self`) wins conflicts.
+  Local,
+  /// The remote document (`other`) wins conflicts.
+  Remote,
+}
+
+impl Default for ReconcilePolicy {
+  fn default() -> Self {
+    Self::Latest
+  }
+}
+
+/// The result of reconciling two divergent DID Documents.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reconciliation {
+  pub(crate) document: Document,
+  pub(crate) conflicts: Vec<String>,
+}
+
+impl Reconciliation {
+  /// Returns a reference to the reconciled DID Document.
+  pub fn document(&self) -> &Document {
+    &self.document
+  }
+
+  /// Returns the ids of the methods, services, and properties that differed
+  /// between both documents and were resolved by the policy.
+  pub fn conflicts(&self) -> &[String] {
+    &self.conflicts
+  }
+
+  /// Consumes the `Reconciliation` and returns the reconciled DID Document.
+  pub fn into_document(self) -> Document {
+    self.document
+  }
+}
+
+/// Applies the changes made to `base` in `local` onto `target`: verification
+/// methods, services, and custom properties added, updated, or removed in
+/// `local` are added, updated, or removed in `target`.
+///
+/// Entries changed in both `local` and `target` are recorded as conflicts and
+/// left unchanged.
+pub(crate) fn rebase_document(
+  target: &mut BaseDocument,
+  base: &BaseDocument,
+  local: &BaseDocument,
+  conflicts: &mut Vec<String>,
+) {
+  rebase_set(
+    target.verification_method_mut(),
+    base.verification_method(),
+    local.verification_method(),
+    conflicts,
+  );
+  rebase_set(
+    target.authentication_mut(),
+    base.authentication(),
+    local.authentication(),
+    conflicts,
+  );
+  rebase_set(
+    target.assertion_method_mut(),
+    base.assertion_method(),
+    local.assertion_method(),
+    conflicts,
+  );
+  rebase_set(
+    target.key_agreement_mut(),
+    base.key_agreement(),
+    local.key_agreement(),
+    conflicts,
+  );
+  rebase_set(
+    target.capability_delegation_mut(),
+    base.capability_delegation(),
+    local.capability_delegation(),
+    conflicts,
+  );
+  rebase_set(
+    target.capability_invocation_mut(),
+    base.capability_invocation(),
+    local.capability_invocation(),
+    conflicts,
+  );
+  rebase_set(target.service_mut(), base.service(), local.service(), conflicts);
+
+  let before: _ = &base.properties().properties;
+  let after: _ = &local.properties().properties;
+
+  for key in before.keys().chain(after.keys()) {
+    let before: Option<&Value> = before.get(key);
+    let after: Option<&Value> = after.get(key);
+    let current: Option<&Value> = target.properties().properties.get(key);
+
+    if before == after || current == after {
+      continue;
+    }
+
+    if current != before {
+      push_conflict(conflicts, key);
+      continue;
+    }
+
+    match after {
+      Some(value) => {
+        target.properties_mut().properties.insert(key.clone(), value.clone());
+      }
+      None => {
+        target.properties_mut().properties.remove(key);
+      }
+    }
+  }
+}
+
+/// Applies the changes made to `base` in `local` onto `target`; items changed
+/// in both `local` and `target` are recorded as conflicts and left unchanged.
+fn rebase_set<T>(
+  target: &mut OrderedSet<DIDKey<T>>,
+  base: &OrderedSet<DIDKey<T>>,
+  local: &OrderedSet<DIDKey<T>>,
+  conflicts: &mut Vec<String>,
+) where
+  T: Clone + PartialEq + AsRef<CoreDID>,
+{
+  fn find<'a, T>(set: &'a OrderedSet<DIDKey<T>>, did: &CoreDID) -> Option<&'a T>
+  where
+    T: AsRef<CoreDID>,
+  {
+    set.iter().find(|item| item.as_did() == did).map(|item| &**item)
+  }
+
+  for item in base.iter().chain(local.iter()) {
+    let before: Option<&T> = find(base, item.as_did());
+    let after: Option<&T> = find(local, item.as_did());
+    let current: Option<&T> = find(target, item.as_did());
+
+    if before == after || current == after {
+      continue;
+    }
+
+    if current != before {
+      push_conflict(conflicts, item.as_did().as_str());
+      continue;
+    }
+
+    match after {
+      Some(after) if current.is_some() => {
+        target.update(DIDKey::new(after.clone()));
+      }
+      Some(after) => {
+        target.append(DIDKey::new(after.clone()));
+      }
+      None => {
+        target.remove(item);
+      }
+    }
+  }
+}
+
+fn push_conflict(conflicts: &mut Vec<String>, id: &str) {
+  if !conflicts.iter().any(|conflict| conflict == id) {
+    conflicts.push(id.to_string());
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use identity_core::common::Value;
+  use identity_core::crypto::KeyPair;
+  use identity_did::verification::MethodScope;
+
+  use crate::did::DocumentDiff;
+  use crate::did::Method;
+  use crate::tangle::MessageId;
+  use crate::tangle::TangleRef;
+
+  fn method(document: &Document, fragment: &str) -> Method {
+    Method::from_did(document.id().clone(), &KeyPair::new_ed25519().unwrap(), fragment).unwrap()
+  }
+
+  #[test]
+  fn test_reconcile_union() {
+    let base: Document = Document::from_keypair(&KeyPair::new_ed25519().unwrap()).unwrap();
+    let mut local: Document = base.clone();
+    let mut remote: Document = base.clone();
+
+    local.insert_method(MethodScope::VerificationMethod, method(&local, "key-1"));
+    remote.insert_method(MethodScope::VerificationMethod, method(&remote, "key-2"));
+
+    let output: Reconciliation = local.reconcile(&remote, &base, ReconcilePolicy::Latest).unwrap();
+
+    assert!(output.conflicts().is_empty());
+    assert!(output.document().resolve("#key-1").is_some());
+    assert!(output.document().resolve("#key-2").is_some());
+  }
+
+  #[test]
+  fn test_reconcile_removal() {
+    let mut base: Document = Document::from_keypair(&KeyPair::new_ed25519().unwrap()).unwrap();
+    let key1: Method = method(&base, "key-1");
+    let key2: Method = method(&base, "key-2");
+
+    base.insert_method(MethodScope::VerificationMethod, key1.clone());
+    base.insert_method(MethodScope::VerificationMethod, key2.clone());
+    base.properties_mut().insert("foo".into(), Value::from(1));
+
+    let mut local: Document = base.clone();
+    let mut remote: Document = base.clone();
+
+    // Removed on one device only
+    local.remove_method(key1.id()).unwrap();
+    remote.properties_mut().remove("foo");
+
+    for policy in [ReconcilePolicy::Local, ReconcilePolicy::Remote].iter() {
+      let output: Reconciliation = local.reconcile(&remote, &base, *policy).unwrap();
+
+      assert!(output.conflicts().is_empty());
+      assert!(output.document().resolve("#key-1").is_none());
+      assert!(output.document().resolve("#key-2").is_some());
+      assert!(output.document().properties().get("foo").is_none());
+    }
+
+    // Removed on one device and replaced on the other
+    remote.remove_method(key2.id()).unwrap();
+    remote.insert_method(MethodScope::VerificationMethod, method(&base, "key-2"));
+    local.remove_method(key2.id()).unwrap();
+
+    let output: Reconciliation = local.reconcile(&remote, &base, ReconcilePolicy::Local).unwrap();
+
+    assert_eq!(output.conflicts(), [key2.id().as_str()]);
+    assert!(output.document().resolve("#key-2").is_none());
+
+    let output: Reconciliation = local.reconcile(&remote, &base, ReconcilePolicy::Remote).unwrap();
+
+    assert_eq!(output.conflicts(), [key2.id().as_str()]);
+    assert!(output.document().resolve("#key-2").is_some());
+  }
+
+  #[test]
+  fn test_reconcile_conflict() {
+    let base: Document = Document::from_keypair(&KeyPair::new_ed25519().unwrap()).unwrap();
+    let mut local: Document = base.clone();
+    let mut remote: Document = base.clone();
+
+    let local_method: Method = method(&local, "key-1");
+    let remote_method: Method = method(&remote, "key-1");
+
+    local.insert_method(MethodScope::VerificationMethod, local_method.clone());
+    remote.insert_method(MethodScope::VerificationMethod, remote_method.clone());
+
+    let output: Reconciliation = local.reconcile(&remote, &base, ReconcilePolicy::Local).unwrap();
+
+    assert_eq!(output.conflicts(), [local_method.id().as_str()]);
+    assert_eq!(output.document().resolve("#key-1").unwrap(), &*local_method);
+
+    let output: Reconciliation = local.reconcile(&remote, &base, ReconcilePolicy::Remote).unwrap();
+
+    assert_eq!(output.conflicts(), [remote_method.id().as_str()]);
+    assert_eq!(output.document().resolve("#key-1").unwrap(), &*remote_method);
+  }
+
+  #[test]
+  fn test_rebase_diff() {
+    let key: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut original: Document = Document::from_keypair(&key).unwrap();
+
+    original.set_message_id(MessageId::new("ORIGINAL".to_string()));
+
+    let mut base: Document = original.clone();
+    let mut local: Document = original.clone();
+
+    base.insert_method(MethodScope::VerificationMethod, method(&base, "key-1"));
+    base.properties_mut().insert("foo".into(), Value::from(1));
+
+    local.insert_method(MethodScope::VerificationMethod, method(&local, "key-2"));
+    local.properties_mut().insert("bar".into(), Value::from(2));
+
+    let diff: DocumentDiff = original
+      .diff(&local, original.message_id().clone(), key.secret())
+      .unwrap();
+    let output: Reconciliation = original.rebase_diff(&diff, &base).unwrap();
+
+    assert!(output.conflicts().is_empty());
+    assert!(output.document().resolve("#key-1").is_some());
+    assert!(output.document().resolve("#key-2").is_some());
+    assert_eq!(output.document().properties().get("foo"), Some(&Value::from(1)));
+    assert_eq!(output.document().properties().get("bar"), Some(&Value::from(2)));
+
+    // Both versions changed the same property
+    base.properties_mut().insert("bar".into(), Value::from(3));
+
+    let output: Reconciliation = original.rebase_diff(&diff, &base).unwrap();
+
+    assert_eq!(output.conflicts(), ["bar"]);
+    assert_eq!(output.document().properties().get("bar"), Some(&Value::from(3)));
+  }
+}
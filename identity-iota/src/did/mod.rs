@@ -11,6 +11,8 @@ pub use self::doc::Document;
 pub use self::doc::DocumentDiff;
 pub use self::doc::Method;
 pub use self::doc::Properties;
+pub use self::doc::ReconcilePolicy;
+pub use self::doc::Reconciliation;
 pub use self::doc::Signer;
 pub use self::doc::Verifier;
 pub use self::url::Segments;
@@ -18,6 +18,8 @@ pub enum Error {
   #[error("{0}")]
   ClientError(#[from] iota::client::error::Error),
   #[error("{0}")]
+  HttpError(#[from] reqwest::Error),
+  #[error("{0}")]
   TernaryError(#[from] iota::ternary::Error),
   #[error("Invalid Document - Missing Message Id")]
   InvalidDocumentMessageId,
@@ -27,8 +29,42 @@ pub enum Error {
   InvalidDocumentAuthFragment,
   #[error("Invalid Document - Authentication Type Not Supported")]
   InvalidDocumentAuthType,
+  #[error("Invalid Document - Invalid Deactivation")]
+  InvalidDocumentDeactivation,
+  #[error("Invalid Document - Missing Base Context")]
+  InvalidDocumentContext,
+  #[error("Invalid Document - Unknown Property `{0}`")]
+  InvalidDocumentProperty(String),
+  #[error("Invalid Document - Duplicate Key Material `{0}`")]
+  InvalidDocumentDuplicateKey(String),
+  #[error("Invalid Document - Term Not Covered By Context `{0}`")]
+  InvalidDocumentTerm(String),
+  #[error("Invalid Document Diff - DID Mismatch")]
+  InvalidDiffDID,
+  #[error("Invalid Document Diff - Key Does Not Match Method")]
+  InvalidDiffKey,
+  #[error("Invalid Document Diff - Invalid Compact Encoding")]
+  InvalidDiffEncoding,
+  #[error("Invalid Document Diff - Compact Encoding Too Long")]
+  InvalidDiffLength,
+  #[error("Invalid Document Reconciliation - DID Mismatch")]
+  InvalidReconcileDID,
+  #[error("Invalid Pinned Document - DID Mismatch")]
+  InvalidPinnedDID,
+  #[error("Invalid Pinned Document - Key Material Changed `{0}`")]
+  InvalidPinnedKey(String),
+  #[error("Invalid Proof - Threshold Not Met")]
+  InvalidProofThreshold,
+  #[error("Invalid Proof - Purpose Not Found")]
+  InvalidProofPurpose,
+  #[error("Invalid Proof - Method Does Not Match Signing Payload")]
+  InvalidProofMethod,
+  #[error("Invalid Proof - Signature Does Not Match Method")]
+  InvalidProofKey,
   #[error("Invalid DID Network")]
   InvalidDIDNetwork,
+  #[error("Network Mismatch - Client: {expected}, DID: {found}")]
+  NetworkMismatch { expected: &'static str, found: String },
   #[error("Invalid Tryte Conversion")]
   InvalidTryteConversion,
   #[error("Invalid Transaction Bundle")]
@@ -41,8 +77,14 @@ pub enum Error {
   InvalidBundleTail,
   #[error("Invalid PResentation Holder")]
   InvalidPresentationHolder,
+  #[error("Invalid Presentation - Holder Signature Invalid")]
+  InvalidPresentationProof,
+  #[error("Invalid Presentation - Credential {0} Invalid")]
+  InvalidPresentationCredential(usize),
   #[error("Chain Error: {error}")]
   ChainError { error: &'static str },
+  #[error("Invalid Verification Method - Unsupported JWK")]
+  InvalidMethodJwk,
   #[error("Missing Verification Method Fragment")]
   MissingMethodFragment,
   #[error("Authentication Method Not Found")]
@@ -51,4 +93,30 @@ pub enum Error {
   CannotRemoveAuthMethod,
   #[error("Cannot Revoke Verification Method")]
   CannotRevokeMethod,
+  #[error("Invalid Service - Missing Routing Keys")]
+  InvalidServiceRoutingKeys,
+  #[error("Service Not Found `{0}`")]
+  MissingService(String),
+  #[error("Resource Not Found `{0}`")]
+  MissingResource(String),
+  #[error("Invalid Resource URL - HTTPS Required `{0}`")]
+  InvalidResourceUrl(String),
+  #[error("Resource Fetching Not Supported")]
+  UnsupportedFetch,
+  #[error("Invalid Domain Linkage")]
+  InvalidDomainLinkage,
+  #[error("Mutex Poisoned")]
+  MutexPoisoned,
+  #[error("Invalid Controller - Cycle Detected `{0}`")]
+  ControllerCycle(String),
+  #[error("Invalid Controller - Maximum Depth Exceeded")]
+  ControllerDepth,
+  #[error("Response Too Large - {size} bytes exceeds the limit of {limit} bytes")]
+  ResponseTooLarge { size: usize, limit: usize },
+  #[error("Node Unavailable")]
+  NodeUnavailable,
+  #[error("MQTT Broker Not Configured")]
+  MissingMqttBroker,
+  #[error("Subscription Failed - {0}")]
+  SubscriptionError(std::io::Error),
 }
@@ -20,6 +20,7 @@ use crate::error::Result;
 use crate::tangle::MessageId;
 use crate::tangle::TangleRef;
 use crate::utils::encode_trits;
+use crate::utils::trytes_to_bytes;
 use crate::utils::trytes_to_utf8;
 use crate::utils::txn_hash;
 
@@ -77,6 +78,17 @@ impl Message {
     trytes_to_utf8(&self.message_str())
   }
 
+  /// Returns the raw contents of the message.
+  pub fn message_bytes(&self) -> Result<Vec<u8>> {
+    trytes_to_bytes(&self.message_str())
+  }
+
+  /// Returns the size of the raw contents of the message in bytes.
+  pub fn size(&self) -> usize {
+    // Each byte is encoded as two trytes of three trits
+    self.message.len() / 6
+  }
+
   /// Returns the `MessageId` identifying the Tangle message.
   pub fn message_id(&self) -> MessageId {
     MessageId::new(encode_trits(&self.tail_hash))
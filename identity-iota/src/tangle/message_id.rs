@@ -6,7 +6,7 @@ use core::fmt::Display;
 use core::fmt::Formatter;
 use core::fmt::Result;
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 #[repr(transparent)]
 pub struct MessageId(Option<String>);
 
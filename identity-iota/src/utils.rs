@@ -1,7 +1,7 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use core::{cmp::Ordering, iter::once};
+use core::{cmp::Ordering, convert::TryFrom, iter::once};
 use iota::{
   crypto::ternary::{
     sponge::{CurlP81, Sponge as _},
@@ -41,6 +41,13 @@ pub(crate) fn create_address_from_trits(trits: impl AsRef<str>) -> Result<Addres
     .map(Address::from_inner_unchecked)
 }
 
+pub(crate) fn create_hash_from_trits(trits: impl AsRef<str>) -> Result<Hash> {
+  TryteBuf::try_from_str(trits.as_ref())
+    .map_err(Into::into)
+    .map(|trytes| trytes.as_trits().encode::<T1B1Buf>())
+    .map(Hash::from_inner_unchecked)
+}
+
 pub(crate) fn to_tryte(byte: u8) -> impl IntoIterator<Item = char> {
   once(iota_constants::TRYTE_ALPHABET[(byte % 27) as usize])
     .chain(once(iota_constants::TRYTE_ALPHABET[(byte / 27) as usize]))
@@ -54,6 +61,106 @@ pub(crate) fn trytes_to_utf8(string: impl AsRef<str>) -> Result<String> {
   trytes_converter::to_string(string.as_ref()).map_err(|_| Error::InvalidTryteConversion)
 }
 
+pub(crate) fn trytes_to_bytes(string: impl AsRef<str>) -> Result<Vec<u8>> {
+  // Message fragments are padded with trailing `9`s
+  let trytes: Vec<char> = string.as_ref().trim_end_matches('9').chars().collect();
+
+  trytes
+    .chunks(2)
+    .map(|chunk| {
+      let lo: usize = tryte_index(chunk[0])?;
+      let hi: usize = chunk.get(1).copied().map(tryte_index).transpose()?.unwrap_or(0);
+
+      u8::try_from(lo + hi * 27).map_err(|_| Error::InvalidTryteConversion)
+    })
+    .collect()
+}
+
+fn tryte_index(tryte: char) -> Result<usize> {
+  iota_constants::TRYTE_ALPHABET
+    .iter()
+    .position(|value| *value == tryte)
+    .ok_or(Error::InvalidTryteConversion)
+}
+
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Encodes the given `data` as a bech32 string with the human-readable part `hrp`.
+pub(crate) fn encode_bech32(hrp: &str, data: &[u8]) -> String {
+  let data: Vec<u8> = bech32_convert_bits(data);
+  let checksum: [u8; 6] = bech32_checksum(hrp, &data);
+
+  let mut output: String = String::with_capacity(hrp.len() + 1 + data.len() + checksum.len());
+
+  output.push_str(hrp);
+  output.push('1');
+  output.extend(
+    data
+      .iter()
+      .chain(checksum.iter())
+      .map(|value| BECH32_CHARSET[*value as usize] as char),
+  );
+
+  output
+}
+
+// Regroups 8-bit bytes into padded 5-bit values
+fn bech32_convert_bits(data: &[u8]) -> Vec<u8> {
+  let mut output: Vec<u8> = Vec::with_capacity((data.len() * 8 + 4) / 5);
+  let mut acc: u32 = 0;
+  let mut bits: u32 = 0;
+
+  for byte in data {
+    acc = ((acc << 8) | u32::from(*byte)) & 0xfff;
+    bits += 8;
+
+    while bits >= 5 {
+      bits -= 5;
+      output.push(((acc >> bits) & 31) as u8);
+    }
+  }
+
+  if bits > 0 {
+    output.push(((acc << (5 - bits)) & 31) as u8);
+  }
+
+  output
+}
+
+fn bech32_checksum(hrp: &str, data: &[u8]) -> [u8; 6] {
+  let values: _ = hrp
+    .bytes()
+    .map(|byte| byte >> 5)
+    .chain(once(0))
+    .chain(hrp.bytes().map(|byte| byte & 31))
+    .chain(data.iter().copied())
+    .chain([0; 6].iter().copied());
+
+  let polymod: u32 = bech32_polymod(values) ^ 1;
+  let mut checksum: [u8; 6] = [0; 6];
+
+  for (index, value) in checksum.iter_mut().enumerate() {
+    *value = ((polymod >> (5 * (5 - index))) & 31) as u8;
+  }
+
+  checksum
+}
+
+fn bech32_polymod(values: impl Iterator<Item = u8>) -> u32 {
+  const GENERATOR: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+
+  values.fold(1, |check, value| {
+    let top: u32 = check >> 25;
+    let check: u32 = ((check & 0x1ff_ffff) << 5) ^ u32::from(value);
+
+    GENERATOR
+      .iter()
+      .enumerate()
+      .filter(|(index, _)| (top >> index) & 1 == 1)
+      .fold(check, |check, (_, generator)| check ^ generator)
+  })
+}
+
 pub(crate) fn bundles_from_trytes(mut transactions: Vec<BundledTransaction>) -> Vec<Vec<BundledTransaction>> {
   transactions.sort_by(|a, b| {
     // TODO: impl Ord for Address, Tag, Hash
@@ -96,3 +203,35 @@ fn cmp_trits(a: &Trits<T1B1>, b: &Trits<T1B1>) -> Ordering {
 fn cmp_transaction(a: &BundledTransaction, b: &BundledTransaction) -> bool {
   a.address() == b.address() && a.tag() == b.tag() && a.bundle() == b.bundle()
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_trytes_to_bytes() {
+    let bytes: Vec<u8> = (0..=255).collect();
+    let trytes: String = utf8_to_trytes(&bytes);
+
+    // Trailing zero bytes are indistinguishable from padding
+    assert_eq!(trytes_to_bytes(&trytes).unwrap(), &bytes[..]);
+    assert_eq!(trytes_to_bytes(format!("{}999", trytes)).unwrap(), &bytes[..]);
+    assert_eq!(trytes_to_bytes(utf8_to_trytes("hello")).unwrap(), b"hello");
+    assert!(trytes_to_bytes("ZZ").is_err());
+  }
+
+  #[test]
+  fn test_encode_bech32() {
+    // BIP 173 test vector with the 5-bit values 0..32
+    let data: &[u8] = &[
+      0x00, 0x44, 0x32, 0x14, 0xc7, 0x42, 0x54, 0xb6, 0x35, 0xcf, 0x84, 0x65, 0x3a, 0x56, 0xd7, 0xc6, 0x75, 0xbe, 0x77,
+      0xdf,
+    ];
+
+    assert_eq!(encode_bech32("a", &[]), "a12uel5l");
+    assert_eq!(
+      encode_bech32("abcdef", data),
+      "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw"
+    );
+  }
+}
@@ -30,7 +30,7 @@
 //!   document.publish(&client).await?;
 //!
 //!   // Print the DID Document transaction link.
-//!   let network: Network = document.id().into();
+//!   let network: Network = Network::try_from_did(document.id())?;
 //!   let explore: String = format!("{}/transaction/{}", network.explorer_url(), document.message_id());
 //!
 //!   println!("DID Document Transaction > {}", explore);